@@ -7,46 +7,359 @@ sessions-running = { $count ->
     *[other] { $count } sessions running
 }
 no-sessions = No sessions
+session-project-branch = { $project } on { $branch }
+session-idle = idle { $minutes } min
+idle-notification-title = Claude session idle
+idle-notification-body = { $project } has not been used for { $minutes } minutes
+session-started-title = Claude session started
+session-ended-title = Claude session ended after { $duration }
+plan-changed-title = Claude plan changed
+plan-changed-body = { $from } → { $to }: usage limits change with the plan
+claude-desktop-running = { $count ->
+    [one] Claude Desktop running
+    *[other] { $count } Claude Desktop windows running
+}
+
+# Login
+log-in = Log in to Claude
+waiting-for-login = Waiting for login…
+login-expired = Login expired
+log-in-again = Log in again
+login-expired-tooltip = Claude login expired, log in again to resume tracking
 
 # Usage sections
 session-usage = 5-Hour Session
 weekly-usage = Weekly Usage
+window-weekly-opus = Weekly Opus
+window-weekly-sonnet = Weekly Sonnet
 
 # Reset times
 resets-in-hours = Resets in { $hours }h { $minutes }m
 resets-in-minutes = Resets in { $minutes }m
-resets-on = Resets { $date }
+resets-in-days = Resets in { $days }d { $hours }h
+resets-at = Resets at { $time }
+resets-in-at = { $countdown } ({ $time })
+block-elapsed-hours = { $hours }h { $minutes }m into this block
+block-elapsed-minutes = { $minutes }m into this block
+session-estimated = 5-Hour Session (estimated)
+estimated-share = ≈{ $percent }% of your busiest block
+estimated-no-baseline = No earlier block to compare with
+estimated-block-totals = { $tokens } tokens, { $cost } from local transcripts
 resetting = Resetting...
+percent-left = { $percent }% left
+forecast-run-out = On track to run out { $day } ~{ $time }
+forecast-lasts = On pace to last until the reset
+forecast-notification-title = Weekly limit will run out before it resets
 unknown = Unknown
 
+# Formatting (date-format uses chrono/strftime syntax)
+date-format = %b %d
+currency-usd = ${ $amount }
+
+# Lockout
+limit-reached = Usage limit reached
+locked-until = Limit reached — resets in { $countdown }
+
+# Extra usage
+extra-usage = Extra Usage
+extra-usage-spent = { $spent } spent this month
+extra-usage-spent-of = { $spent } of { $limit } spent this month
+
+# Budget
+monthly-budget = Monthly Budget
+monthly-budget-placeholder = None
+budget-spent-of = { $spent } of { $budget } spent this month
+budget-warning-title = { $percent }% of monthly budget used
+budget-exceeded-title = Monthly budget reached
+
 # Stats
 today = Today
+streak-days = { $count ->
+    [0] No streak yet
+    [one] 🔥 1-day streak
+    *[other] 🔥 { $count }-day streak
+}
 messages = { $count ->
     [one] 1 message
-    *[other] { $count } messages
+    *[other] { $formatted } messages
 }
 sessions = { $count ->
     [one] 1 session
     *[other] { $count } sessions
 }
+tokens-today = Tokens
+tokens-in-out = { $input } in / { $output } out
+cache-efficiency = Cache Efficiency
+cache-efficiency-value = { $percent }% cached ({ $cached } cached / { $fresh } fresh)
+burn-rate = Burn Rate (10 min)
+burn-rate-value = { $tokens } tokens/min { $trend }
+active-time-today = Active Time
+model-mix = Model Mix Today
+model-mix-value = { $tokens }% tokens · { $cost }% cost ({ $amount })
+model-opus = Opus
+model-sonnet = Sonnet
+model-haiku = Haiku
+model-other = Other
 cost = Cost
+cost-last-7-days = Last 7 days
+cost-this-month = This month
+top-projects = Top Projects This Week
+sync-model-pricing = Sync Model Prices
+
+# Organization (Admin API)
+tab-personal = Personal
+tab-organization = Organization
+organization-usage = Organization Usage
+org-no-admin-key = No Admin API key found. Set ANTHROPIC_ADMIN_KEY or create ~/.config/cosmic-applet-claude/admin-api-key (mode 600).
+org-since = Since { $date }
+org-input-tokens = Input tokens
+org-output-tokens = Output tokens
+org-cache-tokens = Cache tokens
+
+# Cloud providers
+provider-bedrock = AWS Bedrock
+provider-vertex = Google Vertex AI
+cloud-provider = Claude Code via { $provider }
+cloud-provider-no-limits = Plan usage limits don't apply; showing local token and cost stats
+gateway-unsupported = Usage unsupported via { $url }
+gateway-unsupported-note = The gateway does not serve the usage endpoint; showing local token and cost stats
+
+# Billing mode
+api-key-billing = API Key Billing (organization cost, month to date)
+billing-mode = Billing
+billing-mode-auto = Automatic
+billing-mode-subscription = Subscription
+billing-mode-api-key = API Key
+
+# Service status
+service-status = Anthropic: { $description }
+show-service-status = Show Service Status
+notify-incidents = Notify on Incidents
+incident-notification-title = Anthropic service incident
+
+# Claude Code version
+claude-version = Claude Code { $version }
+update-available = Update available: { $version }
+update-claude = Update Claude Code
+check-claude-updates = Check for Claude Code Updates
+
+# Applet updates
+applet-update-available = New applet version available: { $version }
+check-applet-updates = Check for Applet Updates
+
+# Threshold alerts
+notify-thresholds = Notify at Thresholds
+notify-weekly-forecast = Notify on Early Weekly Exhaustion
+notify-plan-changes = Notify When the Plan Changes
+notify-session-changes = Notify When Sessions Start or End
+notify-idle-sessions = Notify About Idle Sessions
+idle-session-after = Idle After
+snooze-duration = Snooze Duration
+threshold-warning-title = { $window } above warning level
+threshold-critical-title = { $window } above critical level
+threshold-body = { $percent }% of the limit used
+show-details = Show details
+snooze-alerts = Snooze Alerts for { $hours ->
+    [one] 1 Hour
+    *[other] { $hours } Hours
+}
+alerts-snoozed-until = Alerts snoozed until { $time } — Resume
+quiet-hours = Quiet hours
+quiet-hours-start = Quiet from
+quiet-hours-end = Quiet until
+quiet-hours-summary-title = { $count ->
+    [one] 1 notification held back during quiet hours
+    *[other] { $count } notifications held back during quiet hours
+}
+weekly-summary = Weekly summary
+weekly-summary-day = Summary day
+weekly-summary-time = Summary time
+weekly-summary-title = Your week with Claude
+weekly-summary-body = { $messages } messages in { $sessions } sessions, weekly usage peaked at { $peak }%, { $cost } spent
+
+# Accessibility
+a11y-session-usage = Session usage { $percent } percent
+a11y-weekly-usage = Weekly usage { $percent } percent
+a11y-session-remaining = Session { $percent } percent remaining
+a11y-weekly-remaining = Weekly { $percent } percent remaining
+a11y-open-usage-page = Opens the usage page on claude.ai
+a11y-stale = last update failed
 
 # Actions
 open-terminal = Open Claude Terminal
 open-claude-dir = Open Claude Directory
+export-data = Export Data…
+export-done = Usage data exported
+export-failed = Export failed
 
 # Settings
 settings = Settings
 settings-section = Settings
+settings-data = Usage Data
+claude-dir = Claude Data Directory
+claude-dir-placeholder = ~/.claude
+include-claude-desktop = Include Claude Desktop
+api-base-url = API base URL
+trust-custom-api-host = Send login token to a custom API host
+history-retention = Keep History
+history-retention-days = { $days } days
+settings-indicator = Panel Indicator
+settings-popup-sections = Popup Sections
+settings-colors = Thresholds & Colors
+settings-time = Time
+settings-numbers = Numbers
+settings-notifications = Notifications & Updates
+settings-mouse = Mouse Actions
+left-click = Left Click
+middle-click = Middle Click
+right-click = Right Click
+scroll-cycles-display = Scroll to Cycle Icon Display
+click-action-none = Nothing
+click-action-toggle-popup = Toggle Popup
+click-action-open-terminal = Open Terminal
+click-action-refresh = Refresh Usage
+click-action-cycle-display = Cycle Icon Display
+settings-hooks = Command Hooks
+settings-apps = Applications
+file-manager-command = File Manager Command
+hook-on-warning = On Warning
+hook-on-critical = On Critical
+hook-on-reset = On Window Reset
+hook-placeholder = Shell command
+restore-defaults = Restore Defaults
+restore-defaults-description = Reset every setting to its default value
+restore-defaults-confirm = Reset all settings? This cannot be undone.
+reset = Reset
+cancel = Cancel
 icon-display = Icon Display
 icon-display-session = Session Only
 icon-display-weekly = Weekly Only
 icon-display-both = Both (Dual Rings)
+icon-display-concentric = Both (Concentric)
+indicator-style = Indicator Style
+indicator-style-ring = Rings
+indicator-style-battery = Battery
+indicator-style-text = Text Only
+text-template = Text Template
 show-mascot = Show Claude Mascot
-warning-threshold = Warning
-critical-threshold = Critical
+animate-mascot = Animate Mascot
+custom-mascot = Custom Mascot
+custom-mascot-placeholder = Path to an SVG file
+show-process-badge = Show Process Badge
+hide-when-inactive = Shrink When Inactive
+session-warning-threshold = Session warning
+session-critical-threshold = Session critical
+weekly-warning-threshold = Weekly warning
+weekly-critical-threshold = Weekly critical
+opus-warning-threshold = Opus warning
+opus-critical-threshold = Opus critical
+show-daily-summary = Show Today's Summary
+popup-max-height = Popup Max Height
+popup-width = Popup Width
+popup-density = Popup Density
+popup-density-comfortable = Comfortable
+popup-density-compact = Compact
+popup-section-plan = Plan
+popup-section-session = Session Usage
+popup-section-weekly = Weekly Usage
+popup-section-models = Model Limits
+popup-section-stats = Activity & Cost
+popup-section-status = Status
+popup-section-actions = Actions
+pulse-when-critical = Pulse When Critical
+use-24-hour-clock = 24-Hour Clock
+reset-time-format = Reset Times
+reset-time-relative = Time Left
+reset-time-absolute = Clock Time
+reset-time-both = Both
+cost-precision = Cost Decimals
+group-digits = Thousands Separators
+show-reset-countdown = Show Reset Countdown
+hover-preview = Preview on Hover
+show-remaining = Show Remaining
 show-percentage = Show Percentage
+percentage-text-metric = Percentage Text Shows
+text-metric-auto = Icon Window
+text-metric-session = Session
+text-metric-weekly = Weekly
+text-metric-highest = Highest
+text-metric-cost = Today's Cost
+show-cost-text = Show Today's Cost
 poll-interval = Poll Interval
+adaptive-polling = Poll Faster Near Limits
+color-mode = Color Mode
+color-mode-threshold = Thresholds
+color-mode-gradient = Gradient
+color-mode-symbolic = Symbolic
+color-low = Low Color
+color-medium = Medium Color
+color-high = High Color
+color-mascot = Mascot Color
+color-default = Theme default
+
+# Diagnostics
+settings-diagnostics = Diagnostics
+show-raw-response = Show Raw API Response
+raw-response = Raw API Response
+raw-response-empty = No response received yet
+log-level = Log Level
+log-level-error = Errors
+log-level-warn = Warnings
+log-level-info = Info
+log-level-debug = Debug
+log-level-trace = Trace
+log-to-file = Write Log File
+log-to-file-path = Write Log File ({ $path })
+view-logs = Logs
+logs = Logs
+logs-empty = No warnings or errors logged
+view-diagnostics = Diagnostics
+diagnostics = Diagnostics
+diagnostics-paths = Files
+diagnostics-account = Account
+diagnostics-streams = Data Sources
+path-credentials = Credentials
+path-stats = Stats cache
+path-projects = Projects directory
+path-desktop-config = Claude Desktop config
+path-desktop-logs = Claude Desktop logs
+path-ok = found
+path-missing = missing
+path-unreadable = not readable
+last-fetch-ok = Last fetch: { $time }, succeeded
+last-fetch-failed = Last fetch: { $time }, failed ({ $error })
+last-fetch-never = Last fetch: not yet
+token-expires = Token expires { $time }
+token-expired = Token expired { $time }
+token-expiry-unknown = Token expiry unknown
+session-discrepancy = Session: local estimate { $local }%, API { $api }%
+session-discrepancy-note = The API counts more than this computer used; another device or claude.ai may be using the account
+stream-usage-api = Usage API
+stream-org-usage = Organization usage
+stream-stats = Stats cache
+stream-transcripts = Transcripts
+stream-processes = Processes
+stream-service-status = Service status
+stream-updated = updated { $time }
+stream-waiting = no data yet
+stream-stalled = stalled, restarted { $restarts } times
+process-scan-duration = Last process scan took { $ms } ms
+copy-report = Copy Report
+report-copied = Report copied to clipboard
 
 # Errors
 api-error = API Error
+stale-data = Last update failed, usage may be out of date ({ $error })
+error-offline = Can't reach the usage API
+error-offline-hint = You seem to be offline. Usage updates again once the connection is back.
+error-auth = The login was rejected
+error-auth-hint = The API no longer accepts the saved token. Log in again with Claude Code.
+error-rate-limited = Too many requests
+error-rate-limited-hint = The usage API is rate limiting requests. The next poll retries; a longer poll interval avoids this.
+error-server = Anthropic's servers returned an error
+error-server-hint = This is usually temporary. Service status shows ongoing incidents.
+error-parse = The usage response could not be read
+error-parse-hint = The API format may have changed. Check for an applet update, and include the diagnostics report in a bug report.
+error-untrusted-base-url = Usage requests to { $url } were blocked
+error-untrusted-base-url-hint = The login token is only sent over HTTPS, and only to hosts outside anthropic.com when "Send login token to a custom API host" is enabled in the settings.
+retry = Retry
@@ -7,46 +7,359 @@ sessions-running = { $count ->
     *[other] { $count } sessioner körs
 }
 no-sessions = Inga sessioner
+session-project-branch = { $project } på { $branch }
+session-idle = inaktiv { $minutes } min
+idle-notification-title = Claude-session inaktiv
+idle-notification-body = { $project } har inte använts på { $minutes } minuter
+session-started-title = Claude-session startad
+session-ended-title = Claude-session avslutad efter { $duration }
+plan-changed-title = Claude-abonnemanget har ändrats
+plan-changed-body = { $from } → { $to }: användningsgränserna följer abonnemanget
+claude-desktop-running = { $count ->
+    [one] Claude Desktop körs
+    *[other] { $count } Claude Desktop-fönster körs
+}
+
+# Inloggning
+log-in = Logga in på Claude
+waiting-for-login = Väntar på inloggning…
+login-expired = Inloggningen har gått ut
+log-in-again = Logga in igen
+login-expired-tooltip = Claude-inloggningen har gått ut, logga in igen för att fortsätta
 
 # Användningsavsnitt
 session-usage = 5-timmar Session
 weekly-usage = Veckovis användning
+window-weekly-opus = Opus per vecka
+window-weekly-sonnet = Sonnet per vecka
 
 # Återställnings tider
 resets-in-hours = Återställer om { $hours }h { $minutes }m
 resets-in-minutes = Återställer om { $minutes }m
-resets-on = Återställer på { $date }
+resets-in-days = Återställer om { $days }d { $hours }h
+resets-at = Återställer { $time }
+resets-in-at = { $countdown } ({ $time })
+block-elapsed-hours = { $hours }h { $minutes }m in i det här blocket
+block-elapsed-minutes = { $minutes }m in i det här blocket
+session-estimated = 5-timmarssession (uppskattad)
+estimated-share = ≈{ $percent } % av ditt mest aktiva block
+estimated-no-baseline = Inget tidigare block att jämföra med
+estimated-block-totals = { $tokens } token, { $cost } enligt lokala transkript
 resetting = Återställer...
+percent-left = { $percent }% kvar
+forecast-run-out = Med nuvarande takt tar det slut { $day } ~{ $time }
+forecast-lasts = Räcker till återställningen i nuvarande takt
+forecast-notification-title = Veckogränsen tar slut före återställningen
 unknown = Okänd
 
+# Formatering (date-format använder chrono/strftime-syntax)
+date-format = %-d %b
+currency-usd = { $amount } US$
+
+# Spärr
+limit-reached = Användningsgränsen nådd
+locked-until = Gränsen nådd — återställs om { $countdown }
+
+# Extra användning
+extra-usage = Extra användning
+extra-usage-spent = { $spent } förbrukat denna månad
+extra-usage-spent-of = { $spent } av { $limit } förbrukat denna månad
+
+# Budget
+monthly-budget = Månadsbudget
+monthly-budget-placeholder = Ingen
+budget-spent-of = { $spent } av { $budget } förbrukat denna månad
+budget-warning-title = { $percent } % av månadsbudgeten använd
+budget-exceeded-title = Månadsbudgeten är nådd
+
 # Statistik
 today = Idag
+streak-days = { $count ->
+    [0] Ingen svit än
+    [one] 🔥 1 dag i rad
+    *[other] 🔥 { $count } dagar i rad
+}
 messages = { $count ->
     [one] 1 meddelande
-    *[other] { $count } meddelanden
+    *[other] { $formatted } meddelanden
 }
 sessions = { $count ->
     [one] 1 session
     *[other] { $count } sessioner
 }
+tokens-today = Token
+tokens-in-out = { $input } in / { $output } ut
+cache-efficiency = Cacheeffektivitet
+cache-efficiency-value = { $percent } % cachat ({ $cached } cachat / { $fresh } nytt)
+burn-rate = Förbrukningstakt (10 min)
+burn-rate-value = { $tokens } token/min { $trend }
+active-time-today = Aktiv tid
+model-mix = Modellfördelning idag
+model-mix-value = { $tokens } % token · { $cost } % kostnad ({ $amount })
+model-opus = Opus
+model-sonnet = Sonnet
+model-haiku = Haiku
+model-other = Övrigt
 cost = Kostnad
+cost-last-7-days = Senaste 7 dagarna
+cost-this-month = Denna månad
+top-projects = Dyraste projekten denna vecka
+sync-model-pricing = Synkronisera modellpriser
+
+# Organisation (Admin-API)
+tab-personal = Personlig
+tab-organization = Organisation
+organization-usage = Organisationens användning
+org-no-admin-key = Ingen Admin-API-nyckel hittades. Ange ANTHROPIC_ADMIN_KEY eller skapa ~/.config/cosmic-applet-claude/admin-api-key (läge 600).
+org-since = Sedan { $date }
+org-input-tokens = Indatatokens
+org-output-tokens = Utdatatokens
+org-cache-tokens = Cachetokens
+
+# Molnleverantörer
+provider-bedrock = AWS Bedrock
+provider-vertex = Google Vertex AI
+cloud-provider = Claude Code via { $provider }
+cloud-provider-no-limits = Planens användningsgränser gäller inte; visar lokal statistik för token och kostnad
+gateway-unsupported = Användning stöds inte via { $url }
+gateway-unsupported-note = Gatewayen tillhandahåller inte användningsdata; visar lokal statistik för token och kostnad
+
+# Faktureringsläge
+api-key-billing = API-nyckelfakturering (organisationens kostnad, månaden hittills)
+billing-mode = Fakturering
+billing-mode-auto = Automatisk
+billing-mode-subscription = Prenumeration
+billing-mode-api-key = API-nyckel
+
+# Tjänststatus
+service-status = Anthropic: { $description }
+show-service-status = Visa tjänststatus
+notify-incidents = Avisera vid incidenter
+incident-notification-title = Incident hos Anthropic
+
+# Claude Code-version
+claude-version = Claude Code { $version }
+update-available = Uppdatering tillgänglig: { $version }
+update-claude = Uppdatera Claude Code
+check-claude-updates = Sök efter uppdateringar av Claude Code
+
+# Appletuppdateringar
+applet-update-available = Ny appletversion tillgänglig: { $version }
+check-applet-updates = Sök efter appletuppdateringar
+
+# Tröskelvarningar
+notify-thresholds = Avisera vid tröskelvärden
+notify-weekly-forecast = Avisera om veckogränsen tar slut tidigt
+notify-plan-changes = Avisera när abonnemanget ändras
+notify-session-changes = Avisera när sessioner startar eller avslutas
+notify-idle-sessions = Avisera om inaktiva sessioner
+idle-session-after = Inaktiv efter
+snooze-duration = Pausens längd
+threshold-warning-title = { $window } över varningsnivå
+threshold-critical-title = { $window } över kritisk nivå
+threshold-body = { $percent } % av gränsen använd
+show-details = Visa detaljer
+snooze-alerts = Pausa varningar i { $hours ->
+    [one] 1 timme
+    *[other] { $hours } timmar
+}
+alerts-snoozed-until = Varningar pausade till { $time } — Återuppta
+quiet-hours = Tysta timmar
+quiet-hours-start = Tyst från
+quiet-hours-end = Tyst till
+quiet-hours-summary-title = { $count ->
+    [one] 1 avisering hölls tillbaka under tysta timmar
+    *[other] { $count } aviseringar hölls tillbaka under tysta timmar
+}
+weekly-summary = Veckosammanfattning
+weekly-summary-day = Sammanfattningsdag
+weekly-summary-time = Sammanfattningstid
+weekly-summary-title = Din vecka med Claude
+weekly-summary-body = { $messages } meddelanden i { $sessions } sessioner, veckoanvändningen toppade på { $peak } %, { $cost } förbrukat
+
+# Tillgänglighet
+a11y-session-usage = Sessionsanvändning { $percent } procent
+a11y-weekly-usage = Veckoanvändning { $percent } procent
+a11y-session-remaining = Session { $percent } procent kvar
+a11y-weekly-remaining = Vecka { $percent } procent kvar
+a11y-open-usage-page = Öppnar användningssidan på claude.ai
+a11y-stale = senaste uppdateringen misslyckades
 
 # Åtgärder
 open-terminal = Öppna Claude terminal
 open-claude-dir = Öppna Claude katalog
+export-data = Exportera data…
+export-done = Användningsdata exporterad
+export-failed = Exporten misslyckades
 
 # Inställningar
 settings = inställningar
 settings-section = inställningar
+settings-data = Användningsdata
+claude-dir = Claude-datakatalog
+claude-dir-placeholder = ~/.claude
+include-claude-desktop = Inkludera Claude Desktop
+api-base-url = API-bas-URL
+trust-custom-api-host = Skicka inloggningstoken till en anpassad API-värd
+history-retention = Spara historik
+history-retention-days = { $days } dagar
+settings-indicator = Panelindikator
+settings-popup-sections = Popupsektioner
+settings-colors = Tröskelvärden och färger
+settings-time = Tid
+settings-numbers = Tal
+settings-notifications = Aviseringar och uppdateringar
+settings-mouse = Musåtgärder
+left-click = Vänsterklick
+middle-click = Mittenklick
+right-click = Högerklick
+scroll-cycles-display = Scrolla för att växla ikonvisning
+click-action-none = Ingenting
+click-action-toggle-popup = Visa/dölj popup
+click-action-open-terminal = Öppna terminal
+click-action-refresh = Uppdatera användning
+click-action-cycle-display = Växla ikonvisning
+settings-hooks = Kommandokrokar
+settings-apps = Program
+file-manager-command = Filhanterarkommando
+hook-on-warning = Vid varning
+hook-on-critical = Vid kritisk nivå
+hook-on-reset = Vid återställning
+hook-placeholder = Skalkommando
+restore-defaults = Återställ standardvärden
+restore-defaults-description = Återställ alla inställningar till sina standardvärden
+restore-defaults-confirm = Återställa alla inställningar? Detta kan inte ångras.
+reset = Återställ
+cancel = Avbryt
 icon-display = Ikonvisning
 icon-display-session = Session endast
 icon-display-weekly = Veckovis endast
 icon-display-both = Båda (dubbla ringar)
+icon-display-concentric = Båda (koncentriska)
+indicator-style = Indikatorstil
+indicator-style-ring = Ringar
+indicator-style-battery = Batteri
+indicator-style-text = Endast text
+text-template = Textmall
 show-mascot = Visa Claude maskot
-warning-threshold = Varning
-critical-threshold = Kritisk
+animate-mascot = Animera maskoten
+custom-mascot = Egen maskot
+custom-mascot-placeholder = Sökväg till en SVG-fil
+show-process-badge = Visa processmärke
+hide-when-inactive = Krymp när inaktiv
+session-warning-threshold = Session varning
+session-critical-threshold = Session kritisk
+weekly-warning-threshold = Vecka varning
+weekly-critical-threshold = Vecka kritisk
+opus-warning-threshold = Opus varning
+opus-critical-threshold = Opus kritisk
+show-daily-summary = Visa dagens sammanfattning
+popup-max-height = Maximal popuphöjd
+popup-width = Popupbredd
+popup-density = Popuptäthet
+popup-density-comfortable = Luftig
+popup-density-compact = Kompakt
+popup-section-plan = Abonnemang
+popup-section-session = Sessionsanvändning
+popup-section-weekly = Veckoanvändning
+popup-section-models = Modellgränser
+popup-section-stats = Aktivitet och kostnad
+popup-section-status = Status
+popup-section-actions = Åtgärder
+pulse-when-critical = Pulsera vid kritisk nivå
+use-24-hour-clock = 24-timmarsklocka
+reset-time-format = Återställningstider
+reset-time-relative = Tid kvar
+reset-time-absolute = Klockslag
+reset-time-both = Båda
+cost-precision = Decimaler för kostnad
+group-digits = Tusentalsavgränsare
+show-reset-countdown = Visa nedräkning till återställning
+hover-preview = Förhandsvisning vid hovring
+show-remaining = Visa återstående
 show-percentage = Visa procent
+percentage-text-metric = Procenttexten visar
+text-metric-auto = Ikonens fönster
+text-metric-session = Session
+text-metric-weekly = Vecka
+text-metric-highest = Högsta
+text-metric-cost = Dagens kostnad
+show-cost-text = Visa dagens kostnad
 poll-interval = Avfråga intervall
+adaptive-polling = Avfråga oftare nära gränser
+color-mode = Färgläge
+color-mode-threshold = Tröskelvärden
+color-mode-gradient = Övertoning
+color-mode-symbolic = Symbolisk
+color-low = Färg för låg
+color-medium = Färg för medel
+color-high = Färg för hög
+color-mascot = Maskotfärg
+color-default = Temats standard
+
+# Diagnostik
+settings-diagnostics = Diagnostik
+show-raw-response = Visa rått API-svar
+raw-response = Rått API-svar
+raw-response-empty = Inget svar har tagits emot än
+log-level = Loggnivå
+log-level-error = Fel
+log-level-warn = Varningar
+log-level-info = Info
+log-level-debug = Felsökning
+log-level-trace = Spårning
+log-to-file = Skriv loggfil
+log-to-file-path = Skriv loggfil ({ $path })
+view-logs = Loggar
+logs = Loggar
+logs-empty = Inga varningar eller fel loggade
+view-diagnostics = Diagnostik
+diagnostics = Diagnostik
+diagnostics-paths = Filer
+diagnostics-account = Konto
+diagnostics-streams = Datakällor
+path-credentials = Inloggningsuppgifter
+path-stats = Statistikcache
+path-projects = Projektkatalog
+path-desktop-config = Claude Desktop-konfiguration
+path-desktop-logs = Claude Desktop-loggar
+path-ok = hittad
+path-missing = saknas
+path-unreadable = inte läsbar
+last-fetch-ok = Senaste hämtning: { $time }, lyckades
+last-fetch-failed = Senaste hämtning: { $time }, misslyckades ({ $error })
+last-fetch-never = Senaste hämtning: inte än
+token-expires = Token går ut { $time }
+token-expired = Token gick ut { $time }
+token-expiry-unknown = Tokenets utgångstid okänd
+session-discrepancy = Session: lokal uppskattning { $local } %, API { $api } %
+session-discrepancy-note = API:et räknar mer än den här datorn använt; en annan enhet eller claude.ai kan använda kontot
+stream-usage-api = Användnings-API
+stream-org-usage = Organisationsanvändning
+stream-stats = Statistikcache
+stream-transcripts = Transkript
+stream-processes = Processer
+stream-service-status = Tjänststatus
+stream-updated = uppdaterad { $time }
+stream-waiting = inga data än
+stream-stalled = har stannat, omstartad { $restarts } gånger
+process-scan-duration = Senaste processgenomsökningen tog { $ms } ms
+copy-report = Kopiera rapport
+report-copied = Rapporten kopierades till urklipp
 
 # Fel
 api-error = API-Fel
+stale-data = Senaste uppdateringen misslyckades, användningen kan vara inaktuell ({ $error })
+error-offline = Användnings-API:t går inte att nå
+error-offline-hint = Du verkar vara offline. Användningen uppdateras igen när anslutningen är tillbaka.
+error-auth = Inloggningen avvisades
+error-auth-hint = API:t godtar inte längre den sparade token. Logga in igen med Claude Code.
+error-rate-limited = För många förfrågningar
+error-rate-limited-hint = Användnings-API:t begränsar förfrågningarna. Nästa hämtning försöker igen; ett längre intervall undviker detta.
+error-server = Anthropics servrar svarade med ett fel
+error-server-hint = Detta är oftast tillfälligt. Tjänstestatus visar pågående incidenter.
+error-parse = Svaret om användning kunde inte läsas
+error-parse-hint = API-formatet kan ha ändrats. Leta efter en uppdatering av appleten och bifoga diagnostikrapporten i en felrapport.
+error-untrusted-base-url = Förfrågningar om användning till { $url } blockerades
+error-untrusted-base-url-hint = Inloggningstoken skickas bara över HTTPS, och bara till värdar utanför anthropic.com när "Skicka inloggningstoken till en anpassad API-värd" är påslaget i inställningarna.
+retry = Försök igen
@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{stream, Subscription};
+use serde::Deserialize;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+const ADMIN_API_BASE: &str = "https://api.anthropic.com/v1/organizations";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const ADMIN_KEY_ENV: &str = "ANTHROPIC_ADMIN_KEY";
+const ADMIN_KEY_FILE: &str = "admin-api-key";
+
+/// Month-to-date organization usage from the Admin API
+#[derive(Debug, Clone, Default)]
+pub struct OrgUsageUpdate {
+    pub has_admin_key: bool,
+    pub period_start: Option<DateTime<Utc>>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cost_usd: f64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    data: Vec<Bucket<T>>,
+    #[serde(default)]
+    has_more: bool,
+    next_page: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Bucket<T> {
+    results: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesUsage {
+    #[serde(default)]
+    uncached_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    cache_creation: Option<CacheCreation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CacheCreation {
+    #[serde(default)]
+    ephemeral_1h_input_tokens: u64,
+    #[serde(default)]
+    ephemeral_5m_input_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CostResult {
+    /// Decimal string in the lowest currency unit (cents)
+    amount: String,
+}
+
+/// Path of the optional file holding the Admin API key
+fn get_admin_key_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|c| c.join("cosmic-applet-claude").join(ADMIN_KEY_FILE))
+}
+
+/// Read the Admin API key from the environment or the key file.
+/// The key file is ignored unless it is private to the owner (mode 0600 or stricter).
+fn read_admin_key() -> Option<String> {
+    if let Ok(key) = std::env::var(ADMIN_KEY_ENV) {
+        let key = key.trim().to_string();
+        if !key.is_empty() {
+            return Some(key);
+        }
+    }
+
+    let path = get_admin_key_path()?;
+    let metadata = std::fs::metadata(&path).ok()?;
+    if metadata.permissions().mode() & 0o077 != 0 {
+        warn!("Ignoring {}: file must not be readable by group or others", path.display());
+        return None;
+    }
+
+    let key = std::fs::read_to_string(&path).ok()?.trim().to_string();
+    (!key.is_empty()).then_some(key)
+}
+
+/// First instant of the current month in UTC
+fn month_start() -> DateTime<Utc> {
+    let now = Utc::now();
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Fetch every page of a report endpoint and collect the bucket results
+async fn fetch_report<T: for<'de> Deserialize<'de>>(
+    client: &reqwest::Client,
+    admin_key: &str,
+    endpoint: &str,
+    starting_at: DateTime<Utc>,
+) -> Result<Vec<T>, String> {
+    let mut results = Vec::new();
+    let mut page: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .get(format!("{ADMIN_API_BASE}/{endpoint}"))
+            .header("x-api-key", admin_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .query(&[
+                ("starting_at", starting_at.to_rfc3339()),
+                ("bucket_width", "1d".to_string()),
+            ]);
+        if let Some(page) = &page {
+            request = request.query(&[("page", page)]);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|_| "Network request failed".to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("Admin API error: HTTP {}", response.status().as_u16()));
+        }
+
+        let text = response.text().await.map_err(|_| "Failed to read response".to_string())?;
+        let parsed: Page<T> = serde_json::from_str(&text).map_err(|_| "Failed to parse response".to_string())?;
+
+        results.extend(parsed.data.into_iter().flat_map(|b| b.results));
+
+        match parsed.next_page {
+            Some(next) if parsed.has_more => page = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok(results)
+}
+
+async fn fetch_org_usage(client: &reqwest::Client, admin_key: &str) -> Result<OrgUsageUpdate, String> {
+    let starting_at = month_start();
+
+    let usage: Vec<MessagesUsage> =
+        fetch_report(client, admin_key, "usage_report/messages", starting_at).await?;
+    let costs: Vec<CostResult> = fetch_report(client, admin_key, "cost_report", starting_at).await?;
+
+    let mut update = OrgUsageUpdate {
+        has_admin_key: true,
+        period_start: Some(starting_at),
+        ..Default::default()
+    };
+
+    for entry in usage {
+        update.input_tokens += entry.uncached_input_tokens;
+        update.output_tokens += entry.output_tokens;
+        update.cache_read_tokens += entry.cache_read_input_tokens;
+        if let Some(cache) = entry.cache_creation {
+            update.cache_creation_tokens += cache.ephemeral_1h_input_tokens + cache.ephemeral_5m_input_tokens;
+        }
+    }
+
+    update.cost_usd = costs
+        .iter()
+        .filter_map(|c| c.amount.parse::<f64>().ok())
+        .sum::<f64>()
+        / 100.0;
+
+    Ok(update)
+}
+
+/// Subscription that polls the organization usage and cost reports.
+/// Bumping `generation` restarts it, which polls right away.
+pub fn admin_subscription(poll_interval_minutes: u32, generation: u64) -> Subscription<OrgUsageUpdate> {
+    let interval = poll_interval_minutes.max(1);
+
+    Subscription::run_with_id(
+        std::sync::Arc::new(("claude-admin-usage", interval, generation)),
+        stream::channel(1, move |mut sender| async move {
+            let poll_duration = Duration::from_secs(interval as u64 * 60);
+            let client = reqwest::Client::new();
+
+            // Initial delay to let the UI settle
+            tokio::time::sleep(Duration::from_secs(2)).await;
+
+            loop {
+                let update = match read_admin_key() {
+                    Some(admin_key) => {
+                        debug!("Fetching organization usage data");
+                        match fetch_org_usage(&client, &admin_key).await {
+                            Ok(update) => update,
+                            Err(e) => {
+                                error!("Failed to fetch organization usage: {}", e);
+                                OrgUsageUpdate {
+                                    has_admin_key: true,
+                                    last_error: Some(e),
+                                    ..Default::default()
+                                }
+                            }
+                        }
+                    }
+                    None => {
+                        debug!("No Admin API key configured");
+                        OrgUsageUpdate::default()
+                    }
+                };
+
+                let _ = sender.send(update).await;
+                tokio::time::sleep(poll_duration).await;
+            }
+        }),
+    )
+}
@@ -3,15 +3,29 @@
 use chrono::{DateTime, Utc};
 use cosmic::iced::futures::SinkExt;
 use cosmic::iced::{stream, Subscription};
-use serde::Deserialize;
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
 use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
 use tracing::{debug, error, warn};
 
 const DEFAULT_POLL_INTERVAL_MINUTES: u32 = 60;
-const USAGE_API_URL: &str = "https://api.anthropic.com/api/oauth/usage";
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const USAGE_API_PATH: &str = "/api/oauth/usage";
 
-#[derive(Debug, Clone, Default)]
+/// API base URL chosen in the settings; `None` falls back to `ANTHROPIC_BASE_URL`
+static BASE_URL_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+/// The user opted in to sending the OAuth token to a base URL outside anthropic.com
+static TRUST_CUSTOM_HOST: AtomicBool = AtomicBool::new(false);
+
+/// Serializable so the primary instance can share it with duplicates over D-Bus
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UsageUpdate {
     pub has_credentials: bool,
     pub subscription_type: String,
@@ -21,7 +35,86 @@ pub struct UsageUpdate {
     pub weekly_reset_time: Option<DateTime<Utc>>,
     pub opus_usage_percent: f32,
     pub sonnet_usage_percent: f32,
+    pub extra_usage: Option<ExtraUsage>,
+    /// Every window besides five_hour and seven_day (per-model, monthly, ...), sorted by name
+    pub other_windows: Vec<NamedWindow>,
+    /// Last response body, pretty-printed with identifying fields redacted (for the debug view)
+    pub raw_response: Option<String>,
+    /// Credentials exist but the token has expired; logging in again fixes it
+    pub token_expired: bool,
+    /// Requests go through a gateway (custom base URL) that does not serve the usage endpoint
+    pub usage_unsupported: bool,
     pub last_error: Option<String>,
+    /// Category of `last_error`, for a specific message and suggested action in the popup
+    pub error_kind: Option<ErrorKind>,
+}
+
+/// Why a usage fetch failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorKind {
+    /// The request never got an answer: no network, DNS failure, timeout
+    Offline,
+    /// The API rejected the token (HTTP 401/403) although it has not expired locally
+    AuthExpired,
+    /// HTTP 429
+    RateLimited,
+    /// HTTP 5xx
+    ServerError,
+    /// The response was not the JSON the applet expects
+    ParseError,
+    /// The base URL is not HTTPS, or points outside anthropic.com without the user trusting it
+    UntrustedBaseUrl,
+    /// Any other failure (unexpected status codes and the like)
+    Other,
+}
+
+/// A failed fetch: its category and a short description for the logs and diagnostics
+#[derive(Debug, Clone)]
+struct FetchError {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl FetchError {
+    fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Category of a non-success HTTP status
+    fn from_status(status: StatusCode) -> Self {
+        let kind = match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorKind::AuthExpired,
+            StatusCode::TOO_MANY_REQUESTS => ErrorKind::RateLimited,
+            status if status.is_server_error() => ErrorKind::ServerError,
+            _ => ErrorKind::Other,
+        };
+        Self::new(kind, format!("API error: HTTP {}", status.as_u16()))
+    }
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// A usage window reported under its API key name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedWindow {
+    pub name: String,
+    pub utilization: f32,
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+/// Pay-as-you-go spillover beyond the plan's included usage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtraUsage {
+    pub used_usd: f64,
+    pub monthly_limit_usd: Option<f64>,
+    pub utilization: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,12 +133,15 @@ struct OAuthCredentials {
     subscription_type: Option<String>,
 }
 
+/// Usage windows the panel rings are built from; everything else is listed generically
+const PRIMARY_WINDOWS: [&str; 2] = ["five_hour", "seven_day"];
+
 #[derive(Debug, Deserialize)]
 struct UsageResponse {
-    five_hour: Option<UsageWindow>,
-    seven_day: Option<UsageWindow>,
-    seven_day_opus: Option<ModelUsage>,
-    seven_day_sonnet: Option<ModelUsage>,
+    extra_usage: Option<ExtraUsageResponse>,
+    /// Remaining top-level keys; any object with a `utilization` is treated as a usage window
+    #[serde(flatten)]
+    windows: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,20 +150,100 @@ struct UsageWindow {
     resets_at: Option<String>,
 }
 
+impl UsageWindow {
+    fn reset_time(&self) -> Option<DateTime<Utc>> {
+        self.resets_at
+            .as_ref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+impl UsageResponse {
+    /// Look up a window by name; absent, null or malformed windows yield `None`
+    fn window(&self, name: &str) -> Option<UsageWindow> {
+        self.windows
+            .get(name)
+            .and_then(|value| UsageWindow::deserialize(value).ok())
+    }
+
+    fn other_windows(&self) -> Vec<NamedWindow> {
+        self.windows
+            .keys()
+            .filter(|name| !PRIMARY_WINDOWS.contains(&name.as_str()))
+            .filter_map(|name| {
+                let window = self.window(name)?;
+                Some(NamedWindow {
+                    name: name.clone(),
+                    utilization: window.utilization,
+                    resets_at: window.reset_time(),
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Deserialize)]
-struct ModelUsage {
-    utilization: f32,
+struct ExtraUsageResponse {
+    #[serde(default)]
+    is_enabled: bool,
+    monthly_limit: Option<f64>, // cents
+    used_credits: Option<f64>,  // cents
+    utilization: Option<f32>,
 }
 
-fn get_credentials_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join(".credentials.json"))
+impl ExtraUsageResponse {
+    fn into_extra_usage(self) -> Option<ExtraUsage> {
+        if !self.is_enabled {
+            return None;
+        }
+        Some(ExtraUsage {
+            used_usd: self.used_credits.unwrap_or(0.0) / 100.0,
+            monthly_limit_usd: self.monthly_limit.map(|cents| cents / 100.0),
+            utilization: self.utilization,
+        })
+    }
+}
+
+/// Path of the OAuth credentials file written by Claude Code
+pub fn get_credentials_path() -> Option<PathBuf> {
+    super::claude_dir().map(|dir| dir.join(".credentials.json"))
 }
 
-fn read_credentials() -> Option<(String, String)> {
+/// Whether Claude Code is configured to bill through a Console API key
+pub fn api_key_configured() -> bool {
+    std::env::var("ANTHROPIC_API_KEY").is_ok_and(|key| !key.trim().is_empty())
+}
+
+fn read_oauth_credentials() -> Option<OAuthCredentials> {
     let path = get_credentials_path()?;
     let content = std::fs::read_to_string(&path).ok()?;
     let creds: Credentials = serde_json::from_str(&content).ok()?;
-    let oauth = creds.claude_ai_oauth?;
+    creds.claude_ai_oauth
+}
+
+/// When the stored OAuth token expires, if credentials exist and record it
+pub fn token_expiry() -> Option<DateTime<Utc>> {
+    let expires_at_ms = read_oauth_credentials()?.expires_at?;
+    DateTime::from_timestamp(expires_at_ms / 1000, 0)
+}
+
+/// Why no usable OAuth token is available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CredentialsError {
+    /// No credentials file, or no OAuth login in it
+    Missing,
+    /// A login exists but its token has expired
+    Expired,
+}
+
+/// Whether usable (present and unexpired) OAuth credentials exist
+pub fn has_valid_credentials() -> bool {
+    read_credentials().is_ok()
+}
+
+fn read_credentials() -> Result<(String, String), CredentialsError> {
+    let oauth = read_oauth_credentials().ok_or(CredentialsError::Missing)?;
 
     // Check if token is expired (expires_at is Unix timestamp in milliseconds)
     // Add 5-minute buffer to prevent mid-request expiration
@@ -77,45 +253,264 @@ fn read_credentials() -> Option<(String, String)> {
             let buffer = chrono::Duration::minutes(5);
             if expiry < Utc::now() + buffer {
                 warn!("OAuth token has expired or is about to expire");
-                return None;
+                return Err(CredentialsError::Expired);
             }
         }
     }
 
     let subscription = oauth.subscription_type.unwrap_or_else(|| "Unknown".to_string());
-    Some((oauth.access_token, subscription))
+    Ok((oauth.access_token, subscription))
 }
 
-async fn fetch_usage(client: &reqwest::Client, access_token: &str) -> Result<UsageResponse, String> {
-    let response = client
-        .get(USAGE_API_URL)
+/// Keys whose values are replaced before the response is shown in the debug view
+const REDACTED_KEYS: [&str; 6] = ["token", "email", "uuid", "account", "organization", "key"];
+
+/// Pretty-print a response with any identifying values redacted
+fn sanitize_response(json: &serde_json::Value) -> String {
+    fn redact(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    let key = key.to_lowercase();
+                    if REDACTED_KEYS.iter().any(|redacted| key.contains(redacted)) && !value.is_null() {
+                        *value = serde_json::Value::String("[redacted]".to_string());
+                    } else {
+                        redact(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+            _ => {}
+        }
+    }
+
+    let mut json = json.clone();
+    redact(&mut json);
+    serde_json::to_string_pretty(&json).unwrap_or_default()
+}
+
+/// Last successfully parsed response and the validators to revalidate it with
+struct CachedResponse {
+    access_token: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    update: UsageUpdate,
+}
+
+/// Response body plus its cache validators
+struct FetchedUsage {
+    json: serde_json::Value,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+enum FetchResult {
+    Modified(FetchedUsage),
+    /// HTTP 304: the cached response is still current
+    NotModified,
+    /// A gateway answered without usage data, so polling it again will not help
+    Unsupported,
+}
+
+/// Use another API base URL for the usage endpoint, e.g. a LiteLLM or corporate gateway.
+/// The OAuth token only goes to hosts outside anthropic.com when `trust_custom_host` is set.
+pub fn set_base_url(url: Option<&str>, trust_custom_host: bool) {
+    let url = url
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(|url| url.trim_end_matches('/').to_string());
+    if let Ok(mut current) = BASE_URL_OVERRIDE.write() {
+        *current = url;
+    }
+    TRUST_CUSTOM_HOST.store(trust_custom_host, Ordering::Relaxed);
+}
+
+/// Refuse to send the OAuth token over plain HTTP to anything but this machine, or to a host
+/// outside anthropic.com the user has not trusted
+fn check_base_url(base_url: &str, trust_custom_host: bool) -> Result<(), FetchError> {
+    let untrusted = |message: &str| FetchError::new(ErrorKind::UntrustedBaseUrl, message);
+    let url = reqwest::Url::parse(base_url).map_err(|_| untrusted("Invalid API base URL"))?;
+    let host = url.host_str().unwrap_or_default();
+    // A gateway on localhost (e.g. LiteLLM) never puts the token on the network
+    let loopback = host == "localhost"
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    if url.scheme() != "https" && !(url.scheme() == "http" && loopback) {
+        return Err(untrusted("API base URL is not HTTPS"));
+    }
+    let anthropic = host == "anthropic.com" || host.ends_with(".anthropic.com");
+    if !anthropic && !trust_custom_host {
+        return Err(untrusted("API base URL is outside anthropic.com and not trusted"));
+    }
+    Ok(())
+}
+
+/// API base URL: the configured one, else `ANTHROPIC_BASE_URL`, else api.anthropic.com
+pub fn base_url() -> String {
+    if let Some(url) = BASE_URL_OVERRIDE.read().ok().and_then(|url| url.clone()) {
+        return url;
+    }
+    std::env::var("ANTHROPIC_BASE_URL")
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
+
+async fn fetch_usage(
+    client: &reqwest::Client,
+    access_token: &str,
+    cached: Option<&CachedResponse>,
+) -> Result<FetchResult, FetchError> {
+    let base_url = base_url();
+    check_base_url(&base_url, TRUST_CUSTOM_HOST.load(Ordering::Relaxed))?;
+    let via_gateway = base_url != DEFAULT_BASE_URL;
+    let mut request = client
+        .get(format!("{base_url}{USAGE_API_PATH}"))
         .header("Authorization", format!("Bearer {}", access_token))
-        .header("anthropic-beta", "oauth-2025-04-20")
+        .header("anthropic-beta", "oauth-2025-04-20");
+
+    // Validators are only reused for the token they were issued to
+    if let Some(cached) = cached.filter(|cached| cached.access_token == access_token) {
+        if let Some(etag) = &cached.etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request
         .send()
         .await
-        .map_err(|_| "Network request failed".to_string())?;
+        .map_err(|_| FetchError::new(ErrorKind::Offline, "Network request failed"))?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(FetchResult::NotModified);
+    }
+
+    // Gateways that only proxy the model endpoints reject or do not know the usage path
+    if via_gateway
+        && matches!(
+            response.status(),
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        )
+    {
+        warn!("Gateway {} does not serve usage data (HTTP {})", base_url, response.status().as_u16());
+        return Ok(FetchResult::Unsupported);
+    }
 
     if !response.status().is_success() {
-        let status = response.status();
         // Log status only, don't expose raw API response body
-        return Err(format!("API error: HTTP {}", status.as_u16()));
+        return Err(FetchError::from_status(response.status()));
+    }
+
+    let validator = |name: header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = validator(header::ETAG);
+    let last_modified = validator(header::LAST_MODIFIED);
+
+    let text = response
+        .text()
+        .await
+        .map_err(|_| FetchError::new(ErrorKind::Offline, "Failed to read response"))?;
+    let json = match serde_json::from_str::<serde_json::Value>(&text) {
+        Ok(json) => json,
+        Err(_) if via_gateway => {
+            warn!("Gateway {} answered the usage request with something other than JSON", base_url);
+            return Ok(FetchResult::Unsupported);
+        }
+        Err(_) => return Err(FetchError::new(ErrorKind::ParseError, "Failed to parse response")),
+    };
+    Ok(FetchResult::Modified(FetchedUsage {
+        json,
+        etag,
+        last_modified,
+    }))
+}
+
+/// Build an update from a successfully parsed usage response
+fn usage_update(usage: UsageResponse, subscription_type: String) -> UsageUpdate {
+    let five_hour = usage.window("five_hour");
+    let seven_day = usage.window("seven_day");
+    let session_reset = five_hour.as_ref().and_then(UsageWindow::reset_time);
+    let weekly_reset = seven_day.as_ref().and_then(UsageWindow::reset_time);
+
+    let session_pct = five_hour.as_ref().map(|w| w.utilization).unwrap_or(0.0);
+    let weekly_pct = seven_day.as_ref().map(|w| w.utilization).unwrap_or(0.0);
+    let opus_pct = usage.window("seven_day_opus").map(|w| w.utilization).unwrap_or(0.0);
+    let sonnet_pct = usage.window("seven_day_sonnet").map(|w| w.utilization).unwrap_or(0.0);
+    let other_windows = usage.other_windows();
+    let extra_usage = usage.extra_usage.and_then(ExtraUsageResponse::into_extra_usage);
+
+    debug!(
+        "Parsed usage: session={:.1}%, weekly={:.1}%, opus={:.1}%, sonnet={:.1}%",
+        session_pct, weekly_pct, opus_pct, sonnet_pct
+    );
+
+    UsageUpdate {
+        has_credentials: true,
+        subscription_type,
+        session_usage_percent: session_pct,
+        session_reset_time: session_reset,
+        weekly_usage_percent: weekly_pct,
+        weekly_reset_time: weekly_reset,
+        opus_usage_percent: opus_pct,
+        sonnet_usage_percent: sonnet_pct,
+        extra_usage,
+        other_windows,
+        raw_response: None,
+        token_expired: false,
+        usage_unsupported: false,
+        last_error: None,
+        error_kind: None,
     }
+}
 
-    let text = response.text().await.map_err(|_| "Failed to read response".to_string())?;
-    serde_json::from_str::<UsageResponse>(&text).map_err(|_| "Failed to parse response".to_string())
+/// Update for a failed poll that carries the last successful usage values forward,
+/// so a transient failure does not drop the panel to 0% until the next good poll
+fn error_update(
+    cached: Option<&CachedResponse>,
+    subscription_type: String,
+    raw_response: Option<String>,
+    error: FetchError,
+) -> UsageUpdate {
+    let last = cached.map(|cached| cached.update.clone()).unwrap_or_default();
+    UsageUpdate {
+        has_credentials: true,
+        subscription_type,
+        raw_response,
+        last_error: Some(error.message),
+        error_kind: Some(error.kind),
+        ..last
+    }
 }
 
-pub fn api_subscription(poll_interval_minutes: u32) -> Subscription<UsageUpdate> {
-    let interval = if poll_interval_minutes > 0 {
+fn poll_duration(poll_interval_minutes: u32) -> Duration {
+    let minutes = if poll_interval_minutes > 0 {
         poll_interval_minutes
     } else {
         DEFAULT_POLL_INTERVAL_MINUTES
     };
+    Duration::from_secs(minutes as u64 * 60)
+}
 
+/// Subscription that polls the usage API; bumping `generation` restarts it, which polls right away.
+/// Interval changes arrive over `poll_interval` and reschedule the next poll without restarting.
+pub fn api_subscription(poll_interval: watch::Receiver<u32>, generation: u64) -> Subscription<UsageUpdate> {
     Subscription::run_with_id(
-        std::sync::Arc::new(("claude-api-usage", interval)),
+        std::sync::Arc::new(("claude-api-usage", generation)),
         stream::channel(1, move |mut sender| async move {
-            let poll_duration = Duration::from_secs(interval as u64 * 60);
+            let mut poll_interval = poll_interval;
+            let mut cached: Option<CachedResponse> = None;
             // Create client once and reuse for connection pooling
             let client = reqwest::Client::new();
 
@@ -124,58 +519,70 @@ pub fn api_subscription(poll_interval_minutes: u32) -> Subscription<UsageUpdate>
 
             loop {
                 let update = match read_credentials() {
-                    Some((token, subscription_type)) => {
+                    Ok((token, subscription_type)) => {
                         debug!("Fetching Claude API usage data");
-                        match fetch_usage(&client, &token).await {
-                            Ok(usage) => {
-                                let session_reset = usage
-                                    .five_hour
-                                    .as_ref()
-                                    .and_then(|w| w.resets_at.as_ref())
-                                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                                    .map(|dt| dt.with_timezone(&Utc));
-
-                                let weekly_reset = usage
-                                    .seven_day
-                                    .as_ref()
-                                    .and_then(|w| w.resets_at.as_ref())
-                                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                                    .map(|dt| dt.with_timezone(&Utc));
-
-                                let session_pct = usage.five_hour.as_ref().map(|w| w.utilization).unwrap_or(0.0);
-                                let weekly_pct = usage.seven_day.as_ref().map(|w| w.utilization).unwrap_or(0.0);
-                                let opus_pct = usage.seven_day_opus.as_ref().map(|m| m.utilization).unwrap_or(0.0);
-                                let sonnet_pct = usage.seven_day_sonnet.as_ref().map(|m| m.utilization).unwrap_or(0.0);
-
-                                debug!(
-                                    "Parsed usage: session={:.1}%, weekly={:.1}%, opus={:.1}%, sonnet={:.1}%",
-                                    session_pct, weekly_pct, opus_pct, sonnet_pct
-                                );
-
-                                UsageUpdate {
-                                    has_credentials: true,
+                        match fetch_usage(&client, &token, cached.as_ref()).await {
+                            Ok(FetchResult::NotModified) => match &cached {
+                                Some(cached) => {
+                                    debug!("Usage unchanged since last poll");
+                                    UsageUpdate {
+                                        subscription_type,
+                                        ..cached.update.clone()
+                                    }
+                                }
+                                None => error_update(
+                                    None,
                                     subscription_type,
-                                    session_usage_percent: session_pct,
-                                    session_reset_time: session_reset,
-                                    weekly_usage_percent: weekly_pct,
-                                    weekly_reset_time: weekly_reset,
-                                    opus_usage_percent: opus_pct,
-                                    sonnet_usage_percent: sonnet_pct,
-                                    last_error: None,
+                                    None,
+                                    FetchError::new(ErrorKind::Other, "Unexpected HTTP 304"),
+                                ),
+                            },
+                            Ok(FetchResult::Unsupported) => UsageUpdate {
+                                has_credentials: true,
+                                subscription_type,
+                                usage_unsupported: true,
+                                ..Default::default()
+                            },
+                            Ok(FetchResult::Modified(fetched)) => {
+                                let raw_response = Some(sanitize_response(&fetched.json));
+                                match UsageResponse::deserialize(fetched.json) {
+                                    Ok(usage) => {
+                                        let update = UsageUpdate {
+                                            raw_response,
+                                            ..usage_update(usage, subscription_type)
+                                        };
+                                        cached = Some(CachedResponse {
+                                            access_token: token,
+                                            etag: fetched.etag,
+                                            last_modified: fetched.last_modified,
+                                            update: update.clone(),
+                                        });
+                                        update
+                                    }
+                                    Err(e) => {
+                                        error!("Failed to parse usage response: {}", e);
+                                        error_update(
+                                            cached.as_ref(),
+                                            subscription_type,
+                                            raw_response,
+                                            FetchError::new(ErrorKind::ParseError, "Failed to parse response"),
+                                        )
+                                    }
                                 }
                             }
                             Err(e) => {
                                 error!("Failed to fetch usage: {}", e);
-                                UsageUpdate {
-                                    has_credentials: true,
-                                    subscription_type,
-                                    last_error: Some(e),
-                                    ..Default::default()
-                                }
+                                error_update(cached.as_ref(), subscription_type, None, e)
                             }
                         }
                     }
-                    None => {
+                    Err(CredentialsError::Expired) => UsageUpdate {
+                        has_credentials: false,
+                        subscription_type: "Login expired".to_string(),
+                        token_expired: true,
+                        ..Default::default()
+                    },
+                    Err(CredentialsError::Missing) => {
                         debug!("No valid credentials found");
                         UsageUpdate {
                             has_credentials: false,
@@ -186,8 +593,74 @@ pub fn api_subscription(poll_interval_minutes: u32) -> Subscription<UsageUpdate>
                 };
 
                 let _ = sender.send(update).await;
-                tokio::time::sleep(poll_duration).await;
+
+                // Wait out the interval, measured from this poll even if it changes meanwhile
+                let fetched_at = Instant::now();
+                loop {
+                    let deadline = fetched_at + poll_duration(*poll_interval.borrow_and_update());
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(deadline) => break,
+                        changed = poll_interval.changed() => {
+                            if changed.is_err() {
+                                // The applet dropped the sender; keep the last interval
+                                tokio::time::sleep_until(deadline).await;
+                                break;
+                            }
+                        }
+                    }
+                }
             }
         }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_status_classifies_http_errors() {
+        for (status, kind) in [
+            (StatusCode::UNAUTHORIZED, ErrorKind::AuthExpired),
+            (StatusCode::FORBIDDEN, ErrorKind::AuthExpired),
+            (StatusCode::TOO_MANY_REQUESTS, ErrorKind::RateLimited),
+            (StatusCode::INTERNAL_SERVER_ERROR, ErrorKind::ServerError),
+            (StatusCode::SERVICE_UNAVAILABLE, ErrorKind::ServerError),
+            (StatusCode::BAD_REQUEST, ErrorKind::Other),
+            (StatusCode::NOT_FOUND, ErrorKind::Other),
+        ] {
+            assert_eq!(FetchError::from_status(status).kind, kind, "HTTP {status}");
+        }
+    }
+
+    #[test]
+    fn from_status_message_carries_the_status_code() {
+        assert_eq!(FetchError::from_status(StatusCode::BAD_GATEWAY).message, "API error: HTTP 502");
+    }
+
+    #[test]
+    fn check_base_url_accepts_anthropic_over_https() {
+        assert!(check_base_url("https://api.anthropic.com", false).is_ok());
+        assert!(check_base_url("https://anthropic.com", false).is_ok());
+        assert!(check_base_url("http://api.anthropic.com", true).is_err());
+        // Lookalike hosts are not anthropic.com
+        assert!(check_base_url("https://api.anthropic.com.example.org", false).is_err());
+        assert!(check_base_url("https://notanthropic.com", false).is_err());
+    }
+
+    #[test]
+    fn check_base_url_requires_trust_for_custom_hosts() {
+        assert!(check_base_url("https://gateway.example.org", false).is_err());
+        assert!(check_base_url("https://gateway.example.org", true).is_ok());
+        assert!(check_base_url("http://gateway.example.org", true).is_err());
+        assert!(check_base_url("not a url", true).is_err());
+    }
+
+    #[test]
+    fn check_base_url_allows_plain_http_to_loopback_hosts() {
+        for url in ["http://localhost:4000", "http://127.0.0.1:4000", "http://[::1]:4000"] {
+            assert!(check_base_url(url, true).is_ok(), "{url}");
+            assert!(check_base_url(url, false).is_err(), "{url}");
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use super::api::{ExtraUsage, NamedWindow, UsageUpdate};
+use super::process::{CliSession, ProcessUpdate};
+use super::stats::StatsUpdate;
+use super::DataSource;
+use chrono::{Duration as ChronoDuration, Local, Utc};
+use cosmic::iced::{futures::SinkExt, Subscription};
+use cosmic::iced_futures::stream;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+/// How often the scripted data advances
+const STEP: Duration = Duration::from_secs(5);
+
+/// Steps in one pass of the script before it starts over
+const SCRIPT_STEPS: u32 = 40;
+
+/// Scripted data for UI development, screenshots and machines without Claude Code installed.
+/// Usage climbs through the warning and critical levels, resets and starts over.
+pub struct Mock;
+
+impl DataSource for Mock {
+    fn usage(&self, _poll_interval: watch::Receiver<u32>, generation: u64) -> Subscription<UsageUpdate> {
+        scripted(("claude-mock-usage", generation), usage_at)
+    }
+
+    fn stats(&self, generation: u64) -> Subscription<StatsUpdate> {
+        scripted(("claude-mock-stats", generation), stats_at)
+    }
+
+    fn processes(&self, generation: u64) -> Subscription<ProcessUpdate> {
+        scripted(("claude-mock-processes", generation), |step| {
+            let count = (step / 10 % 4) as usize;
+            ProcessUpdate {
+                count,
+                sessions: mock_sessions(count),
+                desktop_count: (step / 20 % 2) as usize,
+                scan_duration: Duration::from_millis(2),
+            }
+        })
+    }
+
+    fn is_mock(&self) -> bool {
+        true
+    }
+}
+
+/// Emit `script(step)` every `STEP`, starting right away
+fn scripted<T: Send + 'static>(id: (&'static str, u64), script: fn(u32) -> T) -> Subscription<T> {
+    Subscription::run_with_id(
+        std::sync::Arc::new(id),
+        stream::channel(1, move |mut output| async move {
+            let mut step = 0;
+            loop {
+                let _ = output.send(script(step % SCRIPT_STEPS)).await;
+                step += 1;
+                tokio::time::sleep(STEP).await;
+            }
+        }),
+    )
+}
+
+/// The first `count` of a fixed set of sessions, two of them in worktrees of the same repository
+/// and the last one idle for 45 minutes
+fn mock_sessions(count: usize) -> Vec<CliSession> {
+    [
+        ("/home/user/src/applet", Some("main"), 0),
+        ("/home/user/src/applet-worktrees/panel-ring", Some("panel-ring"), 2),
+        ("/home/user/notes", None, 45),
+    ]
+    .into_iter()
+    .take(count)
+    .enumerate()
+    .map(|(index, (cwd, branch, idle_minutes))| CliSession {
+        pid: 4200 + index as u32,
+        cwd: cwd.into(),
+        branch: branch.map(str::to_string),
+        last_activity: SystemTime::now().checked_sub(Duration::from_secs(idle_minutes * 60)),
+    })
+    .collect()
+}
+
+fn usage_at(step: u32) -> UsageUpdate {
+    let progress = step as f32 / (SCRIPT_STEPS - 1) as f32;
+    let now = Utc::now();
+    let session_usage_percent = (progress * 105.0).min(100.0);
+    let weekly_usage_percent = 20.0 + progress * 60.0;
+    let opus_usage_percent = 35.0 + progress * 60.0;
+
+    UsageUpdate {
+        has_credentials: true,
+        subscription_type: "Max (mock)".to_string(),
+        session_usage_percent,
+        session_reset_time: Some(now + ChronoDuration::minutes(((1.0 - progress) * 300.0) as i64 + 1)),
+        weekly_usage_percent,
+        weekly_reset_time: Some(now + ChronoDuration::days(3)),
+        opus_usage_percent,
+        sonnet_usage_percent: weekly_usage_percent / 2.0,
+        extra_usage: Some(ExtraUsage {
+            used_usd: 12.5,
+            monthly_limit_usd: Some(50.0),
+            utilization: Some(25.0),
+        }),
+        other_windows: vec![
+            NamedWindow {
+                name: "seven_day_opus".to_string(),
+                utilization: opus_usage_percent,
+                resets_at: Some(now + ChronoDuration::days(3)),
+            },
+            NamedWindow {
+                name: "seven_day_sonnet".to_string(),
+                utilization: weekly_usage_percent / 2.0,
+                resets_at: Some(now + ChronoDuration::days(3)),
+            },
+        ],
+        raw_response: Some(format!(
+            "{{\n  \"five_hour\": {{ \"utilization\": {session_usage_percent:.1} }},\n  \"seven_day\": {{ \"utilization\": {weekly_usage_percent:.1} }}\n}}"
+        )),
+        token_expired: false,
+        usage_unsupported: false,
+        last_error: None,
+        error_kind: None,
+    }
+}
+
+fn stats_at(step: u32) -> StatsUpdate {
+    let today = Local::now().date_naive();
+    let daily: Vec<_> = (0..14)
+        .map(|days_ago| {
+            let messages = if days_ago == 0 { 40 + step * 3 } else { 60 + (days_ago * 37) % 90 };
+            crate::history::DailyStats {
+                date: (today - ChronoDuration::days(days_ago as i64)).format("%Y-%m-%d").to_string(),
+                messages,
+                sessions: messages / 20 + 1,
+                cost_usd: messages as f64 * 0.12,
+            }
+        })
+        .collect();
+
+    let week = &daily[..7];
+    StatsUpdate {
+        today_messages: daily[0].messages,
+        today_sessions: daily[0].sessions,
+        total_messages: daily.iter().map(|d| d.messages).sum(),
+        total_sessions: daily.iter().map(|d| d.sessions).sum(),
+        total_cost_usd: daily.iter().map(|d| d.cost_usd).sum(),
+        today_cost_usd: daily[0].cost_usd,
+        week_cost_usd: week.iter().map(|d| d.cost_usd).sum(),
+        month_cost_usd: daily.iter().map(|d| d.cost_usd).sum(),
+        streak_days: daily.len() as u32,
+        daily,
+    }
+}
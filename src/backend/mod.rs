@@ -1,5 +1,181 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+pub mod admin;
 pub mod api;
+pub mod mock;
+pub mod pricing;
 pub mod process;
+pub mod release;
 pub mod stats;
+pub mod status;
+pub mod transcripts;
+pub mod version;
+
+use cosmic::iced::Subscription;
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+use tokio::sync::watch;
+
+/// Environment variable that swaps in scripted mock data
+const MOCK_ENV: &str = "COSMIC_APPLET_CLAUDE_MOCK";
+
+/// Variables Claude Code reads to route requests through a cloud provider
+const BEDROCK_ENV: &str = "CLAUDE_CODE_USE_BEDROCK";
+const VERTEX_ENV: &str = "CLAUDE_CODE_USE_VERTEX";
+
+/// Who serves Claude Code's requests, and so whether the Anthropic usage endpoint applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// Claude subscription or Console API, with usage windows from the Anthropic API
+    Anthropic,
+    /// AWS Bedrock; billed by AWS without usage windows
+    Bedrock,
+    /// Google Vertex AI; billed by Google without usage windows
+    Vertex,
+}
+
+/// Whether a Claude Code boolean setting is switched on ("1", "true", ...)
+fn is_enabled(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on")
+}
+
+/// Detect a Bedrock or Vertex setup from the environment or the `env` block of Claude Code's settings.json
+pub fn detect_provider() -> Provider {
+    let settings_env = claude_dir()
+        .and_then(|dir| std::fs::read_to_string(dir.join("settings.json")).ok())
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .and_then(|settings| settings.get("env").cloned());
+    let enabled = |name: &str| {
+        std::env::var(name).is_ok_and(|value| is_enabled(&value))
+            || settings_env
+                .as_ref()
+                .and_then(|env| env.get(name))
+                .and_then(|value| value.as_str())
+                .is_some_and(is_enabled)
+    };
+
+    if enabled(BEDROCK_ENV) {
+        Provider::Bedrock
+    } else if enabled(VERTEX_ENV) {
+        Provider::Vertex
+    } else {
+        Provider::Anthropic
+    }
+}
+
+/// Where the applet gets usage, stats and process data from.
+/// Each method returns a subscription; bumping `generation` restarts it.
+pub trait DataSource: Send + Sync {
+    /// Usage windows, polled at the interval published on `poll_interval`
+    fn usage(&self, poll_interval: watch::Receiver<u32>, generation: u64) -> Subscription<api::UsageUpdate>;
+    /// Activity and cost totals
+    fn stats(&self, generation: u64) -> Subscription<stats::StatsUpdate>;
+    /// Number of running Claude sessions
+    fn processes(&self, generation: u64) -> Subscription<process::ProcessUpdate>;
+    /// Provider serving requests; anything but Anthropic has no usage windows
+    fn provider(&self) -> Provider {
+        Provider::Anthropic
+    }
+    /// Scripted data that must not reach the usage history, hooks or notifications
+    fn is_mock(&self) -> bool {
+        false
+    }
+}
+
+/// Claude Code's local files and the Anthropic usage API
+pub struct ClaudeCode;
+
+impl DataSource for ClaudeCode {
+    fn usage(&self, poll_interval: watch::Receiver<u32>, generation: u64) -> Subscription<api::UsageUpdate> {
+        api::api_subscription(poll_interval, generation)
+    }
+
+    fn stats(&self, generation: u64) -> Subscription<stats::StatsUpdate> {
+        stats::stats_subscription(generation)
+    }
+
+    fn processes(&self, generation: u64) -> Subscription<process::ProcessUpdate> {
+        process::process_subscription(generation)
+    }
+}
+
+/// Claude Code on Bedrock or Vertex: local stats only, since the Anthropic usage endpoint does not apply
+pub struct CloudProvider(pub Provider);
+
+impl DataSource for CloudProvider {
+    fn usage(&self, _poll_interval: watch::Receiver<u32>, _generation: u64) -> Subscription<api::UsageUpdate> {
+        Subscription::none()
+    }
+
+    fn stats(&self, generation: u64) -> Subscription<stats::StatsUpdate> {
+        stats::stats_subscription(generation)
+    }
+
+    fn processes(&self, generation: u64) -> Subscription<process::ProcessUpdate> {
+        process::process_subscription(generation)
+    }
+
+    fn provider(&self) -> Provider {
+        self.0
+    }
+}
+
+static DATA_SOURCE: LazyLock<Box<dyn DataSource>> = LazyLock::new(|| {
+    if std::env::var_os(MOCK_ENV).is_some_and(|value| !value.is_empty() && value != "0") {
+        tracing::info!("{} is set; using mock data", MOCK_ENV);
+        return Box::new(mock::Mock);
+    }
+    match detect_provider() {
+        Provider::Anthropic => Box::new(ClaudeCode),
+        provider => {
+            tracing::info!("Claude Code is configured for {:?}; showing local stats only", provider);
+            Box::new(CloudProvider(provider))
+        }
+    }
+});
+
+/// The data source in use: scripted mock data when `COSMIC_APPLET_CLAUDE_MOCK` is set,
+/// local stats only for Bedrock and Vertex, Claude Code with the usage API otherwise
+pub fn data_source() -> &'static dyn DataSource {
+    DATA_SOURCE.as_ref()
+}
+
+/// Claude data directory chosen in the settings; `None` uses the default
+static CLAUDE_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Point the backends at another Claude data directory; a leading `~` expands to the home directory
+pub fn set_claude_dir(dir: Option<&str>) {
+    let dir = dir.map(str::trim).filter(|dir| !dir.is_empty()).map(crate::config::expand_home);
+    if let Ok(mut current) = CLAUDE_DIR_OVERRIDE.write() {
+        *current = dir;
+    }
+}
+
+/// Claude Desktop's config directory: `~/.config/Claude`, or the copy inside a flatpak's sandbox data
+pub fn claude_desktop_dir() -> Option<PathBuf> {
+    let native = crate::flatpak::host_config_dir().map(|dir| dir.join("Claude"));
+    if native.as_ref().is_some_and(|dir| dir.is_dir()) {
+        return native;
+    }
+    // Flatpak apps keep their config under ~/.var/app/<app id>/config
+    let flatpak = dirs::home_dir()
+        .and_then(|home| std::fs::read_dir(home.join(".var").join("app")).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().to_ascii_lowercase().contains("claude"))
+        .map(|entry| entry.path().join("config").join("Claude"))
+        .find(|dir| dir.is_dir());
+    flatpak.or(native)
+}
+
+/// Claude Code's data directory: the configured one, else `$CLAUDE_CONFIG_DIR`, else `~/.claude`
+pub fn claude_dir() -> Option<PathBuf> {
+    if let Some(dir) = CLAUDE_DIR_OVERRIDE.read().ok().and_then(|dir| dir.clone()) {
+        return Some(dir);
+    }
+    if let Some(dir) = std::env::var_os("CLAUDE_CONFIG_DIR").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    dirs::home_dir().map(|h| h.join(".claude"))
+}
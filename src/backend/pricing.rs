@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{stream, Subscription};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+const LITELLM_PRICING_URL: &str =
+    "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
+const PRICING_SYNC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Prices for one model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    pub cache_write: f64,
+    pub cache_read: f64,
+}
+
+/// Model line a model id belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModelFamily {
+    Opus,
+    Sonnet,
+    Haiku,
+    Other,
+}
+
+impl ModelFamily {
+    pub fn from_model(model: &str) -> Self {
+        if model.contains("opus") {
+            ModelFamily::Opus
+        } else if model.contains("sonnet") {
+            ModelFamily::Sonnet
+        } else if model.contains("haiku") {
+            ModelFamily::Haiku
+        } else {
+            ModelFamily::Other
+        }
+    }
+}
+
+/// Token counts of a single request, as reported in transcripts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+    pub cache_write: u64,
+    pub cache_read: u64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+    }
+
+    /// All tokens, prompt and output
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_write + self.cache_read
+    }
+
+    /// Share of prompt tokens served from cache, if any prompt tokens were used
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        let prompt = self.input + self.cache_write + self.cache_read;
+        (prompt > 0).then(|| self.cache_read as f64 / prompt as f64)
+    }
+}
+
+impl ModelPricing {
+    const fn new(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write,
+            cache_read,
+        }
+    }
+
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        (usage.input as f64 * self.input
+            + usage.output as f64 * self.output
+            + usage.cache_write as f64 * self.cache_write
+            + usage.cache_read as f64 * self.cache_read)
+            / 1_000_000.0
+    }
+}
+
+/// Published Anthropic prices, used until (or unless) a remote sync succeeds.
+/// Keys are model id prefixes; the longest matching prefix wins.
+const BUILTIN_PRICING: &[(&str, ModelPricing)] = &[
+    ("claude-opus-4-5", ModelPricing::new(5.0, 25.0, 6.25, 0.50)),
+    ("claude-opus-4", ModelPricing::new(15.0, 75.0, 18.75, 1.50)),
+    ("claude-3-opus", ModelPricing::new(15.0, 75.0, 18.75, 1.50)),
+    ("claude-sonnet-4", ModelPricing::new(3.0, 15.0, 3.75, 0.30)),
+    ("claude-3-7-sonnet", ModelPricing::new(3.0, 15.0, 3.75, 0.30)),
+    ("claude-3-5-sonnet", ModelPricing::new(3.0, 15.0, 3.75, 0.30)),
+    ("claude-haiku-4-5", ModelPricing::new(1.0, 5.0, 1.25, 0.10)),
+    ("claude-3-5-haiku", ModelPricing::new(0.80, 4.0, 1.0, 0.08)),
+    ("claude-3-haiku", ModelPricing::new(0.25, 1.25, 0.30, 0.03)),
+];
+
+/// Model id prefix -> pricing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PricingTable {
+    models: BTreeMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    fn builtin() -> Self {
+        Self {
+            models: BUILTIN_PRICING
+                .iter()
+                .map(|(model, pricing)| (model.to_string(), *pricing))
+                .collect(),
+        }
+    }
+
+    /// Pricing for a model id such as "claude-sonnet-4-5-20250929"
+    pub fn lookup(&self, model: &str) -> Option<ModelPricing> {
+        let model = model.strip_prefix("anthropic/").unwrap_or(model);
+        self.models
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, pricing)| *pricing)
+    }
+
+    pub fn cost(&self, model: &str, usage: &TokenUsage) -> f64 {
+        self.lookup(model).map(|p| p.cost(usage)).unwrap_or(0.0)
+    }
+}
+
+/// Shared by the transcript scanner; replaced when a sync succeeds
+static PRICING: LazyLock<RwLock<PricingTable>> =
+    LazyLock::new(|| RwLock::new(load_cached().unwrap_or_else(PricingTable::builtin)));
+
+/// Snapshot of the active pricing table
+pub fn current() -> PricingTable {
+    PRICING.read().map(|table| table.clone()).unwrap_or_else(|_| PricingTable::builtin())
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("cosmic-applet-claude").join("pricing.json"))
+}
+
+fn load_cached() -> Option<PricingTable> {
+    let contents = std::fs::read_to_string(cache_path()?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn store_cached(table: &PricingTable) {
+    let Some(path) = cache_path() else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, serde_json::to_string(table).unwrap_or_default()));
+    if let Err(err) = result {
+        warn!("Failed to cache model pricing: {}", err);
+    }
+}
+
+/// One entry of LiteLLM's pricing JSON (USD per token)
+#[derive(Debug, Deserialize)]
+struct LiteLlmModel {
+    #[serde(default)]
+    litellm_provider: String,
+    input_cost_per_token: Option<f64>,
+    output_cost_per_token: Option<f64>,
+    cache_creation_input_token_cost: Option<f64>,
+    cache_read_input_token_cost: Option<f64>,
+}
+
+async fn fetch_litellm_pricing(client: &reqwest::Client) -> Result<PricingTable, String> {
+    let response = client
+        .get(LITELLM_PRICING_URL)
+        .send()
+        .await
+        .map_err(|_| "Network request failed".to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Pricing sync error: HTTP {}", response.status().as_u16()));
+    }
+    let text = response.text().await.map_err(|_| "Failed to read response".to_string())?;
+
+    // Entries are heterogeneous, so parse each one on its own and skip anything unexpected
+    let raw: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(&text).map_err(|_| "Failed to parse response".to_string())?;
+
+    // Remote data extends the built-in table rather than replacing it
+    let mut table = PricingTable::builtin();
+    for (name, value) in raw {
+        if !name.starts_with("claude-") {
+            continue;
+        }
+        let Ok(model) = serde_json::from_value::<LiteLlmModel>(value) else {
+            continue;
+        };
+        if model.litellm_provider != "anthropic" {
+            continue;
+        }
+        let (Some(input), Some(output)) = (model.input_cost_per_token, model.output_cost_per_token) else {
+            continue;
+        };
+        let per_million = |per_token: f64| per_token * 1_000_000.0;
+        table.models.insert(
+            name,
+            ModelPricing {
+                input: per_million(input),
+                output: per_million(output),
+                cache_write: per_million(model.cache_creation_input_token_cost.unwrap_or(input * 1.25)),
+                cache_read: per_million(model.cache_read_input_token_cost.unwrap_or(input * 0.1)),
+            },
+        );
+    }
+    Ok(table)
+}
+
+/// Subscription that refreshes the pricing table from LiteLLM once a day.
+/// Emits a unit message after each successful sync so costs can be recomputed.
+pub fn pricing_sync_subscription() -> Subscription<()> {
+    Subscription::run_with_id(
+        "claude-pricing-sync",
+        stream::channel(1, move |mut sender| async move {
+            let client = reqwest::Client::new();
+
+            // Initial delay to let the UI settle
+            tokio::time::sleep(Duration::from_secs(15)).await;
+
+            loop {
+                match fetch_litellm_pricing(&client).await {
+                    Ok(table) => {
+                        debug!("Synced pricing for {} models", table.models.len());
+                        store_cached(&table);
+                        if let Ok(mut current) = PRICING.write() {
+                            *current = table;
+                        }
+                        let _ = sender.send(()).await;
+                    }
+                    Err(e) => warn!("Failed to sync model pricing: {}", e),
+                }
+                tokio::time::sleep(PRICING_SYNC_INTERVAL).await;
+            }
+        }),
+    )
+}
@@ -1,82 +1,459 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use chrono::{Local, NaiveDate};
 use cosmic::iced::{futures::SinkExt, Subscription};
 use cosmic::iced_futures::stream;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Command-line fragments (lowercase) that identify the Claude Desktop app, native or flatpak
+const DESKTOP_MARKERS: [&str; 2] = ["claude-desktop", "com.anthropic.claude"];
+
+/// Longest command line read per process; the markers sit in the first arguments
+const CMDLINE_LIMIT: u64 = 4096;
+
+/// Longest gap between two scans counted as active time; longer gaps mean the machine was suspended
+const MAX_ACTIVE_GAP: Duration = Duration::from_secs(30);
+
+/// How long a session's branch and last activity are reused before they are looked up again
+const DETAILS_REFRESH: Duration = Duration::from_secs(60);
 
 /// Message returned from the process detection subscription
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ProcessUpdate {
+    /// Running Claude Code CLI sessions
     pub count: usize,
+    /// Working directory and git branch of each CLI session, where /proc exposes them
+    pub sessions: Vec<CliSession>,
+    /// Running Claude Desktop app instances (main processes only)
+    pub desktop_count: usize,
+    /// How long the scan took, shown on the Diagnostics page
+    pub scan_duration: Duration,
+}
+
+/// A running Claude Code CLI process and the project it was started in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliSession {
+    pub pid: u32,
+    /// Working directory of the process
+    pub cwd: PathBuf,
+    /// Checked-out branch, or the abbreviated commit when HEAD is detached
+    pub branch: Option<String>,
+    /// Last write to one of the project's transcripts, used to spot idle sessions
+    pub last_activity: Option<SystemTime>,
+}
+
+impl CliSession {
+    /// Last component of the working directory, shown in the session list
+    pub fn project_name(&self) -> String {
+        self.cwd
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.cwd.display().to_string())
+    }
+}
+
+/// A CLI session that was not found in the latest scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndedSession {
+    pub project: String,
+    /// Time between the scan that first saw the session and the one that missed it
+    pub duration: Duration,
+}
+
+/// Sessions that appeared or disappeared since the previous scan
+#[derive(Debug, Clone, Default)]
+pub struct SessionChanges {
+    /// Project names of new sessions
+    pub started: Vec<String>,
+    pub ended: Vec<EndedSession>,
+}
+
+/// Per-PID state kept across process scans: when each CLI session was first seen,
+/// and how long today at least one session has been running
+#[derive(Debug, Default)]
+pub struct SessionTracker {
+    sessions: HashMap<u32, (Instant, String)>,
+    /// Whether a scan has been observed; the first one only records what is already running
+    seeded: bool,
+    /// Time of the previous scan and whether any CLI session was running then
+    last_scan: Option<(Instant, bool)>,
+    day: Option<NaiveDate>,
+    active_today: Duration,
+}
+
+impl SessionTracker {
+    /// Record a scan, returning the sessions that started or ended since the previous one
+    pub fn observe(&mut self, update: &ProcessUpdate) -> SessionChanges {
+        let now = Instant::now();
+        let today = Local::now().date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.active_today = Duration::ZERO;
+        }
+        if let Some((previous, running)) = self.last_scan {
+            let gap = now.duration_since(previous);
+            if running && gap <= MAX_ACTIVE_GAP {
+                self.active_today += gap;
+            }
+        }
+        self.last_scan = Some((now, update.count > 0));
+
+        let mut changes = SessionChanges::default();
+        self.sessions.retain(|pid, (first_seen, project)| {
+            let running = update.sessions.iter().any(|session| session.pid == *pid);
+            if !running {
+                changes.ended.push(EndedSession {
+                    project: std::mem::take(project),
+                    duration: now.duration_since(*first_seen),
+                });
+            }
+            running
+        });
+        for session in &update.sessions {
+            if !self.sessions.contains_key(&session.pid) {
+                self.sessions.insert(session.pid, (now, session.project_name()));
+                changes.started.push(session.project_name());
+            }
+        }
+
+        if !self.seeded {
+            self.seeded = true;
+            return SessionChanges::default();
+        }
+        changes
+    }
+
+    /// Time today with at least one CLI session running, while the applet was watching
+    pub fn active_today(&self) -> Duration {
+        if self.day == Some(Local::now().date_naive()) {
+            self.active_today
+        } else {
+            Duration::ZERO
+        }
+    }
 }
 
-/// Subscription that polls for running claude processes
-pub fn process_subscription() -> Subscription<ProcessUpdate> {
+/// Subscription that polls for running claude processes; bumping `generation` restarts it
+pub fn process_subscription(generation: u64) -> Subscription<ProcessUpdate> {
     Subscription::run_with_id(
-        "claude-process-watcher",
+        std::sync::Arc::new(("claude-process-watcher", generation)),
         stream::channel(10, move |mut output| async move {
+            let mut details = DetailsCache::default();
             loop {
                 // Poll every 5 seconds (process count rarely changes rapidly)
                 tokio::time::sleep(Duration::from_secs(5)).await;
 
-                let count = count_claude_processes().await;
-                let _ = output.send(ProcessUpdate { count }).await;
+                let (update, cache) = count_claude_processes(details).await;
+                details = cache;
+                let _ = output.send(update).await;
             }
         }),
     )
 }
 
-/// Count running claude processes by scanning /proc
-async fn count_claude_processes() -> usize {
-    tokio::task::spawn_blocking(count_claude_processes_sync)
+/// Count running claude processes by scanning /proc, handing the details cache back for the next scan
+async fn count_claude_processes(mut details: DetailsCache) -> (ProcessUpdate, DetailsCache) {
+    tokio::task::spawn_blocking(move || (count_claude_processes_sync(&mut details), details))
         .await
-        .unwrap_or(0)
+        .unwrap_or_default()
+}
+
+/// Branch and last transcript write per CLI process. Looking them up walks the directory tree
+/// for `.git` and lists the transcript directory, too much to repeat on every 5-second scan.
+#[derive(Debug, Default)]
+struct DetailsCache {
+    entries: HashMap<u32, CachedDetails>,
 }
 
-fn count_claude_processes_sync() -> usize {
+#[derive(Debug)]
+struct CachedDetails {
+    cwd: PathBuf,
+    looked_up: Instant,
+    branch: Option<String>,
+    last_activity: Option<SystemTime>,
+}
+
+impl DetailsCache {
+    /// Branch and last activity of a process, reusing a lookup for the same directory from the last minute
+    fn get(&mut self, pid: u32, cwd: &Path, now: Instant) -> (Option<String>, Option<SystemTime>) {
+        let fresh = self
+            .entries
+            .get(&pid)
+            .is_some_and(|cached| cached.cwd == cwd && now.duration_since(cached.looked_up) < DETAILS_REFRESH);
+        if !fresh {
+            self.entries.insert(
+                pid,
+                CachedDetails {
+                    cwd: cwd.to_path_buf(),
+                    looked_up: now,
+                    branch: git_branch(cwd),
+                    last_activity: super::transcripts::last_transcript_write(cwd),
+                },
+            );
+        }
+        let cached = &self.entries[&pid];
+        (cached.branch.clone(), cached.last_activity)
+    }
+
+    /// Forget processes that are no longer running
+    fn retain_running(&mut self, sessions: &[CliSession]) {
+        self.entries.retain(|pid, _| sessions.iter().any(|session| session.pid == *pid));
+    }
+}
+
+/// Whether a command line belongs to Claude Desktop's main process.
+/// Electron helpers (renderer, GPU, zygote) carry a `--type=` flag and are not counted.
+fn is_claude_desktop(cmdline: &str) -> bool {
+    let args = || cmdline.split('\0').filter(|arg| !arg.is_empty());
+    if args().any(|arg| arg.starts_with("--type=")) {
+        return false;
+    }
+    args().any(|arg| {
+        let arg = arg.to_ascii_lowercase();
+        DESKTOP_MARKERS.iter().any(|marker| arg.contains(marker))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProcessKind {
+    Cli,
+    Desktop,
+}
+
+/// Count a command line (NUL-separated arguments) towards the CLI or Desktop totals
+fn classify(cmdline: &str, update: &mut ProcessUpdate) -> Option<ProcessKind> {
+    if is_claude_desktop(cmdline) {
+        update.desktop_count += 1;
+        return Some(ProcessKind::Desktop);
+    }
+
+    // Iterate directly over split without Vec allocation
+    for arg in cmdline.split('\0') {
+        if arg.is_empty() {
+            continue;
+        }
+        // Check for claude binary or node running claude
+        if arg.ends_with("/claude") || arg == "claude" {
+            update.count += 1;
+            return Some(ProcessKind::Cli);
+        }
+        // Check for node processes running claude CLI
+        if arg.contains("@anthropic") && arg.contains("claude") {
+            update.count += 1;
+            return Some(ProcessKind::Cli);
+        }
+    }
+    None
+}
+
+/// The git directory for `dir` or its nearest ancestor repository.
+/// Worktrees have a `.git` file pointing at their directory inside the main repository.
+fn git_dir(dir: &Path) -> Option<PathBuf> {
+    for ancestor in dir.ancestors() {
+        let dot_git = ancestor.join(".git");
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+        if dot_git.is_file() {
+            let contents = std::fs::read_to_string(&dot_git).ok()?;
+            let target = PathBuf::from(contents.strip_prefix("gitdir:")?.trim());
+            return Some(if target.is_relative() { ancestor.join(target) } else { target });
+        }
+    }
+    None
+}
+
+/// Branch checked out in the repository containing `dir`, from its HEAD file
+fn git_branch(dir: &Path) -> Option<String> {
+    let head = std::fs::read_to_string(git_dir(dir)?.join("HEAD")).ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            Some(reference.strip_prefix("refs/heads/").unwrap_or(reference).to_string())
+        }
+        // Detached HEAD holds the commit hash
+        None => Some(head.chars().take(7).collect()),
+    }
+}
+
+fn count_claude_processes_sync(details: &mut DetailsCache) -> ProcessUpdate {
+    let started = Instant::now();
+    // The sandbox has its own PID namespace, so host processes are only visible through the host's ps
+    let mut update = if crate::flatpak::is_sandboxed() {
+        count_host_processes()
+    } else {
+        scan_proc(details)
+    };
+    update.scan_duration = started.elapsed();
+    update
+}
+
+/// Classify every process by its /proc cmdline, reusing one path and one read buffer for the whole scan
+fn scan_proc(details: &mut DetailsCache) -> ProcessUpdate {
+    let mut update = ProcessUpdate::default();
     let Ok(entries) = std::fs::read_dir("/proc") else {
-        return 0;
+        return update;
     };
 
-    let mut count = 0;
+    let now = Instant::now();
+    let mut path = String::with_capacity(32);
+    let mut buffer = Vec::with_capacity(CMDLINE_LIMIT as usize);
     for entry in entries.flatten() {
-        let path = entry.path();
+        // Only PID directories (numeric names)
+        let name = entry.file_name();
+        let Some(pid) = name.to_str().filter(|n| n.bytes().all(|b| b.is_ascii_digit())) else {
+            continue;
+        };
 
-        // Check if it's a PID directory (numeric name)
-        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        path.clear();
+        path.push_str("/proc/");
+        path.push_str(pid);
+        path.push_str("/cmdline");
+        let Ok(file) = std::fs::File::open(&path) else {
             continue;
         };
-        if !name.chars().all(|c| c.is_ascii_digit()) {
+        buffer.clear();
+        if file.take(CMDLINE_LIMIT).read_to_end(&mut buffer).is_err() {
+            continue;
+        }
+        // Kernel threads have an empty command line
+        if buffer.is_empty() {
             continue;
         }
 
-        // Read only cmdline (contains both process name and arguments)
-        let cmdline_path = path.join("cmdline");
-        let Ok(cmdline) = std::fs::read_to_string(&cmdline_path) else {
+        if classify(&String::from_utf8_lossy(&buffer), &mut update) != Some(ProcessKind::Cli) {
             continue;
-        };
+        }
+        path.truncate(path.len() - "cmdline".len());
+        path.push_str("cwd");
+        // Other users' processes and short-lived ones may not expose their working directory
+        if let (Ok(pid), Ok(cwd)) = (pid.parse(), std::fs::read_link(&path)) {
+            let (branch, last_activity) = details.get(pid, &cwd, now);
+            update.sessions.push(CliSession {
+                pid,
+                cwd,
+                branch,
+                last_activity,
+            });
+        }
+    }
 
-        // Iterate directly over split without Vec allocation
-        let mut found = false;
-        for arg in cmdline.split('\0') {
-            if arg.is_empty() {
-                continue;
-            }
-            // Check for claude binary or node running claude
-            if arg.ends_with("/claude") || arg == "claude" {
-                found = true;
-                break;
-            }
-            // Check for node processes running claude CLI
-            if arg.contains("@anthropic") && arg.contains("claude") {
-                found = true;
-                break;
-            }
+    update.sessions.sort_by(|a, b| a.cwd.cmp(&b.cwd).then(a.pid.cmp(&b.pid)));
+    details.retain_running(&update.sessions);
+    update
+}
+
+/// Count host processes from `ps` output when running inside a flatpak sandbox.
+/// `ps` joins arguments with spaces, so paths containing spaces may be misread.
+/// Working directories of host processes are not visible, so the session list stays empty.
+fn count_host_processes() -> ProcessUpdate {
+    let mut update = ProcessUpdate::default();
+    let output = match crate::flatpak::host_command("ps").args(["-e", "-o", "args="]).output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            tracing::warn!("Host ps exited with {}", output.status);
+            return update;
+        }
+        Err(err) => {
+            tracing::warn!(?err, "Failed to list host processes");
+            return update;
         }
-        if found {
-            count += 1;
+    };
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let cmdline = line.split_whitespace().collect::<Vec<_>>().join("\0");
+        classify(&cmdline, &mut update);
+    }
+    update
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(pids: &[u32]) -> ProcessUpdate {
+        let sessions: Vec<CliSession> = pids
+            .iter()
+            .map(|&pid| CliSession {
+                pid,
+                cwd: PathBuf::from(format!("/home/user/project-{pid}")),
+                branch: None,
+                last_activity: None,
+            })
+            .collect();
+        ProcessUpdate {
+            count: sessions.len(),
+            sessions,
+            ..Default::default()
         }
     }
 
-    count
+    #[test]
+    fn first_scan_only_records_running_sessions() {
+        let mut tracker = SessionTracker::default();
+        let changes = tracker.observe(&scan(&[1, 2]));
+        assert!(changes.started.is_empty());
+        assert!(changes.ended.is_empty());
+
+        let changes = tracker.observe(&scan(&[1, 2]));
+        assert!(changes.started.is_empty());
+        assert!(changes.ended.is_empty());
+    }
+
+    #[test]
+    fn reports_started_and_ended_sessions() {
+        let mut tracker = SessionTracker::default();
+        tracker.observe(&scan(&[1]));
+
+        let changes = tracker.observe(&scan(&[1, 2]));
+        assert_eq!(changes.started, vec!["project-2".to_string()]);
+        assert!(changes.ended.is_empty());
+
+        let changes = tracker.observe(&scan(&[2]));
+        assert!(changes.started.is_empty());
+        assert_eq!(changes.ended.len(), 1);
+        assert_eq!(changes.ended[0].project, "project-1");
+    }
+
+    #[test]
+    fn active_time_only_counts_while_a_session_runs() {
+        let mut tracker = SessionTracker::default();
+        tracker.observe(&scan(&[]));
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.observe(&scan(&[1]));
+        assert_eq!(tracker.active_today(), Duration::ZERO);
+
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.observe(&scan(&[1]));
+        assert!(tracker.active_today() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn details_are_reused_for_a_minute_per_process_and_directory() {
+        let cwd = PathBuf::from("/nonexistent/project");
+        let looked_up = Instant::now();
+        let mut details = DetailsCache::default();
+        details.entries.insert(
+            7,
+            CachedDetails {
+                cwd: cwd.clone(),
+                looked_up,
+                branch: Some("main".to_string()),
+                last_activity: None,
+            },
+        );
+
+        assert_eq!(details.get(7, &cwd, looked_up + Duration::from_secs(59)).0.as_deref(), Some("main"));
+        // A changed directory or an old lookup is looked up again
+        assert_eq!(details.get(7, Path::new("/nonexistent/other"), looked_up).0, None);
+        details.entries.get_mut(&7).unwrap().branch = Some("main".to_string());
+        assert_eq!(details.get(7, Path::new("/nonexistent/other"), looked_up + DETAILS_REFRESH).0, None);
+
+        details.retain_running(&[]);
+        assert!(details.entries.is_empty());
+    }
 }
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use super::version::is_newer;
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{stream, Subscription};
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/m4ul3r/cosmic-ext-applet-claude/releases/latest";
+const RELEASE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const APPLET_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A newer applet release published on GitHub
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseUpdate {
+    pub version: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    html_url: String,
+}
+
+async fn fetch_latest_release(client: &reqwest::Client) -> Option<GitHubRelease> {
+    let response = client
+        .get(RELEASES_URL)
+        .header("User-Agent", concat!("cosmic-applet-claude/", env!("CARGO_PKG_VERSION")))
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        warn!("GitHub releases error: HTTP {}", response.status().as_u16());
+        return None;
+    }
+    response.json::<GitHubRelease>().await.ok()
+}
+
+/// Subscription that checks GitHub once a day for a newer applet release
+pub fn release_subscription() -> Subscription<Option<ReleaseUpdate>> {
+    Subscription::run_with_id(
+        "claude-applet-release-check",
+        stream::channel(1, move |mut sender| async move {
+            let client = reqwest::Client::new();
+
+            // Initial delay to let the UI settle
+            tokio::time::sleep(Duration::from_secs(10)).await;
+
+            loop {
+                let update = fetch_latest_release(&client)
+                    .await
+                    .filter(|release| is_newer(&release.tag_name, APPLET_VERSION))
+                    .map(|release| ReleaseUpdate {
+                        version: release.tag_name.trim_start_matches('v').to_string(),
+                        url: release.html_url,
+                    });
+                debug!("Applet release check: {:?}", update);
+
+                let _ = sender.send(update).await;
+                tokio::time::sleep(RELEASE_CHECK_INTERVAL).await;
+            }
+        }),
+    )
+}
@@ -2,7 +2,9 @@
 
 use cosmic::iced::{futures::SinkExt, Subscription};
 use cosmic::iced_futures::stream;
+use chrono::{Datelike, NaiveDate};
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -14,6 +16,15 @@ pub struct StatsUpdate {
     pub total_messages: u32,
     pub total_sessions: u32,
     pub total_cost_usd: f64,
+    pub today_cost_usd: f64,
+    /// Cost of today and the six days before it
+    pub week_cost_usd: f64,
+    /// Cost of the days recorded so far this calendar month
+    pub month_cost_usd: f64,
+    /// Consecutive days with activity, counting back from today (or yesterday if today is still empty)
+    pub streak_days: u32,
+    /// Every day recorded in the cache
+    pub daily: Vec<crate::history::DailyStats>,
 }
 
 /// Raw JSON structure from stats-cache.json
@@ -38,12 +49,14 @@ struct DailyActivity {
     messages: u32,
     #[serde(default)]
     sessions: u32,
+    #[serde(default)]
+    cost_usd: f64,
 }
 
-/// Subscription that monitors the stats-cache.json file
-pub fn stats_subscription() -> Subscription<StatsUpdate> {
+/// Subscription that monitors the stats-cache.json file; bumping `generation` restarts it
+pub fn stats_subscription(generation: u64) -> Subscription<StatsUpdate> {
     Subscription::run_with_id(
-        "claude-stats-watcher",
+        std::sync::Arc::new(("claude-stats-watcher", generation)),
         stream::channel(10, move |mut output| async move {
             loop {
                 // Poll every 30 seconds
@@ -57,8 +70,8 @@ pub fn stats_subscription() -> Subscription<StatsUpdate> {
 }
 
 /// Get the path to the stats cache file
-fn get_stats_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".claude").join("stats-cache.json"))
+pub fn get_stats_path() -> Option<PathBuf> {
+    super::claude_dir().map(|dir| dir.join("stats-cache.json"))
 }
 
 /// Read and parse the stats cache file
@@ -68,16 +81,48 @@ async fn read_stats_file() -> Option<StatsUpdate> {
     let contents = tokio::fs::read_to_string(&path).await.ok()?;
     let cache: StatsCache = serde_json::from_str(&contents).ok()?;
 
-    // Get today's date in YYYY-MM-DD format
-    let today = get_today_string();
+    let today_date = chrono::Local::now().date_naive();
+    let today = today_date.format("%Y-%m-%d").to_string();
 
     // Find today's activity
     let today_activity = cache.daily_activity.iter().find(|a| a.date == today);
 
-    let (today_messages, today_sessions) = match today_activity {
-        Some(a) => (a.messages, a.sessions),
-        None => (0, 0),
+    let (today_messages, today_sessions, today_cost_usd) = match today_activity {
+        Some(a) => (a.messages, a.sessions, a.cost_usd),
+        None => (0, 0, 0.0),
+    };
+
+    let mut week_cost_usd = 0.0;
+    let mut month_cost_usd = 0.0;
+    for activity in &cache.daily_activity {
+        let Ok(date) = NaiveDate::parse_from_str(&activity.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let days_ago = (today_date - date).num_days();
+        if (0..7).contains(&days_ago) {
+            week_cost_usd += activity.cost_usd;
+        }
+        if date.year() == today_date.year() && date.month() == today_date.month() {
+            month_cost_usd += activity.cost_usd;
+        }
+    }
+
+    let active_days: HashSet<NaiveDate> = cache
+        .daily_activity
+        .iter()
+        .filter(|a| a.messages > 0)
+        .filter_map(|a| NaiveDate::parse_from_str(&a.date, "%Y-%m-%d").ok())
+        .collect();
+    let mut day = if active_days.contains(&today_date) {
+        today_date
+    } else {
+        today_date - chrono::Duration::days(1)
     };
+    let mut streak_days = 0;
+    while active_days.contains(&day) {
+        streak_days += 1;
+        day -= chrono::Duration::days(1);
+    }
 
     Some(StatsUpdate {
         today_messages,
@@ -85,10 +130,19 @@ async fn read_stats_file() -> Option<StatsUpdate> {
         total_messages: cache.total_messages,
         total_sessions: cache.total_sessions,
         total_cost_usd: cache.total_cost_usd,
+        today_cost_usd,
+        week_cost_usd,
+        month_cost_usd,
+        streak_days,
+        daily: cache
+            .daily_activity
+            .iter()
+            .map(|a| crate::history::DailyStats {
+                date: a.date.clone(),
+                messages: a.messages,
+                sessions: a.sessions,
+                cost_usd: a.cost_usd,
+            })
+            .collect(),
     })
 }
-
-/// Get today's date as YYYY-MM-DD string
-fn get_today_string() -> String {
-    chrono::Local::now().format("%Y-%m-%d").to_string()
-}
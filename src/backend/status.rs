@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{stream, Subscription};
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+const STATUS_SUMMARY_URL: &str = "https://status.anthropic.com/api/v2/summary.json";
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Overall severity reported by the status page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum StatusIndicator {
+    #[default]
+    None,
+    Maintenance,
+    Minor,
+    Major,
+    Critical,
+}
+
+/// An unresolved incident affecting a Claude component
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incident {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub impact: StatusIndicator,
+}
+
+/// Message returned from the status page subscription
+#[derive(Debug, Clone, Default)]
+pub struct StatusUpdate {
+    pub indicator: StatusIndicator,
+    pub description: String,
+    pub incidents: Vec<Incident>,
+    pub last_error: Option<String>,
+}
+
+/// Raw JSON structure from the Statuspage summary endpoint
+#[derive(Debug, Deserialize)]
+struct Summary {
+    status: SummaryStatus,
+    #[serde(default)]
+    incidents: Vec<SummaryIncident>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SummaryStatus {
+    indicator: String,
+    description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SummaryIncident {
+    id: String,
+    name: String,
+    status: String,
+    impact: String,
+    #[serde(default)]
+    components: Vec<Component>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Component {
+    name: String,
+}
+
+fn parse_indicator(value: &str) -> StatusIndicator {
+    match value {
+        "minor" => StatusIndicator::Minor,
+        "major" => StatusIndicator::Major,
+        "critical" => StatusIndicator::Critical,
+        "maintenance" => StatusIndicator::Maintenance,
+        _ => StatusIndicator::None,
+    }
+}
+
+/// Incidents without component data are kept, since they may still affect Claude
+fn affects_claude(incident: &SummaryIncident) -> bool {
+    incident.components.is_empty()
+        || incident
+            .components
+            .iter()
+            .any(|c| c.name.to_ascii_lowercase().contains("claude"))
+}
+
+async fn fetch_status(client: &reqwest::Client) -> Result<StatusUpdate, String> {
+    let response = client
+        .get(STATUS_SUMMARY_URL)
+        .send()
+        .await
+        .map_err(|_| "Network request failed".to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Status page error: HTTP {}", response.status().as_u16()));
+    }
+
+    let text = response.text().await.map_err(|_| "Failed to read response".to_string())?;
+    let summary: Summary = serde_json::from_str(&text).map_err(|_| "Failed to parse response".to_string())?;
+
+    let incidents = summary
+        .incidents
+        .iter()
+        .filter(|i| affects_claude(i))
+        .map(|i| Incident {
+            id: i.id.clone(),
+            name: i.name.clone(),
+            status: i.status.clone(),
+            impact: parse_indicator(&i.impact),
+        })
+        .collect();
+
+    Ok(StatusUpdate {
+        indicator: parse_indicator(&summary.status.indicator),
+        description: summary.status.description,
+        incidents,
+        last_error: None,
+    })
+}
+
+/// Subscription that polls the Anthropic status page; bumping `generation` restarts it
+pub fn status_subscription(generation: u64) -> Subscription<StatusUpdate> {
+    Subscription::run_with_id(
+        std::sync::Arc::new(("claude-status-page", generation)),
+        stream::channel(1, move |mut sender| async move {
+            let client = reqwest::Client::new();
+
+            // Initial delay to let the UI settle
+            tokio::time::sleep(Duration::from_secs(2)).await;
+
+            loop {
+                debug!("Fetching Anthropic status page");
+                let update = match fetch_status(&client).await {
+                    Ok(update) => update,
+                    Err(e) => {
+                        warn!("Failed to fetch status page: {}", e);
+                        StatusUpdate {
+                            last_error: Some(e),
+                            ..Default::default()
+                        }
+                    }
+                };
+
+                let _ = sender.send(update).await;
+                tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+            }
+        }),
+    )
+}
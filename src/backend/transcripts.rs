@@ -0,0 +1,615 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use super::pricing::{self, ModelFamily, TokenUsage};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike, Utc};
+use cosmic::iced::{futures::SinkExt, Subscription};
+use cosmic::iced_futures::stream;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, BufReader};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Costs computed from Claude Code session transcripts
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptUpdate {
+    /// Whether any transcripts were found; costs fall back to the stats cache otherwise
+    pub available: bool,
+    pub today_cost_usd: f64,
+    pub week_cost_usd: f64,
+    pub month_cost_usd: f64,
+    /// Token counts for today, including cache reads and writes
+    pub today_tokens: TokenUsage,
+    /// Today's tokens and cost split by model family
+    pub today_models: BTreeMap<ModelFamily, ModelTotals>,
+    /// Most expensive projects of the last 7 days, highest cost first
+    pub top_projects: Vec<ProjectCost>,
+    /// The 5-hour block in progress, estimated from transcript timestamps
+    pub current_block: Option<SessionBlock>,
+    pub burn_rate: BurnRate,
+}
+
+/// Window over which the token burn rate is measured
+const BURN_RATE_WINDOW: chrono::Duration = chrono::Duration::minutes(10);
+
+/// Tokens per minute over the last ten minutes and the ten minutes before, to show a trend
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BurnRate {
+    pub tokens_per_minute: f64,
+    pub previous_tokens_per_minute: f64,
+}
+
+impl BurnRate {
+    fn from_activity(activity: &[(DateTime<Utc>, u64, f64)], now: DateTime<Utc>) -> Self {
+        let recent_start = now - BURN_RATE_WINDOW;
+        let previous_start = recent_start - BURN_RATE_WINDOW;
+        let (mut recent, mut previous) = (0u64, 0u64);
+        for &(timestamp, tokens, _) in activity {
+            if timestamp > now {
+                continue;
+            }
+            if timestamp >= recent_start {
+                recent += tokens;
+            } else if timestamp >= previous_start {
+                previous += tokens;
+            }
+        }
+        let minutes = BURN_RATE_WINDOW.num_minutes() as f64;
+        Self {
+            tokens_per_minute: recent as f64 / minutes,
+            previous_tokens_per_minute: previous as f64 / minutes,
+        }
+    }
+}
+
+/// Length of a usage block, matching the API's session window
+pub const BLOCK_LENGTH: chrono::Duration = chrono::Duration::hours(5);
+
+/// A 5-hour usage block reconstructed from local activity (like ccusage's "blocks").
+/// A block starts at the hour of its first message and lasts five hours; activity after it ends, or after
+/// five idle hours, starts the next one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionBlock {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Tokens used in the block, including cache reads and writes
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// Share of the busiest earlier block in the scanned days, the best local stand-in for the plan limit.
+    /// `None` without an earlier block to compare with.
+    pub estimated_percent: Option<f32>,
+}
+
+/// Number of projects kept for the weekly leaderboard
+const TOP_PROJECTS: usize = 5;
+
+/// Upper bound on threads aggregating transcripts in parallel
+const MAX_SCAN_WORKERS: usize = 8;
+
+/// Cost and activity of one project over the scanned days
+#[derive(Debug, Clone, Default)]
+pub struct ProjectCost {
+    /// Directory name of the project
+    pub name: String,
+    /// Working directory recorded in the transcripts, if any
+    pub path: Option<PathBuf>,
+    pub cost_usd: f64,
+    pub messages: u32,
+}
+
+/// Tokens and cost attributed to one model family
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModelTotals {
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Quoted value every assistant line contains, checked before parsing.
+/// Other lines rarely match it and are still rejected by the parsed `type`.
+const ASSISTANT_MARKER: &str = r#""assistant""#;
+
+/// One line of a transcript; only assistant messages carry usage.
+/// Strings borrow from the line and everything else (message content, tool calls) is skipped unparsed.
+#[derive(Debug, Deserialize)]
+struct TranscriptLine<'a> {
+    #[serde(rename = "type", borrow)]
+    kind: Option<Cow<'a, str>>,
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(borrow)]
+    cwd: Option<Cow<'a, str>>,
+    #[serde(rename = "requestId", borrow)]
+    request_id: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    message: Option<TranscriptMessage<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptMessage<'a> {
+    #[serde(borrow)]
+    id: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    model: Option<Cow<'a, str>>,
+    usage: Option<TranscriptUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+/// Subscription that recomputes transcript costs every minute.
+/// Bumping `generation` (e.g. after a pricing sync) restarts it with fresh prices.
+pub fn transcripts_subscription(generation: u64) -> Subscription<TranscriptUpdate> {
+    Subscription::run_with_id(
+        std::sync::Arc::new(("claude-transcripts", generation)),
+        stream::channel(1, move |mut output| async move {
+            loop {
+                let update = tokio::task::spawn_blocking(scan_transcripts)
+                    .await
+                    .unwrap_or_default();
+                let _ = output.send(update).await;
+
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        }),
+    )
+}
+
+/// Directory Claude Code writes session transcripts to, one subdirectory per project
+pub fn projects_dir() -> Option<PathBuf> {
+    super::claude_dir().map(|dir| dir.join("projects"))
+}
+
+/// Transcript directory of a project started in `cwd`.
+/// Claude Code names it after the path with every character other than ASCII letters and digits replaced by `-`.
+pub fn project_transcripts_dir(cwd: &Path) -> Option<PathBuf> {
+    let encoded: String = cwd
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    projects_dir().map(|dir| dir.join(encoded))
+}
+
+/// When a transcript of the project started in `cwd` was last written to
+pub fn last_transcript_write(cwd: &Path) -> Option<SystemTime> {
+    std::fs::read_dir(project_transcripts_dir(cwd)?)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+}
+
+/// Transcript files under `~/.claude/projects/<project>/` modified since `since`
+fn transcript_files(since: SystemTime) -> Vec<PathBuf> {
+    let Some(projects) = projects_dir() else {
+        return Vec::new();
+    };
+    let Ok(project_dirs) = std::fs::read_dir(projects) else {
+        return Vec::new();
+    };
+
+    project_dirs
+        .flatten()
+        .filter_map(|project| std::fs::read_dir(project.path()).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .filter(|entry| {
+            entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified >= since)
+        })
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Days that bound the cost totals
+#[derive(Debug, Clone, Copy)]
+struct ScanDays {
+    today: NaiveDate,
+    week_start: NaiveDate,
+    month_start: NaiveDate,
+    /// First day counted towards per-project costs
+    project_start: NaiveDate,
+    earliest: NaiveDate,
+}
+
+/// Totals from one worker's share of the transcripts, merged into the update afterwards
+#[derive(Debug, Default)]
+struct PartialTotals {
+    today_cost_usd: f64,
+    week_cost_usd: f64,
+    month_cost_usd: f64,
+    today_tokens: TokenUsage,
+    today_models: BTreeMap<ModelFamily, ModelTotals>,
+    /// Keyed by the project directory under ~/.claude/projects
+    projects: HashMap<PathBuf, ProjectCost>,
+    /// Timestamp, tokens and cost of every counted entry, for the block estimate
+    activity: Vec<(DateTime<Utc>, u64, f64)>,
+}
+
+impl PartialTotals {
+    fn merge(&mut self, other: PartialTotals) {
+        self.today_cost_usd += other.today_cost_usd;
+        self.week_cost_usd += other.week_cost_usd;
+        self.month_cost_usd += other.month_cost_usd;
+        self.today_tokens.add(&other.today_tokens);
+        for (family, totals) in other.today_models {
+            let entry = self.today_models.entry(family).or_default();
+            entry.tokens += totals.tokens;
+            entry.cost_usd += totals.cost_usd;
+        }
+        // Each project is scanned by a single worker, so keys never collide
+        self.projects.extend(other.projects);
+        self.activity.extend(other.activity);
+    }
+}
+
+/// Split activity into 5-hour blocks and estimate the one in progress at `now`
+fn current_block(mut activity: Vec<(DateTime<Utc>, u64, f64)>, now: DateTime<Utc>) -> Option<SessionBlock> {
+    activity.sort_by_key(|(timestamp, _, _)| *timestamp);
+
+    let mut blocks: Vec<SessionBlock> = Vec::new();
+    let mut last_activity: Option<DateTime<Utc>> = None;
+    for (timestamp, tokens, cost) in activity {
+        let continues = match (blocks.last(), last_activity) {
+            (Some(block), Some(last)) => timestamp < block.end && timestamp - last < BLOCK_LENGTH,
+            _ => false,
+        };
+        if !continues {
+            let start = timestamp
+                .with_minute(0)
+                .and_then(|t| t.with_second(0))
+                .and_then(|t| t.with_nanosecond(0))
+                .unwrap_or(timestamp);
+            blocks.push(SessionBlock {
+                start,
+                end: start + BLOCK_LENGTH,
+                tokens: 0,
+                cost_usd: 0.0,
+                estimated_percent: None,
+            });
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.tokens += tokens;
+            block.cost_usd += cost;
+        }
+        last_activity = Some(timestamp);
+    }
+
+    let mut current = blocks.pop().filter(|block| block.start <= now && now < block.end)?;
+    let busiest = blocks.iter().map(|block| block.tokens).max().filter(|&tokens| tokens > 0);
+    current.estimated_percent = busiest.map(|busiest| (current.tokens as f32 / busiest as f32 * 100.0).min(100.0));
+    Some(current)
+}
+
+fn scan_transcripts() -> TranscriptUpdate {
+    let today = Local::now().date_naive();
+    let week_start = today - chrono::Duration::days(6);
+    let month_start = today.with_day(1).unwrap_or(today);
+    let days = ScanDays {
+        today,
+        week_start,
+        month_start,
+        project_start: week_start,
+        earliest: week_start.min(month_start),
+    };
+
+    let now = Utc::now();
+    let (available, totals) = aggregate(&days);
+    let mut top_projects = named_projects(totals.projects);
+    top_projects.truncate(TOP_PROJECTS);
+
+    TranscriptUpdate {
+        available,
+        today_cost_usd: totals.today_cost_usd,
+        week_cost_usd: totals.week_cost_usd,
+        month_cost_usd: totals.month_cost_usd,
+        today_tokens: totals.today_tokens,
+        today_models: totals.today_models,
+        top_projects,
+        burn_rate: BurnRate::from_activity(&totals.activity, now),
+        current_block: current_block(totals.activity, now),
+    }
+}
+
+/// Every project with activity since `since`, highest cost first, for the data export
+pub fn project_costs(since: NaiveDate) -> Vec<ProjectCost> {
+    let today = Local::now().date_naive();
+    let since = since.min(today);
+    let days = ScanDays {
+        today,
+        week_start: today - chrono::Duration::days(6),
+        month_start: today.with_day(1).unwrap_or(today),
+        project_start: since,
+        earliest: since,
+    };
+    named_projects(aggregate(&days).1.projects)
+}
+
+/// Scan the transcripts written since `days.earliest`; also reports whether any were found
+fn aggregate(days: &ScanDays) -> (bool, PartialTotals) {
+    // Files untouched since the earliest day of interest cannot contain relevant entries
+    let since = days
+        .earliest
+        .and_hms_opt(0, 0, 0)
+        .and_then(|dt| dt.and_local_timezone(Local).earliest())
+        .map(SystemTime::from)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let files = transcript_files(since);
+    let available = !files.is_empty();
+    let table = pricing::current();
+    let pricing = &table;
+
+    // Group files by project so every project is aggregated by exactly one worker
+    let mut by_project: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for path in files {
+        let project_key = path.parent().map(PathBuf::from).unwrap_or_default();
+        by_project.entry(project_key).or_default().push(path);
+    }
+    let projects: Vec<(PathBuf, Vec<PathBuf>)> = by_project.into_iter().collect();
+
+    // A bounded set of scoped threads, each taking a contiguous share of the projects
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_SCAN_WORKERS)
+        .min(projects.len())
+        .max(1);
+    let chunk_size = projects.len().div_ceil(workers).max(1);
+    let mut totals = PartialTotals::default();
+    let seen = Mutex::new(HashSet::new());
+    let seen = &seen;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = projects
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || scan_projects(chunk, days, pricing, seen)))
+            .collect();
+        for handle in handles {
+            match handle.join() {
+                Ok(partial) => totals.merge(partial),
+                Err(_) => tracing::error!("Transcript scan worker panicked"),
+            }
+        }
+    });
+
+    (available, totals)
+}
+
+/// Name projects after their working directory and sort them by cost, highest first
+fn named_projects(projects: HashMap<PathBuf, ProjectCost>) -> Vec<ProjectCost> {
+    let mut projects: Vec<ProjectCost> = projects
+        .into_iter()
+        .map(|(key, mut project)| {
+            // Prefer the real working directory's name; the transcript folder name is an encoded path
+            project.name = project
+                .path
+                .as_deref()
+                .or(Some(key.as_path()))
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            project
+        })
+        .filter(|p| p.cost_usd > 0.0)
+        .collect();
+    projects.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+    projects
+}
+
+/// Key identifying one API response; `None` when the line lacks either id and cannot be deduplicated
+fn dedup_key(message_id: Option<&str>, request_id: Option<&str>) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    (message_id?, request_id?).hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Aggregate the transcripts of a share of the projects.
+/// Streamed, retried and resumed requests repeat the same message and request ids, within a file or across the
+/// session files of a resumed conversation; `seen` is shared by all workers so each is counted once.
+fn scan_projects(
+    projects: &[(PathBuf, Vec<PathBuf>)],
+    days: &ScanDays,
+    pricing: &pricing::PricingTable,
+    seen: &Mutex<HashSet<u64>>,
+) -> PartialTotals {
+    let mut totals = PartialTotals::default();
+    // One line buffer per worker; transcripts can run to gigabytes across projects
+    let mut line = String::new();
+
+    for (project_key, files) in projects {
+        for path in files {
+            let Ok(file) = std::fs::File::open(path) else {
+                continue;
+            };
+            let mut reader = BufReader::new(file);
+
+            loop {
+                line.clear();
+                match reader.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                // Most lines are user messages and tool results; skip them without parsing
+                if !line.contains(ASSISTANT_MARKER) {
+                    continue;
+                }
+                let Ok(entry) = serde_json::from_str::<TranscriptLine>(&line) else {
+                    continue;
+                };
+                if entry.kind.as_deref() != Some("assistant") {
+                    continue;
+                }
+                let cwd = entry.cwd;
+                let (Some(timestamp), Some(message)) = (entry.timestamp, entry.message) else {
+                    continue;
+                };
+                let (Some(model), Some(usage)) = (message.model, message.usage) else {
+                    continue;
+                };
+
+                let date: NaiveDate = timestamp.with_timezone(&Local).date_naive();
+                if date < days.earliest || date > days.today {
+                    continue;
+                }
+                if let Some(key) = dedup_key(message.id.as_deref(), entry.request_id.as_deref()) {
+                    let first = seen.lock().map(|mut seen| seen.insert(key)).unwrap_or(true);
+                    if !first {
+                        continue;
+                    }
+                }
+                let tokens = TokenUsage {
+                    input: usage.input_tokens,
+                    output: usage.output_tokens,
+                    cache_write: usage.cache_creation_input_tokens,
+                    cache_read: usage.cache_read_input_tokens,
+                };
+                let cost = pricing.cost(&model, &tokens);
+                totals.activity.push((timestamp, tokens.total(), cost));
+
+                if date == days.today {
+                    totals.today_cost_usd += cost;
+                    totals.today_tokens.add(&tokens);
+                    let family = totals.today_models.entry(ModelFamily::from_model(&model)).or_default();
+                    family.tokens += tokens.total();
+                    family.cost_usd += cost;
+                }
+                if date >= days.week_start {
+                    totals.week_cost_usd += cost;
+                }
+                if date >= days.project_start {
+                    let project = totals.projects.entry(project_key.clone()).or_default();
+                    project.cost_usd += cost;
+                    project.messages += 1;
+                    if project.path.is_none() {
+                        project.path = cwd.map(|cwd| PathBuf::from(cwd.as_ref()));
+                    }
+                }
+                if date >= days.month_start {
+                    totals.month_cost_usd += cost;
+                }
+            }
+        }
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn assistant_line(message_id: &str, request_id: &str, output_tokens: u64) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"{}","requestId":"{request_id}","message":{{"id":"{message_id}","model":"claude-sonnet-4-5","usage":{{"input_tokens":0,"output_tokens":{output_tokens}}}}}}}"#,
+            Utc::now().to_rfc3339()
+        )
+    }
+
+    fn scan_lines(files: &[&[String]]) -> PartialTotals {
+        let dir = std::env::temp_dir().join(format!("cosmic-applet-claude-dedup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths: Vec<PathBuf> = files
+            .iter()
+            .enumerate()
+            .map(|(i, lines)| {
+                let path = dir.join(format!("{i}.jsonl"));
+                std::fs::write(&path, lines.join("\n")).unwrap();
+                path
+            })
+            .collect();
+
+        let today = Local::now().date_naive();
+        let days = ScanDays {
+            today,
+            week_start: today,
+            month_start: today,
+            project_start: today,
+            earliest: today,
+        };
+        let totals = scan_projects(&[(dir.clone(), paths)], &days, &pricing::current(), &Mutex::new(HashSet::new()));
+        let _ = std::fs::remove_dir_all(&dir);
+        totals
+    }
+
+    #[test]
+    fn dedup_key_needs_both_ids() {
+        assert_eq!(dedup_key(Some("msg_1"), Some("req_1")), dedup_key(Some("msg_1"), Some("req_1")));
+        assert_ne!(dedup_key(Some("msg_1"), Some("req_1")), dedup_key(Some("msg_1"), Some("req_2")));
+        assert_eq!(dedup_key(Some("msg_1"), None), None);
+        assert_eq!(dedup_key(None, Some("req_1")), None);
+    }
+
+    #[test]
+    fn repeated_message_and_request_ids_count_once() {
+        let first = [assistant_line("msg_1", "req_1", 100), assistant_line("msg_1", "req_1", 100)];
+        let resumed = [assistant_line("msg_1", "req_1", 100), assistant_line("msg_2", "req_2", 10)];
+        let totals = scan_lines(&[&first, &resumed]);
+        assert_eq!(totals.today_tokens.output, 110);
+        assert_eq!(totals.activity.len(), 2);
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 2, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn block_starts_at_the_hour_of_its_first_message() {
+        let activity = vec![(at(9, 40), 100, 1.0), (at(11, 5), 50, 0.5)];
+        let block = current_block(activity, at(12, 0)).unwrap();
+        assert_eq!(block.start, at(9, 0));
+        assert_eq!(block.end, at(14, 0));
+        assert_eq!(block.tokens, 150);
+        assert_eq!(block.estimated_percent, None);
+    }
+
+    #[test]
+    fn activity_after_the_block_ends_starts_a_new_one() {
+        let activity = vec![(at(3, 10), 400, 4.0), (at(8, 30), 100, 1.0)];
+        let block = current_block(activity, at(9, 0)).unwrap();
+        assert_eq!(block.start, at(8, 0));
+        assert_eq!(block.tokens, 100);
+        assert_eq!(block.estimated_percent, Some(25.0));
+    }
+
+    #[test]
+    fn no_current_block_once_the_last_one_has_ended() {
+        let activity = vec![(at(3, 10), 400, 4.0)];
+        assert_eq!(current_block(activity, at(8, 0)), None);
+    }
+
+    #[test]
+    fn burn_rate_splits_the_last_twenty_minutes() {
+        let activity = [
+            (at(11, 55), 500, 0.0),
+            (at(11, 50), 500, 0.0),
+            (at(11, 45), 200, 0.0),
+            (at(11, 30), 9_000, 0.0),
+            (at(12, 5), 9_000, 0.0),
+        ];
+        let rate = BurnRate::from_activity(&activity, at(12, 0));
+        assert_eq!(rate.tokens_per_minute, 100.0);
+        assert_eq!(rate.previous_tokens_per_minute, 20.0);
+    }
+
+    #[test]
+    fn burn_rate_is_zero_without_recent_activity() {
+        let rate = BurnRate::from_activity(&[(at(9, 0), 1_000, 0.0)], at(12, 0));
+        assert_eq!(rate, BurnRate::default());
+    }
+}
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{stream, Subscription};
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+const NPM_LATEST_URL: &str = "https://registry.npmjs.org/@anthropic-ai/claude-code/latest";
+const VERSION_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Message returned from the Claude Code version check
+#[derive(Debug, Clone, Default)]
+pub struct VersionUpdate {
+    pub installed: Option<String>,
+    pub latest: Option<String>,
+}
+
+impl VersionUpdate {
+    /// Whether the latest published release is newer than the installed one
+    pub fn update_available(&self) -> bool {
+        match (&self.installed, &self.latest) {
+            (Some(installed), Some(latest)) => is_newer(latest, installed),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmPackage {
+    version: String,
+}
+
+/// Compare dotted version strings numerically, ignoring pre-release suffixes
+pub(crate) fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u32> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    }
+    parts(candidate) > parts(current)
+}
+
+/// Run `claude --version`, whose output looks like "1.0.35 (Claude Code)"
+async fn installed_version() -> Option<String> {
+    let output = crate::flatpak::host_tokio_command("claude")
+        .arg("--version")
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+}
+
+async fn latest_version(client: &reqwest::Client) -> Option<String> {
+    let response = client.get(NPM_LATEST_URL).send().await.ok()?;
+    if !response.status().is_success() {
+        warn!("npm registry error: HTTP {}", response.status().as_u16());
+        return None;
+    }
+    response.json::<NpmPackage>().await.ok().map(|p| p.version)
+}
+
+/// Subscription that periodically compares the installed Claude Code with the latest release
+pub fn version_subscription() -> Subscription<VersionUpdate> {
+    Subscription::run_with_id(
+        "claude-version-check",
+        stream::channel(1, move |mut sender| async move {
+            let client = reqwest::Client::new();
+
+            // Initial delay to let the UI settle
+            tokio::time::sleep(Duration::from_secs(5)).await;
+
+            loop {
+                let installed = installed_version().await;
+                let latest = latest_version(&client).await;
+                debug!("Claude Code version: installed={:?}, latest={:?}", installed, latest);
+
+                let _ = sender.send(VersionUpdate { installed, latest }).await;
+                tokio::time::sleep(VERSION_CHECK_INTERVAL).await;
+            }
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_newer_compares_components_numerically() {
+        assert!(is_newer("1.0.10", "1.0.9"));
+        assert!(is_newer("2.0.0", "1.99.99"));
+        assert!(!is_newer("1.0.9", "1.0.10"));
+        assert!(!is_newer("1.0.35", "1.0.35"));
+    }
+
+    #[test]
+    fn is_newer_ignores_prefixes_and_suffixes() {
+        assert!(is_newer("v1.0.36", "1.0.35"));
+        assert!(!is_newer("1.0.35-beta.1", "1.0.35"));
+        assert!(!is_newer("1.0.35+build.7", "v1.0.35"));
+        // A longer version with the same prefix is newer
+        assert!(is_newer("1.0.35.1", "1.0.35"));
+    }
+}
@@ -1,7 +1,11 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use chrono::{NaiveDate, Weekday};
+use cosmic::iced::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 pub const APP_ID: &str = "dev.m4ul3r.CosmicExtAppletClaude";
 
@@ -14,44 +18,647 @@ pub enum IconDisplay {
     /// Show dual rings (default)
     #[default]
     Both,
+    /// Session and weekly as concentric arcs in one glyph (session outside)
+    Concentric,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ColorMode {
+    /// Snap between low/medium/high colors at the configured thresholds (default)
+    #[default]
+    Threshold,
+    /// Interpolate continuously from low through medium to high
+    Gradient,
+    /// Single foreground color; levels are told apart by ring shape
+    Symbolic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum IndicatorStyle {
+    /// Circular progress rings (default)
+    #[default]
+    Ring,
+    /// Battery-like bars, easier to read on short panels
+    Battery,
+    /// Colored percentage text only, for very compact panels
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PopupDensity {
+    /// Theme paddings and text sizes (default)
+    #[default]
+    Comfortable,
+    /// Tighter paddings and smaller text, for small screens
+    Compact,
+}
+
+/// A block of the usage popup that can be hidden or moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PopupSection {
+    /// Plan name, login button and lockout banner
+    Plan,
+    /// 5-hour session window
+    Session,
+    /// Weekly window and its forecast
+    Weekly,
+    /// Per-model weekly windows and the model mix
+    Models,
+    /// Running sessions, service status and errors
+    Status,
+    /// Today's activity, cost, top projects, budget and extra usage
+    Stats,
+    /// Terminal, folder, export, logs and diagnostics buttons
+    Actions,
+}
+
+impl PopupSection {
+    /// Every section, in the default popup order
+    pub const ALL: [PopupSection; 7] = [
+        PopupSection::Plan,
+        PopupSection::Session,
+        PopupSection::Weekly,
+        PopupSection::Models,
+        PopupSection::Stats,
+        PopupSection::Status,
+        PopupSection::Actions,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ResetTimeFormat {
+    /// Time left, e.g. "Resets in 2h 14m" (default)
+    #[default]
+    Relative,
+    /// Local clock time, e.g. "Resets at 14:30"
+    Absolute,
+    /// Time left followed by the clock time
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TextMetric {
+    /// The window shown by the icon; session when both are shown (default)
+    #[default]
+    Auto,
+    /// 5-hour session usage
+    Session,
+    /// Weekly usage
+    Weekly,
+    /// Whichever of session and weekly usage is higher
+    Highest,
+    /// Today's local cost
+    Cost,
+}
+
+impl TextMetric {
+    /// Next metric in settings cycle order
+    pub fn next(self) -> Self {
+        match self {
+            TextMetric::Auto => TextMetric::Session,
+            TextMetric::Session => TextMetric::Weekly,
+            TextMetric::Weekly => TextMetric::Highest,
+            TextMetric::Highest => TextMetric::Cost,
+            TextMetric::Cost => TextMetric::Auto,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BillingMode {
+    /// Use API-key billing when `ANTHROPIC_API_KEY` is set and no OAuth login exists (default)
+    #[default]
+    Auto,
+    /// Claude subscription usage from the OAuth usage endpoint
+    Subscription,
+    /// Console API-key billing from the usage & cost report API
+    ApiKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ClickAction {
+    /// Do nothing
+    None,
+    /// Open or close the usage popup
+    #[default]
+    TogglePopup,
+    /// Launch `claude` in a terminal
+    OpenTerminal,
+    /// Poll the usage API immediately
+    Refresh,
+    /// Step to the next icon display mode
+    CycleIconDisplay,
+}
+
+impl ClickAction {
+    /// Next action in settings cycle order
+    pub fn next(self) -> Self {
+        match self {
+            ClickAction::None => ClickAction::TogglePopup,
+            ClickAction::TogglePopup => ClickAction::OpenTerminal,
+            ClickAction::OpenTerminal => ClickAction::Refresh,
+            ClickAction::Refresh => ClickAction::CycleIconDisplay,
+            ClickAction::CycleIconDisplay => ClickAction::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LogLevel {
+    /// Errors only (default)
+    #[default]
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// Next level in settings cycle order, from least to most verbose
+    pub fn next(self) -> Self {
+        match self {
+            LogLevel::Error => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Info,
+            LogLevel::Info => LogLevel::Debug,
+            LogLevel::Debug => LogLevel::Trace,
+            LogLevel::Trace => LogLevel::Error,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, CosmicConfigEntry)]
-#[version = 1]
+#[version = 2]
 pub struct ClaudeAppletConfig {
     /// Which usage indicator(s) to display in the icon
     pub icon_display: IconDisplay,
+    /// Shape of the panel usage indicators: rings, battery bars or text
+    pub indicator_style: IndicatorStyle,
+    /// Panel text in the text indicator style, with `{session}`, `{weekly}`, `{reset}` and `{cost}`
+    /// placeholders; unset shows the percentages of the windows picked by `icon_display`
+    pub text_template: Option<String>,
     /// Show Claude mascot alongside usage rings
     pub show_mascot: bool,
-    /// Threshold percentage for warning state (yellow)
-    pub warning_threshold: u8,
-    /// Threshold percentage for critical state (red)
-    pub critical_threshold: u8,
+    /// Animate the mascot by state: breathing, wiggling while sessions run, drooping at critical usage
+    pub animate_mascot: bool,
+    /// Shrink the panel indicator to a dot while logged out with no claude processes running
+    pub hide_when_inactive: bool,
+    /// Overlay the number of running claude processes on the panel indicator
+    pub show_process_badge: bool,
+    /// Count running Claude Desktop app instances along with Claude Code sessions
+    pub include_claude_desktop: bool,
+    /// Session usage percentage for the warning state (yellow)
+    pub session_warning_threshold: u8,
+    /// Session usage percentage for the critical state (red)
+    pub session_critical_threshold: u8,
+    /// Weekly usage percentage for the warning state (yellow)
+    pub weekly_warning_threshold: u8,
+    /// Weekly usage percentage for the critical state (red)
+    pub weekly_critical_threshold: u8,
+    /// Weekly Opus usage percentage that triggers a warning alert
+    pub opus_warning_threshold: u8,
+    /// Weekly Opus usage percentage that triggers a critical alert
+    pub opus_critical_threshold: u8,
     /// Show percentage text next to icon in panel
     pub show_percentage_text: bool,
+    /// Which number the percentage text shows, independent of the icon display
+    pub percentage_text_metric: TextMetric,
+    /// Show today's local cost next to icon in panel
+    pub show_cost_text: bool,
+    /// Show time until the session reset next to the icon in panel
+    pub show_reset_countdown: bool,
+    /// Show a compact preview with both usage windows while hovering the panel icon
+    pub hover_preview: bool,
+    /// Format clock times with a 24-hour clock (otherwise 12-hour with AM/PM)
+    pub use_24_hour_clock: bool,
+    /// Decimals shown for dollar amounts
+    pub cost_precision: u8,
+    /// Group digits of large numbers with the locale's separator (e.g. "12,345")
+    pub group_digits: bool,
+    /// How popup reset times are written
+    pub reset_time_format: ResetTimeFormat,
+    /// Show remaining capacity instead of usage (rings drain as usage grows)
+    pub show_remaining: bool,
     /// API poll interval in minutes
     pub poll_interval_minutes: u32,
+    /// Poll every few minutes while session usage is above the warning threshold or a reset is near
+    pub adaptive_polling: bool,
+    /// Query organization usage through the Admin API (key read from env or a private file)
+    pub show_organization_usage: bool,
+    /// Where usage data comes from: subscription (OAuth) or API-key billing
+    pub billing_mode: BillingMode,
+    /// Poll status.anthropic.com and show an indicator during incidents
+    pub show_service_status: bool,
+    /// Send a desktop notification when a new incident is reported
+    pub notify_service_incidents: bool,
+    /// Send a desktop notification when the subscription plan changes (e.g. after an upgrade)
+    pub notify_plan_changes: bool,
+    /// Plan tier seen in the last usage update, so changes made while the applet was not running are reported
+    pub last_plan_tier: Option<String>,
+    /// Send a desktop notification when session or weekly usage crosses a threshold
+    pub notify_thresholds: bool,
+    /// Notify when the weekly limit is forecast to run out before it resets
+    pub notify_weekly_forecast: bool,
+    /// How long "snooze alerts" suppresses threshold notifications
+    pub snooze_hours: u32,
+    /// Minutes without a transcript write before a running CLI session is shown as idle
+    pub idle_session_minutes: u32,
+    /// Send a desktop notification when a running CLI session becomes idle
+    pub notify_idle_sessions: bool,
+    /// Send a desktop notification when a CLI session starts or ends
+    pub notify_session_changes: bool,
+    /// Hold back notifications between `quiet_hours_start` and `quiet_hours_end`
+    pub quiet_hours: bool,
+    /// Hour of the day (local time, 0-23) quiet hours begin
+    pub quiet_hours_start: u32,
+    /// Hour of the day (local time, 0-23) quiet hours end
+    pub quiet_hours_end: u32,
+    /// Send a summary of the past seven days once a week
+    pub weekly_summary: bool,
+    /// Day the weekly summary is sent
+    pub weekly_summary_day: Weekday,
+    /// Hour of the day (local time, 0-23) the weekly summary is sent
+    pub weekly_summary_hour: u32,
+    /// Scheduled day of the last weekly summary that was sent (or skipped), kept across restarts
+    pub weekly_summary_sent_for: Option<NaiveDate>,
+    /// Shell command run when a window crosses the warning threshold
+    pub hook_on_warning: Option<String>,
+    /// Shell command run when a window crosses the critical threshold
+    pub hook_on_critical: Option<String>,
+    /// Shell command run when a usage window resets
+    pub hook_on_reset: Option<String>,
+    /// Monthly spending budget in USD; 0 disables budget tracking
+    pub monthly_budget_usd: f64,
+    /// Refresh model prices from LiteLLM's public pricing data once a day
+    pub sync_model_pricing: bool,
+    /// Days of usage history to keep (older samples are compacted into daily rollups first)
+    pub history_retention_days: u32,
+    /// Compare the installed Claude Code with the latest npm release
+    pub check_claude_updates: bool,
+    /// Check GitHub for new applet releases
+    pub check_applet_updates: bool,
+    /// Claude Code data directory (`~` allowed); unset uses `$CLAUDE_CONFIG_DIR` or `~/.claude`
+    pub claude_dir: Option<String>,
+    /// Base URL for the usage API (e.g. a gateway); unset uses `ANTHROPIC_BASE_URL` or api.anthropic.com
+    pub api_base_url: Option<String>,
+    /// Send the OAuth token to an `api_base_url` (or `ANTHROPIC_BASE_URL`) host outside anthropic.com
+    pub trust_custom_api_host: bool,
+    /// Command used to open directories, with arguments; unset uses `xdg-open`
+    pub file_manager_command: Option<String>,
+    /// Show the last (redacted) usage API response in the popup for troubleshooting
+    pub show_raw_response: bool,
+    /// Most verbose tracing level written to stderr and the log file (`RUST_LOG` overrides stderr)
+    pub log_level: LogLevel,
+    /// Also write logs to a size-rotated file under the XDG state directory
+    pub log_to_file: bool,
+    /// What a left click on the panel icon does
+    pub left_click_action: ClickAction,
+    /// What a middle click on the panel icon does
+    pub middle_click_action: ClickAction,
+    /// What a right click on the panel icon does
+    pub right_click_action: ClickAction,
+    /// Scrolling over the panel icon cycles the icon display mode
+    pub scroll_cycles_display: bool,
+    /// Show the "Today" section with activity totals and the usage streak
+    pub show_daily_summary: bool,
+    /// Maximum popup height in logical pixels; taller content scrolls
+    pub popup_max_height: u32,
+    /// Popup width in logical pixels
+    pub popup_width: u32,
+    /// Paddings and text sizes in the popup
+    pub popup_density: PopupDensity,
+    /// Popup sections to show, top to bottom; sections left out are hidden
+    pub popup_sections: Vec<PopupSection>,
+    /// Gently pulse the indicator while usage is above the critical threshold
+    pub pulse_when_critical: bool,
+    /// How ring colors are derived from usage percentage
+    pub color_mode: ColorMode,
+    /// Custom color for low usage as "#rrggbb" (theme success color when unset)
+    pub custom_low_color: Option<String>,
+    /// Custom color for medium usage as "#rrggbb" (theme warning color when unset)
+    pub custom_medium_color: Option<String>,
+    /// Custom color for high usage as "#rrggbb" (theme destructive color when unset)
+    pub custom_high_color: Option<String>,
+    /// Custom mascot color as "#rrggbb" (Claude orange when unset)
+    pub custom_mascot_color: Option<String>,
+    /// SVG file (`~` allowed) drawn instead of the built-in mascot; `currentColor` in it follows the usage level
+    pub custom_mascot_path: Option<String>,
 }
 
 impl Default for ClaudeAppletConfig {
     fn default() -> Self {
         Self {
             icon_display: IconDisplay::default(),
+            indicator_style: IndicatorStyle::default(),
+            text_template: None,
             show_mascot: true,
-            warning_threshold: 50,
-            critical_threshold: 80,
+            animate_mascot: true,
+            hide_when_inactive: false,
+            show_process_badge: false,
+            include_claude_desktop: false,
+            session_warning_threshold: 50,
+            session_critical_threshold: 80,
+            weekly_warning_threshold: 50,
+            weekly_critical_threshold: 80,
+            opus_warning_threshold: 50,
+            opus_critical_threshold: 80,
             show_percentage_text: false,
+            percentage_text_metric: TextMetric::default(),
+            show_cost_text: false,
+            show_reset_countdown: false,
+            hover_preview: false,
+            use_24_hour_clock: true,
+            cost_precision: 2,
+            group_digits: true,
+            reset_time_format: ResetTimeFormat::default(),
+            show_remaining: false,
             poll_interval_minutes: 60,
+            adaptive_polling: true,
+            show_organization_usage: false,
+            billing_mode: BillingMode::default(),
+            show_service_status: true,
+            notify_service_incidents: false,
+            notify_plan_changes: true,
+            last_plan_tier: None,
+            notify_thresholds: false,
+            notify_weekly_forecast: false,
+            snooze_hours: 2,
+            idle_session_minutes: 30,
+            notify_idle_sessions: false,
+            notify_session_changes: false,
+            quiet_hours: false,
+            quiet_hours_start: 22,
+            quiet_hours_end: 8,
+            weekly_summary: false,
+            weekly_summary_day: Weekday::Sun,
+            weekly_summary_hour: 18,
+            weekly_summary_sent_for: None,
+            hook_on_warning: None,
+            hook_on_critical: None,
+            hook_on_reset: None,
+            monthly_budget_usd: 0.0,
+            sync_model_pricing: false,
+            history_retention_days: 90,
+            check_claude_updates: true,
+            check_applet_updates: true,
+            claude_dir: None,
+            api_base_url: None,
+            trust_custom_api_host: false,
+            file_manager_command: None,
+            show_raw_response: false,
+            log_level: LogLevel::default(),
+            log_to_file: false,
+            left_click_action: ClickAction::TogglePopup,
+            middle_click_action: ClickAction::None,
+            right_click_action: ClickAction::None,
+            scroll_cycles_display: true,
+            show_daily_summary: true,
+            popup_max_height: 600,
+            popup_width: 360,
+            popup_density: PopupDensity::default(),
+            popup_sections: PopupSection::ALL.to_vec(),
+            pulse_when_critical: false,
+            color_mode: ColorMode::default(),
+            custom_low_color: None,
+            custom_medium_color: None,
+            custom_high_color: None,
+            custom_mascot_color: None,
+            custom_mascot_path: None,
         }
     }
 }
 
 impl ClaudeAppletConfig {
     /// Validate and clamp config values to sensible ranges.
-    /// Ensures warning_threshold < critical_threshold and values are within bounds.
+    /// Ensures each warning threshold is below its critical threshold and values are within bounds.
     pub fn validate(&mut self) {
-        self.warning_threshold = self.warning_threshold.min(100);
-        self.critical_threshold = self.critical_threshold.clamp(self.warning_threshold.saturating_add(1), 100);
+        for (warning, critical) in [
+            (&mut self.session_warning_threshold, &mut self.session_critical_threshold),
+            (&mut self.weekly_warning_threshold, &mut self.weekly_critical_threshold),
+            (&mut self.opus_warning_threshold, &mut self.opus_critical_threshold),
+        ] {
+            *warning = (*warning).min(100);
+            *critical = (*critical).clamp(warning.saturating_add(1), 100);
+        }
         self.poll_interval_minutes = self.poll_interval_minutes.clamp(1, 1440);
+        self.popup_max_height = self.popup_max_height.clamp(300, 1200);
+        self.popup_width = self.popup_width.clamp(280, 600);
+        self.snooze_hours = self.snooze_hours.clamp(1, 24);
+        self.idle_session_minutes = self.idle_session_minutes.clamp(5, 240);
+        self.cost_precision = self.cost_precision.min(4);
+        self.weekly_summary_hour = self.weekly_summary_hour.min(23);
+        self.quiet_hours_start = self.quiet_hours_start.min(23);
+        self.quiet_hours_end = self.quiet_hours_end.min(23);
+        self.history_retention_days = self.history_retention_days.clamp(7, 3650);
+        let mut seen = Vec::with_capacity(self.popup_sections.len());
+        self.popup_sections.retain(|section| {
+            let first = !seen.contains(section);
+            seen.push(*section);
+            first
+        });
+        if !self.monthly_budget_usd.is_finite() || self.monthly_budget_usd < 0.0 {
+            self.monthly_budget_usd = 0.0;
+        }
+        for color in [
+            &mut self.custom_low_color,
+            &mut self.custom_medium_color,
+            &mut self.custom_high_color,
+            &mut self.custom_mascot_color,
+        ] {
+            if color.as_deref().and_then(parse_hex_color).is_none() {
+                *color = None;
+            }
+        }
+    }
+}
+
+/// Parse a "#rrggbb" (or "rrggbb") hex string into a color
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Path with a leading `~` expanded to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Whether the desktop asks for reduced motion, via the GTK `gtk-enable-animations` setting
+pub fn prefers_reduced_motion() -> bool {
+    let Some(config_dir) = dirs::config_dir() else {
+        return false;
+    };
+
+    ["gtk-4.0", "gtk-3.0"].iter().any(|gtk| {
+        std::fs::read_to_string(config_dir.join(gtk).join("settings.ini"))
+            .map(|contents| {
+                contents.lines().any(|line| {
+                    let line = line.replace(' ', "");
+                    line == "gtk-enable-animations=false" || line == "gtk-enable-animations=0"
+                })
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Raw config fields as cosmic-config stores them: field name -> RON-encoded value
+pub type RawFields = BTreeMap<String, String>;
+
+/// Upgrades the raw fields of one config version to the next
+type Migration = fn(&mut RawFields);
+
+/// `MIGRATIONS[i]` upgrades version `i + 1` to version `i + 2`.
+/// Bumping `#[version]` on `ClaudeAppletConfig` requires appending a step here.
+const MIGRATIONS: &[Migration] = &[split_thresholds];
+
+/// v1 -> v2: the combined warning/critical thresholds seed the separate session and weekly ones
+fn split_thresholds(fields: &mut RawFields) {
+    for level in ["warning", "critical"] {
+        if let Some(value) = fields.remove(&format!("{level}_threshold")) {
+            fields.insert(format!("session_{level}_threshold"), value.clone());
+            fields.insert(format!("weekly_{level}_threshold"), value);
+        }
+    }
+}
+
+/// Apply every migration step from `from` up to the end of `migrations`, returning the resulting version
+fn apply_migrations(fields: &mut RawFields, from: u64, migrations: &[Migration]) -> u64 {
+    let mut version = from.max(1);
+    while let Some(step) = migrations.get(version as usize - 1) {
+        step(fields);
+        version += 1;
+    }
+    version
+}
+
+/// Directory cosmic-config uses for a given config version
+fn version_dir(version: u64) -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("cosmic").join(APP_ID).join(format!("v{version}")))
+}
+
+fn read_fields(dir: &Path) -> std::io::Result<RawFields> {
+    let mut fields = RawFields::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            if let Some(name) = entry.file_name().to_str() {
+                fields.insert(name.to_string(), std::fs::read_to_string(entry.path())?);
+            }
+        }
+    }
+    Ok(fields)
+}
+
+fn write_fields(dir: &Path, fields: &RawFields) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for (name, value) in fields {
+        std::fs::write(dir.join(name), value)?;
+    }
+    Ok(())
+}
+
+/// Carry settings from the newest older config version forward to the current one.
+/// Does nothing once the current version exists, so it only runs on the first start after an upgrade.
+pub fn migrate_legacy_config() {
+    let current = ClaudeAppletConfig::VERSION;
+    let Some(current_dir) = version_dir(current) else {
+        return;
+    };
+    if current_dir.exists() {
+        return;
+    }
+
+    let Some((from, dir)) = (1..current)
+        .rev()
+        .filter_map(|version| Some((version, version_dir(version)?)))
+        .find(|(_, dir)| dir.is_dir())
+    else {
+        return;
+    };
+
+    let result = read_fields(&dir).and_then(|mut fields| {
+        let reached = apply_migrations(&mut fields, from, MIGRATIONS);
+        if reached != current {
+            tracing::warn!("No migration path from config v{} to v{}; using defaults", from, current);
+            return Ok(());
+        }
+        write_fields(&current_dir, &fields)?;
+        tracing::info!("Migrated config from v{} to v{}", from, current);
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        tracing::error!(?err, "Failed to migrate config from v{}", from);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> RawFields {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn parse_hex_color_accepts_an_optional_hash() {
+        assert_eq!(parse_hex_color("#ff8000"), Some(Color::from_rgb8(255, 128, 0)));
+        assert_eq!(parse_hex_color(" 0A0b0C "), Some(Color::from_rgb8(10, 11, 12)));
+    }
+
+    #[test]
+    fn parse_hex_color_rejects_malformed_values() {
+        for value in ["", "#fff", "#ff80000", "#gg8000", "ff 800"] {
+            assert_eq!(parse_hex_color(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn migrations_run_in_order_from_the_stored_version() {
+        let steps: &[Migration] = &[
+            |f| {
+                f.insert("b".to_string(), "1".to_string());
+            },
+            |f| {
+                f.insert("c".to_string(), "2".to_string());
+            },
+        ];
+
+        let mut from_v1 = fields(&[("a", "0")]);
+        assert_eq!(apply_migrations(&mut from_v1, 1, steps), 3);
+        assert_eq!(from_v1, fields(&[("a", "0"), ("b", "1"), ("c", "2")]));
+
+        let mut from_v2 = fields(&[("a", "0")]);
+        assert_eq!(apply_migrations(&mut from_v2, 2, steps), 3);
+        assert_eq!(from_v2, fields(&[("a", "0"), ("c", "2")]));
+    }
+
+    #[test]
+    fn split_thresholds_copies_combined_values_to_both_windows() {
+        let mut raw = fields(&[("warning_threshold", "60"), ("critical_threshold", "90"), ("show_mascot", "true")]);
+        split_thresholds(&mut raw);
+        assert_eq!(
+            raw,
+            fields(&[
+                ("session_critical_threshold", "90"),
+                ("session_warning_threshold", "60"),
+                ("show_mascot", "true"),
+                ("weekly_critical_threshold", "90"),
+                ("weekly_warning_threshold", "60"),
+            ])
+        );
+    }
+
+    #[test]
+    fn current_version_is_reachable() {
+        let mut raw = RawFields::new();
+        assert_eq!(apply_migrations(&mut raw, 1, MIGRATIONS), ClaudeAppletConfig::VERSION);
     }
 }
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::backend::api::UsageUpdate;
+use cosmic::iced::futures::{SinkExt, StreamExt};
+use cosmic::iced::Subscription;
+use cosmic::iced_futures::stream;
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::mpsc;
+use zbus::fdo::{DBusProxy, RequestNameReply};
+use zbus::object_server::SignalEmitter;
+use zbus::{interface, proxy, Connection};
+
+/// Well-known name owned by the primary instance; also the name D-Bus activation starts the applet for
+pub const BUS_NAME: &str = "dev.m4ul3r.CosmicExtAppletClaude";
+const OBJECT_PATH: &str = "/dev/m4ul3r/CosmicExtAppletClaude";
+
+/// Session bus connection, shared by the service and the mirror of a duplicate instance
+static CONNECTION: OnceLock<Connection> = OnceLock::new();
+
+/// Last usage update published by the primary instance, as JSON
+static LAST_USAGE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Events from the session bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbusEvent {
+    /// Whether this instance owns the bus name and runs the usage poller.
+    /// A duplicate stays queued for the name and takes over when the primary exits.
+    Primary(bool),
+    /// An external caller asked for a refresh
+    Refresh,
+    /// An external caller, e.g. a custom keyboard shortcut, asked to open or close the popup
+    TogglePopup,
+}
+
+struct Service {
+    events: mpsc::UnboundedSender<DbusEvent>,
+}
+
+#[interface(name = "dev.m4ul3r.CosmicExtAppletClaude")]
+impl Service {
+    /// Poll usage now
+    async fn refresh(&self) {
+        let _ = self.events.send(DbusEvent::Refresh);
+    }
+
+    /// Open or close the popup
+    async fn toggle_popup(&self) {
+        let _ = self.events.send(DbusEvent::TogglePopup);
+    }
+
+    /// The last usage update as JSON, empty before the first fetch
+    async fn usage(&self) -> String {
+        LAST_USAGE.lock().ok().and_then(|usage| usage.clone()).unwrap_or_default()
+    }
+
+    /// Sent with every usage update of the primary instance
+    #[zbus(signal)]
+    async fn usage_changed(emitter: &SignalEmitter<'_>, usage: &str) -> zbus::Result<()>;
+}
+
+#[proxy(
+    interface = "dev.m4ul3r.CosmicExtAppletClaude",
+    default_service = "dev.m4ul3r.CosmicExtAppletClaude",
+    default_path = "/dev/m4ul3r/CosmicExtAppletClaude"
+)]
+trait Applet {
+    fn refresh(&self) -> zbus::Result<()>;
+
+    fn usage(&self) -> zbus::Result<String>;
+
+    #[zbus(signal)]
+    fn usage_changed(&self, usage: String) -> zbus::Result<()>;
+}
+
+/// Subscription that serves the applet's D-Bus interface and claims the bus name.
+/// Without a session bus the instance runs on its own as the primary.
+pub fn dbus_subscription() -> Subscription<DbusEvent> {
+    Subscription::run_with_id(
+        "claude-dbus",
+        stream::channel(4, |mut output| async move {
+            let (events, mut requests) = mpsc::unbounded_channel();
+            let (connection, primary) = match connect(events).await {
+                Ok(connected) => connected,
+                Err(err) => {
+                    tracing::warn!(?err, "D-Bus unavailable, running as a standalone instance");
+                    let _ = output.send(DbusEvent::Primary(true)).await;
+                    return;
+                }
+            };
+            if !primary {
+                tracing::info!("Another instance owns {BUS_NAME}, mirroring its usage updates");
+            }
+            let _ = output.send(DbusEvent::Primary(primary)).await;
+
+            let acquired = match DBusProxy::new(&connection).await {
+                Ok(bus) => bus.receive_name_acquired().await.ok(),
+                Err(_) => None,
+            };
+            // Without the signal stream a duplicate simply stays a mirror
+            let mut acquired = cosmic::iced::futures::stream::iter(acquired).flatten().boxed();
+
+            loop {
+                let event = tokio::select! {
+                    Some(signal) = acquired.next() => {
+                        if !signal.args().is_ok_and(|args| args.name() == BUS_NAME) {
+                            continue;
+                        }
+                        tracing::info!("Took over {BUS_NAME} from the previous primary instance");
+                        DbusEvent::Primary(true)
+                    }
+                    Some(event) = requests.recv() => event,
+                    else => return,
+                };
+                let _ = output.send(event).await;
+            }
+        }),
+    )
+}
+
+/// Connect to the session bus, serve the interface and queue for the bus name
+async fn connect(events: mpsc::UnboundedSender<DbusEvent>) -> zbus::Result<(Connection, bool)> {
+    let connection = zbus::connection::Builder::session()?
+        .serve_at(OBJECT_PATH, Service { events })?
+        .build()
+        .await?;
+    // No flags: wait in the queue instead of failing, and never hand the name over while running
+    let reply = connection.request_name_with_flags(BUS_NAME, Default::default()).await?;
+    let primary = matches!(reply, RequestNameReply::PrimaryOwner | RequestNameReply::AlreadyOwner);
+    let _ = CONNECTION.set(connection.clone());
+    Ok((connection, primary))
+}
+
+/// Subscription for duplicate instances: the primary's current usage, then every update it publishes
+pub fn mirror_subscription() -> Subscription<UsageUpdate> {
+    Subscription::run_with_id(
+        "claude-dbus-mirror",
+        stream::channel(4, |mut output| async move {
+            let Some(connection) = CONNECTION.get() else {
+                return;
+            };
+            let result: zbus::Result<()> = async {
+                let proxy = AppletProxy::new(connection).await?;
+                let mut changes = proxy.receive_usage_changed().await?;
+                if let Some(update) = parse_usage(&proxy.usage().await?) {
+                    let _ = output.send(update).await;
+                }
+                while let Some(signal) = changes.next().await {
+                    if let Some(update) = signal.args().ok().and_then(|args| parse_usage(args.usage())) {
+                        let _ = output.send(update).await;
+                    }
+                }
+                Ok(())
+            }
+            .await;
+            if let Err(err) = result {
+                tracing::warn!(?err, "Failed to mirror usage from the primary instance");
+            }
+        }),
+    )
+}
+
+fn parse_usage(json: &str) -> Option<UsageUpdate> {
+    if json.is_empty() {
+        return None;
+    }
+    serde_json::from_str(json)
+        .inspect_err(|err| tracing::warn!(?err, "Invalid usage update from the primary instance"))
+        .ok()
+}
+
+/// Keep a usage update for duplicate instances and broadcast it to the running ones
+pub fn publish_usage(update: &UsageUpdate) {
+    let Ok(json) = serde_json::to_string(update) else {
+        return;
+    };
+    if let Ok(mut last) = LAST_USAGE.lock() {
+        *last = Some(json.clone());
+    }
+    let Some(connection) = CONNECTION.get().cloned() else {
+        return;
+    };
+    tokio::spawn(async move {
+        let result = match SignalEmitter::new(&connection, OBJECT_PATH) {
+            Ok(emitter) => Service::usage_changed(&emitter, &json).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            tracing::warn!(?err, "Failed to broadcast usage update");
+        }
+    });
+}
+
+/// Ask the primary instance to poll now, for refreshes requested in a duplicate
+pub fn request_refresh() {
+    let Some(connection) = CONNECTION.get().cloned() else {
+        return;
+    };
+    tokio::spawn(async move {
+        let result = match AppletProxy::new(&connection).await {
+            Ok(proxy) => proxy.refresh().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            tracing::warn!(?err, "Failed to forward refresh to the primary instance");
+        }
+    });
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+/// Whether the applet runs inside a flatpak sandbox
+static SANDBOXED: LazyLock<bool> =
+    LazyLock::new(|| std::path::Path::new("/.flatpak-info").exists() || std::env::var_os("FLATPAK_ID").is_some());
+
+pub fn is_sandboxed() -> bool {
+    *SANDBOXED
+}
+
+/// Command that runs `program` on the host, through `flatpak-spawn --host` when sandboxed
+pub fn host_command(program: &str) -> std::process::Command {
+    host_command_with_env(program, &[])
+}
+
+/// Like [`host_command`], with environment variables that reach the host process as well.
+/// `flatpak-spawn` does not forward its own environment, so they are passed as `--env` options.
+pub fn host_command_with_env(program: &str, envs: &[(&str, String)]) -> std::process::Command {
+    if is_sandboxed() {
+        let mut cmd = std::process::Command::new("flatpak-spawn");
+        cmd.arg("--host");
+        for (name, value) in envs {
+            cmd.arg(format!("--env={name}={value}"));
+        }
+        cmd.arg(program);
+        cmd
+    } else {
+        let mut cmd = std::process::Command::new(program);
+        cmd.envs(envs.iter().cloned());
+        cmd
+    }
+}
+
+/// Async variant of [`host_command`]
+pub fn host_tokio_command(program: &str) -> tokio::process::Command {
+    host_command(program).into()
+}
+
+/// Command that opens a file, directory or URL in the default application.
+/// Inside the sandbox `xdg-open` is flatpak-xdg-utils, which goes through the OpenURI portal.
+pub fn open_command(target: impl AsRef<std::ffi::OsStr>) -> std::process::Command {
+    let mut cmd = std::process::Command::new("xdg-open");
+    cmd.arg(target);
+    cmd
+}
+
+/// The host's config directory; the sandbox remaps `XDG_CONFIG_HOME` to the app's private one
+pub fn host_config_dir() -> Option<PathBuf> {
+    if !is_sandboxed() {
+        return dirs::config_dir();
+    }
+    std::env::var_os("HOST_XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+}
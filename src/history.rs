@@ -0,0 +1,418 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use cosmic::iced::{futures::SinkExt, Subscription};
+use cosmic::iced_futures::stream;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Samples older than this are folded into daily rollups
+const ROLLUP_AFTER_DAYS: i64 = 7;
+const COMPACTION_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Held while the history file is written; compaction rewrites it and would drop samples appended meanwhile
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// One usage reading taken from the usage API
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UsageSample {
+    pub timestamp: DateTime<Utc>,
+    pub session_percent: f32,
+    pub weekly_percent: f32,
+}
+
+/// Summary of one day's samples, kept after the raw samples are compacted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DailyRollup {
+    pub date: NaiveDate,
+    pub samples: u32,
+    pub avg_session_percent: f32,
+    pub max_session_percent: f32,
+    pub avg_weekly_percent: f32,
+    pub max_weekly_percent: f32,
+}
+
+impl DailyRollup {
+    fn from_samples(date: NaiveDate, samples: &[UsageSample]) -> Self {
+        let count = samples.len().max(1) as f32;
+        Self {
+            date,
+            samples: samples.len() as u32,
+            avg_session_percent: samples.iter().map(|s| s.session_percent).sum::<f32>() / count,
+            max_session_percent: samples.iter().map(|s| s.session_percent).fold(0.0, f32::max),
+            avg_weekly_percent: samples.iter().map(|s| s.weekly_percent).sum::<f32>() / count,
+            max_weekly_percent: samples.iter().map(|s| s.weekly_percent).fold(0.0, f32::max),
+        }
+    }
+}
+
+/// Per-day activity from the stats cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyStats {
+    pub date: String,
+    pub messages: u32,
+    pub sessions: u32,
+    pub cost_usd: f64,
+}
+
+/// Per-project cost over the last 7 days
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectStats {
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub cost_usd: f64,
+    pub messages: u32,
+}
+
+/// Totals for the seven days ending on a given date, sent as the weekly summary
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WeeklySummary {
+    pub messages: u32,
+    pub sessions: u32,
+    pub cost_usd: f64,
+    pub peak_weekly_percent: f32,
+}
+
+/// Everything written by "Export data"
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportData {
+    pub samples: Vec<UsageSample>,
+    pub rollups: Vec<DailyRollup>,
+    pub daily: Vec<DailyStats>,
+    pub projects: Vec<ProjectStats>,
+}
+
+/// Usage samples are appended to `~/.local/share/cosmic-applet-claude/history.jsonl`
+fn history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("cosmic-applet-claude").join("history.jsonl"))
+}
+
+/// Daily rollups of compacted samples live next to the raw history
+fn rollups_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("cosmic-applet-claude").join("history-daily.jsonl"))
+}
+
+fn read_jsonl<T: DeserializeOwned>(path: Option<PathBuf>) -> Vec<T> {
+    let Some(file) = path.and_then(|p| std::fs::File::open(p).ok()) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Replace a JSONL file atomically so a crash never leaves it half written
+fn write_jsonl<T: Serialize>(path: &Path, items: &[T]) -> std::io::Result<()> {
+    let mut contents = String::new();
+    for item in items {
+        contents.push_str(&serde_json::to_string(item)?);
+        contents.push('\n');
+    }
+    let tmp = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(tmp, path)
+}
+
+/// Append a sample to the history file
+pub fn append(sample: UsageSample) {
+    let Some(path) = history_path() else {
+        return;
+    };
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let result = (|| -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", serde_json::to_string(&sample)?)
+    })();
+    if let Err(err) = result {
+        tracing::error!(?err, "Failed to append usage history");
+    }
+}
+
+/// Load every stored sample, skipping unreadable lines
+pub fn load() -> Vec<UsageSample> {
+    read_jsonl(history_path())
+}
+
+/// Load the daily rollups of compacted samples
+pub fn load_rollups() -> Vec<DailyRollup> {
+    read_jsonl(rollups_path())
+}
+
+/// Summarize the seven days ending on `end`: activity and cost from the daily stats,
+/// peak weekly utilization from the stored samples and rollups
+pub fn weekly_summary(end: NaiveDate, daily: &[DailyStats]) -> WeeklySummary {
+    let start = end - chrono::Duration::days(6);
+    let in_week = |date: NaiveDate| (start..=end).contains(&date);
+
+    let mut summary = WeeklySummary::default();
+    for day in daily {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        if in_week(date) {
+            summary.messages += day.messages;
+            summary.sessions += day.sessions;
+            summary.cost_usd += day.cost_usd;
+        }
+    }
+
+    let sample_peak = load()
+        .iter()
+        .filter(|s| in_week(s.timestamp.with_timezone(&Local).date_naive()))
+        .map(|s| s.weekly_percent)
+        .fold(0.0, f32::max);
+    let rollup_peak = load_rollups()
+        .iter()
+        .filter(|r| in_week(r.date))
+        .map(|r| r.max_weekly_percent)
+        .fold(0.0, f32::max);
+    summary.peak_weekly_percent = sample_peak.max(rollup_peak);
+    summary
+}
+
+/// Fold samples older than a week into daily rollups and drop rollups past the retention period.
+/// Returns how many raw samples were compacted.
+fn compact(retention_days: u32) -> std::io::Result<usize> {
+    let (Some(history), Some(rollups_file)) = (history_path(), rollups_path()) else {
+        return Ok(0);
+    };
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    if !history.exists() {
+        return Ok(0);
+    }
+
+    let today = Local::now().date_naive();
+    let (recent, rollups, compacted) = compact_samples(load(), load_rollups(), today, retention_days);
+    write_jsonl(&rollups_file, &rollups)?;
+    write_jsonl(&history, &recent)?;
+    Ok(compacted)
+}
+
+/// Split stored history into the samples kept raw and the daily rollups, as of `today`.
+/// Returns the kept samples, the rollups and how many samples were folded into rollups.
+fn compact_samples(
+    samples: Vec<UsageSample>,
+    rollups: Vec<DailyRollup>,
+    today: NaiveDate,
+    retention_days: u32,
+) -> (Vec<UsageSample>, Vec<DailyRollup>, usize) {
+    let rollup_before = today - chrono::Duration::days(ROLLUP_AFTER_DAYS);
+    let keep_after = today - chrono::Duration::days(retention_days as i64);
+
+    let (old, recent): (Vec<UsageSample>, Vec<UsageSample>) = samples
+        .into_iter()
+        .filter(|s| s.timestamp.with_timezone(&Local).date_naive() > keep_after)
+        .partition(|s| s.timestamp.with_timezone(&Local).date_naive() < rollup_before);
+
+    let mut by_day: BTreeMap<NaiveDate, Vec<UsageSample>> = BTreeMap::new();
+    for sample in &old {
+        by_day
+            .entry(sample.timestamp.with_timezone(&Local).date_naive())
+            .or_default()
+            .push(*sample);
+    }
+
+    let mut rollups: BTreeMap<NaiveDate, DailyRollup> = rollups
+        .into_iter()
+        .filter(|r| r.date > keep_after)
+        .map(|r| (r.date, r))
+        .collect();
+    for (date, samples) in &by_day {
+        // A day is only compacted once, so an existing rollup is never overwritten
+        rollups
+            .entry(*date)
+            .or_insert_with(|| DailyRollup::from_samples(*date, samples));
+    }
+
+    (recent, rollups.into_values().collect(), old.len())
+}
+
+/// Subscription that compacts the history at startup and every few hours after
+pub fn compaction_subscription(retention_days: u32) -> Subscription<usize> {
+    Subscription::run_with_id(
+        std::sync::Arc::new(("claude-history-compaction", retention_days)),
+        stream::channel(1, move |mut output| async move {
+            loop {
+                match tokio::task::spawn_blocking(move || compact(retention_days)).await {
+                    Ok(Ok(compacted)) => {
+                        let _ = output.send(compacted).await;
+                    }
+                    Ok(Err(err)) => tracing::error!(?err, "Failed to compact usage history"),
+                    Err(err) => tracing::error!(?err, "History compaction task failed"),
+                }
+                tokio::time::sleep(COMPACTION_INTERVAL).await;
+            }
+        }),
+    )
+}
+
+/// Write export data as JSON when the path ends in `.json`, CSV otherwise.
+/// The CSV is a single table; the `type` column tells sample, daily and project rows apart.
+pub fn export(data: &ExportData, path: &Path) -> Result<(), String> {
+    let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let contents = if is_json {
+        serde_json::to_string_pretty(data).map_err(|err| err.to_string())?
+    } else {
+        to_csv(data)
+    };
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// CSV keeps `.` decimals, no digit grouping and unrounded costs so spreadsheets parse and sum them exactly
+fn to_csv(data: &ExportData) -> String {
+    let mut csv = String::from("type,date,session_percent,weekly_percent,messages,sessions,cost_usd,project\n");
+    for sample in &data.samples {
+        csv.push_str(&format!(
+            "sample,{},{:.1},{:.1},,,,\n",
+            sample.timestamp.to_rfc3339(),
+            sample.session_percent,
+            sample.weekly_percent
+        ));
+    }
+    for rollup in &data.rollups {
+        csv.push_str(&format!(
+            "rollup,{},{:.1},{:.1},,,,\n",
+            rollup.date, rollup.avg_session_percent, rollup.avg_weekly_percent
+        ));
+    }
+    for day in &data.daily {
+        csv.push_str(&format!(
+            "daily,{},,,{},{},{},\n",
+            csv_field(&day.date),
+            day.messages,
+            day.sessions,
+            day.cost_usd
+        ));
+    }
+    for project in &data.projects {
+        let name = project
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| project.name.clone());
+        csv.push_str(&format!(
+            "project,,,,{},,{},{}\n",
+            project.messages,
+            project.cost_usd,
+            csv_field(&name)
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: &str) -> NaiveDate {
+        day.parse().unwrap()
+    }
+
+    /// A sample taken at local noon on `day`
+    fn sample_on(day: &str, percent: f32) -> UsageSample {
+        UsageSample {
+            timestamp: date(day).and_hms_opt(12, 0, 0).unwrap().and_local_timezone(Local).unwrap().with_timezone(&Utc),
+            session_percent: percent,
+            weekly_percent: percent,
+        }
+    }
+
+    #[test]
+    fn csv_field_quotes_separators_and_doubles_quotes() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn to_csv_writes_one_row_per_record() {
+        let mut data = ExportData::default();
+        data.samples.push(UsageSample {
+            timestamp: "2026-10-01T12:00:00Z".parse().unwrap(),
+            session_percent: 42.3,
+            weekly_percent: 10.0,
+        });
+        data.daily.push(DailyStats {
+            date: "2026-10-01".to_string(),
+            messages: 12,
+            sessions: 2,
+            cost_usd: 1.25,
+        });
+        data.projects.push(ProjectStats {
+            name: "app".to_string(),
+            path: Some(PathBuf::from("/home/me/a,b")),
+            cost_usd: 0.5,
+            messages: 3,
+        });
+
+        let csv = to_csv(&data);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "type,date,session_percent,weekly_percent,messages,sessions,cost_usd,project",
+                "sample,2026-10-01T12:00:00+00:00,42.3,10.0,,,,",
+                "daily,2026-10-01,,,12,2,1.25,",
+                "project,,,,3,,0.5,\"/home/me/a,b\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn compaction_folds_samples_older_than_a_week_into_daily_rollups() {
+        let samples = vec![sample_on("2026-10-08", 20.0), sample_on("2026-10-08", 40.0), sample_on("2026-10-09", 50.0)];
+        let (recent, rollups, compacted) = compact_samples(samples, Vec::new(), date("2026-10-16"), 90);
+
+        assert_eq!(compacted, 2);
+        assert_eq!(recent, [sample_on("2026-10-09", 50.0)]);
+        assert_eq!(rollups.len(), 1);
+        assert_eq!(rollups[0].date, date("2026-10-08"));
+        assert_eq!(rollups[0].samples, 2);
+        assert_eq!(rollups[0].avg_session_percent, 30.0);
+        assert_eq!(rollups[0].max_weekly_percent, 40.0);
+    }
+
+    #[test]
+    fn compaction_drops_samples_and_rollups_past_retention() {
+        let samples = vec![sample_on("2026-09-16", 10.0), sample_on("2026-09-17", 20.0)];
+        let rollups = vec![
+            DailyRollup::from_samples(date("2026-09-16"), &[sample_on("2026-09-16", 5.0)]),
+            DailyRollup::from_samples(date("2026-09-18"), &[sample_on("2026-09-18", 5.0)]),
+        ];
+        let (recent, rollups, compacted) = compact_samples(samples, rollups, date("2026-10-16"), 30);
+
+        assert!(recent.is_empty());
+        assert_eq!(compacted, 1);
+        let dates: Vec<NaiveDate> = rollups.iter().map(|r| r.date).collect();
+        assert_eq!(dates, [date("2026-09-17"), date("2026-09-18")]);
+    }
+
+    #[test]
+    fn compaction_keeps_an_existing_rollup_for_the_same_day() {
+        let existing = DailyRollup::from_samples(date("2026-10-01"), &[sample_on("2026-10-01", 80.0); 5]);
+        let samples = vec![sample_on("2026-10-01", 10.0)];
+        let (recent, rollups, compacted) = compact_samples(samples, vec![existing], date("2026-10-16"), 90);
+
+        assert!(recent.is_empty());
+        assert_eq!(compacted, 1);
+        assert_eq!(rollups, [existing]);
+    }
+}
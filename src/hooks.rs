@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+/// Usage event a user-defined command can be attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Warning,
+    Critical,
+    Reset,
+}
+
+impl HookEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::Warning => "warning",
+            HookEvent::Critical => "critical",
+            HookEvent::Reset => "reset",
+        }
+    }
+}
+
+/// Usage snapshot exported to hook commands as environment variables
+#[derive(Debug, Clone, Copy)]
+pub struct HookContext {
+    /// Which window triggered the event: "session" or "weekly"
+    pub window: &'static str,
+    pub session_percent: f32,
+    pub weekly_percent: f32,
+}
+
+/// Run a hook command through `sh -c` in the background, logging failures.
+/// The command sees CLAUDE_EVENT, CLAUDE_WINDOW, CLAUDE_SESSION_PCT and CLAUDE_WEEKLY_PCT.
+pub fn run(command: Option<&str>, event: HookEvent, context: HookContext) {
+    let Some(command) = command.map(str::trim).filter(|c| !c.is_empty()) else {
+        return;
+    };
+
+    let envs = [
+        ("CLAUDE_EVENT", event.as_str().to_string()),
+        ("CLAUDE_WINDOW", context.window.to_string()),
+        ("CLAUDE_SESSION_PCT", format!("{:.0}", context.session_percent)),
+        ("CLAUDE_WEEKLY_PCT", format!("{:.0}", context.weekly_percent)),
+    ];
+    let mut cmd: tokio::process::Command = crate::flatpak::host_command_with_env("sh", &envs).into();
+    cmd.arg("-c").arg(command);
+
+    tracing::debug!("Running {} hook: {}", event.as_str(), command);
+    tokio::spawn(async move {
+        match cmd.status().await {
+            Ok(status) if !status.success() => {
+                tracing::warn!("{} hook exited with {}", event.as_str(), status);
+            }
+            Ok(_) => {}
+            Err(err) => tracing::error!(?err, "Failed to run {} hook", event.as_str()),
+        }
+    });
+}
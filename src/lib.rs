@@ -2,12 +2,25 @@
 
 mod backend;
 mod config;
+mod dbus;
+mod flatpak;
+mod history;
+mod hooks;
 mod localize;
+mod logs;
+mod notifications;
+mod ring;
+mod signals;
+mod supervisor;
 
-use backend::{api, process, stats};
+use backend::{admin, api, pricing, process, release, stats, status, transcripts, version};
 use tracing::debug;
-use chrono::{DateTime, Utc};
-use config::{ClaudeAppletConfig, IconDisplay};
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone, Timelike, Utc};
+use config::{
+    BillingMode, ClaudeAppletConfig, ClickAction, ColorMode, IconDisplay, IndicatorStyle, LogLevel, PopupDensity,
+    PopupSection, ResetTimeFormat, TextMetric,
+};
+use hooks::{HookContext, HookEvent};
 use cosmic::{
     Element, Task, app,
     app::Core,
@@ -15,78 +28,373 @@ use cosmic::{
     cosmic_config::CosmicConfigEntry,
     cosmic_theme::Spacing,
     iced::{
-        Alignment, Color, Length, Subscription,
+        Alignment, Color, Length, Size, Subscription, keyboard, mouse,
         platform_specific::shell::wayland::commands::popup::{destroy_popup, get_popup},
-        widget::svg,
-        window::Id,
+        time,
+        widget::{canvas, svg},
+        window::{self, Id},
     },
-    iced_widget::{column, row},
+    iced_widget::{column, row, stack},
     theme,
     widget::{
-        button, divider, horizontal_space, mouse_area, text, progress_bar, slider, toggler,
+        button, container, divider, horizontal_space, icon, mouse_area, scrollable, segmented_button, settings, tab_bar, text,
+        text_input, progress_bar, slider, toggler, tooltip,
     },
 };
-use cosmic_time::Timeline;
+use cosmic_time::{id, space, Cubic, Ease, Linear, Timeline};
+use ring::{Badge, BarMarkers, Battery, ConcentricRings, Ring, RingArc, RingStyle};
+use supervisor::{StreamKind, Supervisor};
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::f32::consts::PI;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Set up tracing output and the buffer behind the popup's log page
+pub fn init_logging() {
+    logs::init();
+}
 
 pub fn run() -> cosmic::iced::Result {
     localize::localize();
     cosmic::applet::run::<ClaudeApplet>(())
 }
 
-/// Colors for usage levels
-const COLOR_LOW: Color = Color::from_rgb(0.29, 0.87, 0.50);      // #4ade80 green
-const COLOR_MEDIUM: Color = Color::from_rgb(0.98, 0.80, 0.08);   // #facc15 yellow
-const COLOR_HIGH: Color = Color::from_rgb(0.97, 0.44, 0.44);     // #f87171 red
-const COLOR_INACTIVE: Color = Color::from_rgb(0.5, 0.5, 0.5);    // gray
+/// Brand colors that stay fixed regardless of theme
 const COLOR_CLAUDE: Color = Color::from_rgb(0.85, 0.47, 0.34);   // #da7756 Claude orange
+const COLOR_LOCKED: Color = Color::from_rgb(0.75, 0.15, 0.83);   // #c026d3 magenta
 
-/// Usage level derived from percentage and thresholds
+/// Semantic colors pulled from the active COSMIC theme, with user overrides applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ThemeColors {
+    low: Color,
+    medium: Color,
+    high: Color,
+    inactive: Color,
+    track: Color,
+    label: Color,
+    mascot: Color,
+}
+
+impl ThemeColors {
+    fn current(settings: &ClaudeAppletConfig) -> Self {
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        let custom = |value: &Option<String>| value.as_deref().and_then(config::parse_hex_color);
+        Self {
+            low: custom(&settings.custom_low_color).unwrap_or_else(|| cosmic.success_color().into()),
+            medium: custom(&settings.custom_medium_color).unwrap_or_else(|| cosmic.warning_color().into()),
+            high: custom(&settings.custom_high_color).unwrap_or_else(|| cosmic.destructive_color().into()),
+            inactive: cosmic.palette.neutral_6.into(),
+            track: cosmic.palette.neutral_4.into(),
+            label: cosmic.on_bg_color().into(),
+            mascot: custom(&settings.custom_mascot_color).unwrap_or(COLOR_CLAUDE),
+        }
+    }
+}
+
+/// Duration of the ring fill/color transition after new usage data arrives
+const RING_ANIMATION_DURATION: Duration = Duration::from_millis(600);
+
+/// In-flight transition of a ring from its previously displayed state to a new target.
+/// Its progress is the ring's track on the timeline.
+#[derive(Debug, Clone, Copy)]
+struct RingAnimation {
+    from_percent: f32,
+    from_color: Color,
+}
+
+impl RingAnimation {
+    /// Interpolated percentage and color at `progress` (0..=1) between the start state and the target
+    fn sample(&self, progress: f32, to_percent: f32, to_color: Color) -> (f32, Color) {
+        (
+            self.from_percent + (to_percent - self.from_percent) * progress,
+            lerp_color(self.from_color, to_color, progress),
+        )
+    }
+}
+
+/// Timeline chain moving a track's value from 0 to 1 over `duration`
+fn progress_chain(id: &id::Space, duration: Duration, ease: impl Into<Ease>) -> cosmic_time::Chain {
+    id.clone()
+        .into_chain()
+        .link(space(Duration::ZERO).width(Length::Fixed(0.0)))
+        .link(space(duration).width(Length::Fixed(1.0)).ease(ease))
+        .into()
+}
+
+/// Timeline chain cycling a track's value from 0 to 1 once every `period`, forever
+fn cycle_chain(id: &id::Space, period: Duration) -> cosmic_time::Chain {
+    id.clone()
+        .into_chain()
+        .link(space(Duration::ZERO).width(Length::Fixed(0.0)))
+        .link(space(period).width(Length::Fixed(1.0)).ease(Linear::InOut))
+        .loop_forever()
+        .into()
+}
+
+/// Account usage page opened from the popup's usage rows
+const USAGE_PAGE_URL: &str = "https://claude.ai/settings/usage";
+
+/// Touchpad scroll distance that counts as one wheel notch
+const SCROLL_PIXELS_PER_STEP: f32 = 40.0;
+
+/// Length of the weekly usage window
+const WEEKLY_WINDOW: chrono::Duration = chrono::Duration::days(7);
+
+/// Poll interval used near the session limit or a window reset when adaptive polling is on
+const ADAPTIVE_POLL_INTERVAL_MINUTES: u32 = 5;
+
+/// How close a window reset must be for adaptive polling to speed up
+const ADAPTIVE_RESET_WINDOW_MINUTES: i64 = 30;
+
+/// Delay after a window's reset time before polling for the fresh window
+const RESET_POLL_DELAY: chrono::Duration = chrono::Duration::seconds(15);
+
+/// How long to watch for credentials after starting `claude /login`
+const LOGIN_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// How far usage must fall below a threshold before that threshold can alert again
+const ALERT_HYSTERESIS_PERCENT: f32 = 5.0;
+
+/// Budget percentages that trigger a notification
+const BUDGET_ALERT_STEPS: [u8; 2] = [80, 100];
+
+/// Period of the critical-usage pulse
+const PULSE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Breathing cycle of the resting mascot, drawn in `BREATHE_FRAMES` steps
+const BREATHE_PERIOD: Duration = Duration::from_secs(3);
+const BREATHE_FRAMES: u8 = 6;
+
+/// Wiggle cycle of the mascot while sessions are running, drawn in `WIGGLE_FRAMES` steps
+const WIGGLE_PERIOD: Duration = Duration::from_millis(800);
+const WIGGLE_FRAMES: u8 = 4;
+
+/// Largest custom mascot file read, in bytes; panel icons are a few kilobytes
+const CUSTOM_MASCOT_LIMIT: u64 = 1024 * 1024;
+
+/// Mascot drawing for the current state; animated poses carry their frame index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MascotPose {
+    Rest,
+    Breathe(u8),
+    Wiggle(u8),
+    Droop,
+}
+
+/// Points by which API session usage may exceed the local estimate before the popup suggests another device
+const DISCREPANCY_NOTE_POINTS: f32 = 15.0;
+
+/// How long the pointer must rest on the panel icon before the hover preview opens
+const HOVER_PREVIEW_DELAY: Duration = Duration::from_millis(500);
+
+/// Which configurable color a settings row edits
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSlot {
+    Low,
+    Medium,
+    High,
+    Mascot,
+}
+
+/// Linearly interpolate between two colors
+fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    Color::from_rgb(
+        from.r + (to.r - from.r) * t,
+        from.g + (to.g - from.g) * t,
+        from.b + (to.b - from.b) * t,
+    )
+}
+
+/// Convert a color to an SVG hex string
+fn color_to_hex(color: Color) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        (color.r * 255.0) as u8,
+        (color.g * 255.0) as u8,
+        (color.b * 255.0) as u8
+    )
+}
+
+/// Usage level derived from percentage and thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum UsageLevel {
     Low,
     Medium,
     High,
 }
 
-/// Cached SVG data to avoid regenerating on every render
+/// Most SVG handles kept per kind; pulsing and color transitions only cycle through a few dozen colors
+const SVG_CACHE_LIMIT: usize = 64;
+
+/// SVG handles keyed by 8-bit RGBA color (and mascot pose), so rendering neither rebuilds markup nor copies it.
+/// Handles are reference counted and reusing one also lets the renderer reuse its rasterization.
 #[derive(Default)]
 struct SvgCacheInner {
-    session_percent: f32,
-    session_color: Option<Color>,
-    session_svg: Option<String>,
-    weekly_percent: f32,
-    weekly_color: Option<Color>,
-    weekly_svg: Option<String>,
-    mascot_color: Option<Color>,
-    mascot_svg: Option<String>,
+    /// Theme colors and color mode the cached SVGs were generated with
+    theme_colors: Option<(ThemeColors, ColorMode)>,
+    mascots: HashMap<([u8; 4], MascotPose), svg::Handle>,
+    /// The custom mascot file recolored per color; a file without `currentColor` has a single entry
+    custom_mascots: HashMap<[u8; 4], svg::Handle>,
+    dots: HashMap<[u8; 4], svg::Handle>,
+}
+
+impl SvgCacheInner {
+    /// Handle for `key` from `handles`, generating the markup only on a miss
+    fn handle<K: std::hash::Hash + Eq>(
+        handles: &mut HashMap<K, svg::Handle>,
+        key: K,
+        generate: impl FnOnce() -> String,
+    ) -> svg::Handle {
+        if let Some(handle) = handles.get(&key) {
+            return handle.clone();
+        }
+        if handles.len() >= SVG_CACHE_LIMIT {
+            handles.clear();
+        }
+        let handle = svg::Handle::from_memory(generate().into_bytes());
+        handles.insert(key, handle.clone());
+        handle
+    }
 }
 
 type SvgCache = RefCell<SvgCacheInner>;
 
+/// Mouse button whose panel click action a settings row edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// Threshold event whose hook command a settings row edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookSlot {
+    Warning,
+    Critical,
+    Reset,
+}
+
+/// Rate-limit window shown on the panel, with its own thresholds and accessible labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageWindow {
+    Session,
+    Weekly,
+}
+
+/// Page shown in the popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupPage {
+    Usage,
+    Logs,
+    Diagnostics,
+}
+
+/// Result of checking a file the applet reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathStatus {
+    Ok,
+    Missing,
+    Unreadable,
+}
+
+/// Whether this instance runs the backend pollers, as decided by the D-Bus name request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InstanceRole {
+    /// The name request has not been answered yet; nothing polls until it is
+    Undecided,
+    /// Owns the bus name (or runs without a session bus) and polls
+    Primary,
+    /// Another instance owns the bus name; this one mirrors its usage updates
+    Secondary,
+}
+
+/// Tabs shown at the top of the popup when organization usage is enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PopupTab {
+    Personal,
+    Organization,
+}
+
 pub struct ClaudeApplet {
     core: Core,
     popup: Option<Id>,
+    /// Compact preview shown while hovering the panel icon
+    hover_popup: Option<Id>,
+    /// Bumped when the pointer enters or leaves, so a stale hover delay does not open the preview
+    hover_generation: u64,
     timeline: Timeline,
+    /// Looping animations. Not subscribed to, so they advance only on their own throttled ticks
+    /// instead of redrawing the panel at display rate.
+    ambient: Timeline,
 
     // Configuration
     config: ClaudeAppletConfig,
 
     // UI state
-    settings_expanded: bool,
+    settings_window: Option<Id>,
+    popup_page: PopupPage,
+    /// Restore-defaults button was pressed and is waiting for confirmation
+    confirm_reset: bool,
+    tab_model: segmented_button::SingleSelectModel,
+    /// In-progress text of the custom color inputs (low, medium, high, mascot)
+    color_inputs: [String; 4],
+    /// Contents of the custom mascot file, when one is set and readable
+    custom_mascot_svg: Option<String>,
+    /// In-progress text of the monthly budget input
+    budget_input: String,
+
+    /// Scroll distance not yet turned into a display mode step (touchpads scroll in small increments)
+    scroll_accumulator: f32,
+    /// Bumped to restart the usage subscriptions for an immediate poll
+    refresh_generation: u64,
+    /// Whether this instance polls or mirrors another instance that owns the D-Bus name
+    instance_role: InstanceRole,
+    /// Poll interval handed to the running usage API subscription
+    poll_interval: tokio::sync::watch::Sender<u32>,
 
     // Process status
     process_count: usize,
+    /// Project and branch of each CLI session, listed under the status
+    cli_sessions: Vec<process::CliSession>,
+    /// CLI sessions (by PID) already notified about for their current idle stretch
+    idle_notified: HashSet<u32>,
+    /// Start times of running CLI sessions and today's active time
+    session_tracker: process::SessionTracker,
+    /// Running Claude Desktop instances, counted only when enabled in the settings
+    desktop_process_count: usize,
+    /// Duration of the last process scan, for the Diagnostics page
+    process_scan_duration: Option<Duration>,
 
     // Stats from file
     today_messages: u32,
     today_sessions: u32,
+    streak_days: u32,
     cost_usd: f64,
+    today_cost_usd: f64,
+    week_cost_usd: f64,
+    month_cost_usd: f64,
+    daily_stats: Vec<history::DailyStats>,
+    /// Costs priced from session transcripts; preferred over the stats cache when available
+    transcript_costs: transcripts::TranscriptUpdate,
+    /// Bumped after a pricing sync so transcript costs are recomputed
+    pricing_generation: u64,
+    /// Highest budget step (percent) already notified this month
+    budget_alerted_step: u8,
+    /// Local (year, month) that `budget_alerted_step` belongs to
+    budget_alert_month: Option<(i32, u32)>,
+
+    /// When `claude /login` was launched from the popup, while waiting for credentials
+    login_started: Option<Instant>,
 
     // API usage data
     has_credentials: bool,
+    /// A login exists but its token expired, so a fresh login is needed
+    token_expired: bool,
+    /// The configured gateway does not serve usage data
+    usage_unsupported: bool,
     subscription_type: String,
     session_usage_percent: f32,
     session_reset_time: Option<DateTime<Utc>>,
@@ -94,7 +402,61 @@ pub struct ClaudeApplet {
     weekly_reset_time: Option<DateTime<Utc>>,
     opus_usage_percent: f32,
     sonnet_usage_percent: f32,
+    extra_usage: Option<api::ExtraUsage>,
+    other_windows: Vec<api::NamedWindow>,
     api_error: Option<String>,
+    /// Category of `api_error`, picking the message and suggested action in the popup
+    api_error_kind: Option<api::ErrorKind>,
+    /// A poll has returned usage data; failed polls after it carry that data forward
+    has_usage_data: bool,
+    /// When the usage API was last polled, successfully or not
+    last_api_fetch: Option<DateTime<Utc>>,
+    /// Due time of the post-reset poll that was last triggered
+    reset_poll_triggered: Option<DateTime<Utc>>,
+    /// Watches backend streams for stalls and restarts them
+    supervisor: Supervisor,
+    /// The diagnostics report was copied since the page was opened
+    report_copied: bool,
+    /// File checks and token expiry for the diagnostics page, taken when it is opened
+    diagnostic_probes: Vec<(String, Option<std::path::PathBuf>, PathStatus)>,
+    diagnostic_token_expiry: Option<DateTime<Utc>>,
+    /// Last usage API response as shown in the debug view, kept across failed requests
+    raw_api_response: Option<String>,
+    raw_response_expanded: bool,
+
+    // Organization usage from the Admin API (also used for API-key billing)
+    org_usage: admin::OrgUsageUpdate,
+    api_key_configured: bool,
+
+    // Threshold alerts: highest level already notified per window, and snooze deadline
+    alerted_session_level: UsageLevel,
+    alerted_weekly_level: UsageLevel,
+    alerted_opus_level: UsageLevel,
+    alerts_snoozed_until: Option<DateTime<Utc>>,
+    /// Weekly reset time of the window whose early-exhaustion forecast was already notified
+    forecast_notified_for: Option<DateTime<Utc>>,
+    /// Titles of notifications held back during quiet hours, summarized once they end
+    suppressed_alerts: Vec<String>,
+
+    // Anthropic service status
+    service_status: status::StatusUpdate,
+    known_incident_ids: HashSet<String>,
+    /// Whether a status update has been seen; incidents already open at that point are not notified
+    incidents_seeded: bool,
+
+    // Installed vs. latest Claude Code release
+    claude_version: version::VersionUpdate,
+    applet_release: Option<release::ReleaseUpdate>,
+
+    // Ring transitions, driven by their timeline tracks
+    session_animation: Option<RingAnimation>,
+    weekly_animation: Option<RingAnimation>,
+    session_ring_track: id::Space,
+    weekly_ring_track: id::Space,
+    pulse_track: id::Space,
+    breathe_track: id::Space,
+    wiggle_track: id::Space,
+    reduced_motion: bool,
 
     // SVG cache for performance
     svg_cache: SvgCache,
@@ -103,22 +465,139 @@ pub struct ClaudeApplet {
 #[derive(Clone, Debug)]
 pub enum Message {
     TogglePopup,
+    /// Open the popup unless it is already shown (e.g. from a notification action)
+    OpenPopup,
     PopupClosed(Id),
+    /// Move keyboard focus between the popup's interactive rows
+    FocusNext,
+    FocusPrevious,
+    OpenUsagePage,
+    OpenProject(std::path::PathBuf),
+    ShowPage(PopupPage),
+    CopyDiagnostics,
+    ExportData,
+    ExportFinished(Result<Option<std::path::PathBuf>, String>),
+    HistoryCompacted(usize),
+    PanelClick(ClickAction),
+    PanelScroll(mouse::ScrollDelta),
+    /// Pointer entered (`true`) or left the panel icon
+    PanelHover(bool),
+    /// Hover delay elapsed; opens the preview if the generation is still current
+    ShowHoverPreview(u64),
+    SnoozeAlerts,
+    ResumeAlerts,
+    Refresh,
+    /// Whether this instance owns the D-Bus name (see `dbus::DbusEvent::Primary`)
+    InstanceRole(bool),
     Frame(Instant),
+    /// Throttled tick advancing the looping animations
+    AmbientTick(Instant),
+    /// Once-per-second tick used to refresh the lockout countdown
+    LockoutTick(Instant),
+    /// Once-per-minute tick used to refresh relative reset times
+    MinuteTick(Instant),
+    /// Refreshes the log page while it is open
+    LogsTick(Instant),
+    /// Periodic check for backend streams that stopped delivering
+    SuperviseStreams(Instant),
+    /// Checks whether a window just reset and should be polled again
+    ResetPollTick(Instant),
     ProcessUpdate(process::ProcessUpdate),
     StatsUpdate(stats::StatsUpdate),
+    TranscriptUpdate(transcripts::TranscriptUpdate),
+    PricingSynced,
     ApiUpdate(api::UsageUpdate),
+    OrgUsageUpdate(admin::OrgUsageUpdate),
+    StatusUpdate(status::StatusUpdate),
+    VersionUpdate(version::VersionUpdate),
+    ReleaseUpdate(Option<release::ReleaseUpdate>),
+    OpenReleasePage,
+    TabActivated(segmented_button::Entity),
     ConfigChanged(ClaudeAppletConfig),
     OpenTerminal,
+    StartLogin,
+    /// Looks for credentials while a login started from the popup is in progress
+    CheckLogin(Instant),
+    UpdateClaude,
     OpenSettings,
-    ToggleSettings,
+    OpenSettingsWindow,
+    RequestResetSettings,
+    ConfirmResetSettings,
+    CancelResetSettings,
     // Settings messages
     CycleIconDisplay,
+    CycleIndicatorStyle,
+    SetTextTemplate(String),
+    CycleColorMode,
     ToggleMascot(bool),
-    SetWarningThreshold(u8),
-    SetCriticalThreshold(u8),
+    ToggleMascotAnimation(bool),
+    ToggleHideWhenInactive(bool),
+    ToggleProcessBadge(bool),
+    ToggleClaudeDesktop(bool),
+    SetWarningThreshold(UsageWindow, u8),
+    SetCriticalThreshold(UsageWindow, u8),
+    SetOpusWarningThreshold(u8),
+    SetOpusCriticalThreshold(u8),
     TogglePercentageText(bool),
+    CycleTextMetric,
+    ToggleCostText(bool),
+    TogglePulse(bool),
+    ToggleRemaining(bool),
+    ToggleResetCountdown(bool),
+    ToggleHoverPreview(bool),
+    Toggle24HourClock(bool),
+    SetCostPrecision(u8),
+    ToggleGroupDigits(bool),
+    CycleResetTimeFormat,
     SetPollInterval(u32),
+    SetPopupMaxHeight(u32),
+    SetPopupWidth(u32),
+    CyclePopupDensity,
+    TogglePopupSection(PopupSection, bool),
+    MovePopupSectionUp(PopupSection),
+    MovePopupSectionDown(PopupSection),
+    ToggleDailySummary(bool),
+    ToggleAdaptivePolling(bool),
+    ToggleOrganizationUsage(bool),
+    CycleBillingMode,
+    CycleClickAction(ClickButton),
+    ToggleScrollCyclesDisplay(bool),
+    SetCustomColor(ColorSlot, String),
+    ToggleServiceStatus(bool),
+    ToggleIncidentNotifications(bool),
+    ToggleClaudeUpdateCheck(bool),
+    ToggleAppletUpdateCheck(bool),
+    ToggleThresholdNotifications(bool),
+    SetSnoozeHours(u32),
+    SetIdleSessionMinutes(u32),
+    TogglePlanChangeNotifications(bool),
+    ToggleSessionChangeNotifications(bool),
+    ToggleIdleNotifications(bool),
+    ToggleForecastNotifications(bool),
+    ToggleQuietHours(bool),
+    SetQuietHoursStart(u32),
+    SetQuietHoursEnd(u32),
+    QuietHoursTick(Instant),
+    ToggleWeeklySummary(bool),
+    CycleWeeklySummaryDay,
+    SetWeeklySummaryHour(u32),
+    WeeklySummaryTick(Instant),
+    WeeklySummaryReady(history::WeeklySummary),
+    SetHookCommand(HookSlot, String),
+    SetClaudeDir(String),
+    SetCustomMascotPath(String),
+    /// Load the typed mascot path; reading it on every keystroke would hit the disk for each partial path
+    LoadCustomMascot,
+    SetApiBaseUrl(String),
+    ToggleTrustCustomApiHost(bool),
+    SetFileManagerCommand(String),
+    SetMonthlyBudget(String),
+    ToggleModelPricingSync(bool),
+    ToggleRawResponse(bool),
+    ToggleRawResponseExpanded,
+    CycleLogLevel,
+    ToggleLogFile(bool),
+    CycleHistoryRetention,
 }
 
 impl cosmic::Application for ClaudeApplet {
@@ -136,24 +615,75 @@ impl cosmic::Application for ClaudeApplet {
     }
 
     fn init(core: Core, _flags: Self::Flags) -> (Self, app::Task<Self::Message>) {
+        // Carry settings over from an older config version before loading
+        config::migrate_legacy_config();
+
         // Load config from cosmic-config or use defaults
         let mut config = cosmic::cosmic_config::Config::new(Self::APP_ID, ClaudeAppletConfig::VERSION)
             .ok()
             .and_then(|c| ClaudeAppletConfig::get_entry(&c).ok())
             .unwrap_or_default();
         config.validate();
+        logs::configure(config.log_level, config.log_to_file);
+        backend::set_claude_dir(config.claude_dir.as_deref());
+        api::set_base_url(config.api_base_url.as_deref(), config.trust_custom_api_host);
+        localize::set_number_format(config.cost_precision, config.group_digits);
+
+        let color_inputs = Self::custom_colors(&config).map(Option::unwrap_or_default);
 
-        let applet = Self {
+        let budget_input = Self::budget_text(config.monthly_budget_usd);
+        let poll_interval = tokio::sync::watch::Sender::new(config.poll_interval_minutes);
+
+        let mut applet = Self {
             core,
             popup: None,
+            hover_popup: None,
+            hover_generation: 0,
             timeline: Timeline::default(),
+            ambient: Timeline::default(),
             config,
-            settings_expanded: false,
+            settings_window: None,
+            popup_page: PopupPage::Usage,
+            confirm_reset: false,
+            refresh_generation: 0,
+            poll_interval,
+            scroll_accumulator: 0.0,
+            alerted_session_level: UsageLevel::Low,
+            alerted_weekly_level: UsageLevel::Low,
+            alerted_opus_level: UsageLevel::Low,
+            alerts_snoozed_until: None,
+            forecast_notified_for: None,
+            suppressed_alerts: Vec::new(),
+            color_inputs,
+            custom_mascot_svg: None,
+            budget_input,
+            tab_model: segmented_button::ModelBuilder::default()
+                .insert(|b| b.text(fl!("tab-personal")).data(PopupTab::Personal).activate())
+                .insert(|b| b.text(fl!("tab-organization")).data(PopupTab::Organization))
+                .build(),
+            instance_role: InstanceRole::Undecided,
             process_count: 0,
+            cli_sessions: Vec::new(),
+            idle_notified: HashSet::new(),
+            session_tracker: process::SessionTracker::default(),
+            desktop_process_count: 0,
+            process_scan_duration: None,
             today_messages: 0,
             today_sessions: 0,
+            streak_days: 0,
             cost_usd: 0.0,
+            today_cost_usd: 0.0,
+            week_cost_usd: 0.0,
+            month_cost_usd: 0.0,
+            daily_stats: Vec::new(),
+            transcript_costs: transcripts::TranscriptUpdate::default(),
+            pricing_generation: 0,
+            budget_alerted_step: 0,
+            budget_alert_month: None,
+            login_started: None,
             has_credentials: false,
+            token_expired: false,
+            usage_unsupported: false,
             subscription_type: String::from("Unknown"),
             session_usage_percent: 0.0,
             session_reset_time: None,
@@ -161,9 +691,43 @@ impl cosmic::Application for ClaudeApplet {
             weekly_reset_time: None,
             opus_usage_percent: 0.0,
             sonnet_usage_percent: 0.0,
+            extra_usage: None,
+            other_windows: Vec::new(),
             api_error: None,
+            api_error_kind: None,
+            has_usage_data: false,
+            last_api_fetch: None,
+            reset_poll_triggered: None,
+            supervisor: Supervisor::default(),
+            report_copied: false,
+            diagnostic_probes: Vec::new(),
+            diagnostic_token_expiry: None,
+            raw_api_response: None,
+            raw_response_expanded: false,
+            org_usage: admin::OrgUsageUpdate::default(),
+            api_key_configured: api::api_key_configured(),
+            service_status: status::StatusUpdate::default(),
+            known_incident_ids: HashSet::new(),
+            incidents_seeded: false,
+            claude_version: version::VersionUpdate::default(),
+            applet_release: None,
+            session_animation: None,
+            weekly_animation: None,
+            session_ring_track: id::Space::unique(),
+            weekly_ring_track: id::Space::unique(),
+            pulse_track: id::Space::unique(),
+            breathe_track: id::Space::unique(),
+            wiggle_track: id::Space::unique(),
+            reduced_motion: config::prefers_reduced_motion(),
             svg_cache: SvgCache::default(),
         };
+        applet
+            .ambient
+            .set_chain(cycle_chain(&applet.pulse_track, PULSE_PERIOD))
+            .set_chain(cycle_chain(&applet.breathe_track, BREATHE_PERIOD))
+            .set_chain(cycle_chain(&applet.wiggle_track, WIGGLE_PERIOD));
+        applet.ambient.start();
+        applet.reload_custom_mascot();
         (applet, Task::none())
     }
 
@@ -184,52 +748,221 @@ impl cosmic::Application for ClaudeApplet {
             Message::ConfigChanged(u.config)
         });
 
-        Subscription::batch([
+        let mut subscriptions = vec![
             timeline,
             config_watcher,
-            process::process_subscription().map(Message::ProcessUpdate),
-            stats::stats_subscription().map(Message::StatsUpdate),
-            api::api_subscription(self.config.poll_interval_minutes).map(Message::ApiUpdate),
-        ])
+            time::every(Duration::from_secs(60)).map(Message::SuperviseStreams),
+            backend::data_source()
+                .stats(self.supervisor.generation(StreamKind::Stats))
+                .map(Message::StatsUpdate),
+            signals::signal_subscription().map(|request| match request {
+                signals::SignalRequest::Refresh => Message::Refresh,
+                signals::SignalRequest::TogglePopup => Message::TogglePopup,
+            }),
+            dbus::dbus_subscription().map(|event| match event {
+                dbus::DbusEvent::Primary(primary) => Message::InstanceRole(primary),
+                dbus::DbusEvent::Refresh => Message::Refresh,
+                dbus::DbusEvent::TogglePopup => Message::TogglePopup,
+            }),
+        ];
+
+        // Local scans run in every instance. A duplicate takes usage from the primary instead of
+        // polling the API itself and runs none of the other network pollers; until the role is
+        // known, neither happens.
+        subscriptions.extend(self.local_subscriptions());
+        match self.instance_role {
+            InstanceRole::Primary => subscriptions.extend(self.poller_subscriptions()),
+            InstanceRole::Secondary => subscriptions.push(dbus::mirror_subscription().map(Message::ApiUpdate)),
+            InstanceRole::Undecided => {}
+        }
+
+        if self.config.weekly_summary && self.instance_role == InstanceRole::Primary {
+            subscriptions.push(time::every(Duration::from_secs(60)).map(Message::WeeklySummaryTick));
+        }
+
+        // Watch for the end of quiet hours while notifications are held back
+        if !self.suppressed_alerts.is_empty() {
+            subscriptions.push(time::every(Duration::from_secs(60)).map(Message::QuietHoursTick));
+        }
+
+        // A slow tick is plenty for the pulse; it keeps the panel from redrawing at full frame rate
+        if self.is_pulsing() {
+            subscriptions.push(time::every(Duration::from_millis(100)).map(Message::AmbientTick));
+        }
+
+        // The mascot only changes once per frame of its pose, so tick at that rate
+        if let Some(interval) = self.mascot_frame_interval() {
+            subscriptions.push(time::every(interval).map(Message::AmbientTick));
+        }
+
+        // Arrow keys walk the focusable rows; Enter activates the focused one
+        if self.popup.is_some() {
+            subscriptions.push(keyboard::on_key_press(|key, _modifiers| match key {
+                keyboard::Key::Named(keyboard::key::Named::ArrowDown) => Some(Message::FocusNext),
+                keyboard::Key::Named(keyboard::key::Named::ArrowUp) => Some(Message::FocusPrevious),
+                _ => None,
+            }));
+        }
+
+        // Keep relative reset times fresh while they are visible in the popup or panel
+        if self.popup.is_some() || (self.config.show_reset_countdown && self.has_credentials) {
+            subscriptions.push(time::every(Duration::from_secs(60)).map(Message::MinuteTick));
+        }
+
+        if self.popup.is_some() && self.popup_page == PopupPage::Logs {
+            subscriptions.push(time::every(Duration::from_secs(2)).map(Message::LogsTick));
+        }
+
+        // Check often around a window reset so the fresh window shows up within a minute
+        if self
+            .pending_reset_poll()
+            .is_some_and(|due| due - Utc::now() <= chrono::Duration::minutes(2))
+        {
+            subscriptions.push(time::every(Duration::from_secs(10)).map(Message::ResetPollTick));
+        }
+
+        if self.login_started.is_some() {
+            subscriptions.push(time::every(Duration::from_secs(2)).map(Message::CheckLogin));
+        }
+
+        // Only tick while locked out so the countdown stays live without idle wakeups
+        if self.is_locked_out() {
+            subscriptions.push(time::every(Duration::from_secs(1)).map(Message::LockoutTick));
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn update(&mut self, message: Self::Message) -> app::Task<Self::Message> {
         match message {
             Message::TogglePopup => {
+                // The full popup replaces the hover preview
+                self.hover_generation = self.hover_generation.wrapping_add(1);
+                if let Some(id) = self.hover_popup.take() {
+                    return Task::batch([destroy_popup(id), self.update(Message::TogglePopup)]);
+                }
                 return if let Some(p) = self.popup.take() {
                     destroy_popup(p)
                 } else {
                     let Some(main_id) = self.core.main_window_id() else {
                         return Task::none();
                     };
-                    self.timeline = Timeline::default();
+                    self.popup_page = PopupPage::Usage;
                     let new_id = Id::unique();
                     self.popup = Some(new_id);
-                    let popup_settings = self.core.applet.get_popup_settings(
+                    let mut popup_settings = self.core.applet.get_popup_settings(
                         main_id,
                         new_id,
                         Some((1, 1)),  // Required for complex popups to prevent Wayland crash
                         None,
                         None,
                     );
+                    popup_settings.positioner.size_limits = popup_settings
+                        .positioner
+                        .size_limits
+                        .min_width(self.config.popup_width as f32)
+                        .max_width(self.config.popup_width as f32)
+                        .max_height(self.config.popup_max_height as f32);
                     get_popup(popup_settings)
                 };
             }
+            Message::OpenPopup => {
+                if self.popup.is_none() {
+                    return self.update(Message::TogglePopup);
+                }
+            }
             Message::PopupClosed(id) => {
                 if self.popup.as_ref() == Some(&id) {
                     self.popup = None;
                 }
+                if self.hover_popup == Some(id) {
+                    self.hover_popup = None;
+                }
+                if self.settings_window == Some(id) {
+                    self.settings_window = None;
+                    self.confirm_reset = false;
+                    return window::close(id);
+                }
+            }
+            Message::Frame(now) => {
+                self.timeline.now(now);
+                // A finished transition leaves its ring at the target
+                if self.session_animation.is_some() && self.track_progress(&self.session_ring_track) >= 1.0 {
+                    self.session_animation = None;
+                    self.timeline.clear_chain(self.session_ring_track.clone());
+                }
+                if self.weekly_animation.is_some() && self.track_progress(&self.weekly_ring_track) >= 1.0 {
+                    self.weekly_animation = None;
+                    self.timeline.clear_chain(self.weekly_ring_track.clone());
+                }
+            }
+            Message::AmbientTick(now) => {
+                self.ambient.now(now);
+            }
+            Message::LockoutTick(_) | Message::MinuteTick(_) | Message::LogsTick(_) => {
+                // Nothing to update; receiving the message triggers a redraw of the countdown
+            }
+            Message::SuperviseStreams(_) => {
+                // Reset proximity changes with time, not only with new data
+                self.publish_poll_interval();
+                let active: Vec<StreamKind> = self.active_streams().into_iter().map(|(kind, _)| kind).collect();
+                for kind in self.supervisor.check(&active, self.config.poll_interval_minutes) {
+                    tracing::warn!("{:?} data source stalled, restarting it", kind);
+                }
+            }
+            Message::ResetPollTick(_) => {
+                if let Some(due) = self.pending_reset_poll().filter(|due| *due <= Utc::now()) {
+                    debug!("Usage window reset, polling again");
+                    self.reset_poll_triggered = Some(due);
+                    self.refresh_generation = self.refresh_generation.wrapping_add(1);
+                }
             }
-            Message::Frame(now) => self.timeline.now(now),
             Message::ProcessUpdate(update) => {
+                self.supervisor.record(StreamKind::Processes);
                 self.process_count = update.count;
+                let changes = self.session_tracker.observe(&update);
+                self.cli_sessions = update.sessions;
+                self.check_idle_sessions();
+                if self.config.notify_session_changes {
+                    for project in changes.started {
+                        self.notify(fl!("session-started-title"), project);
+                    }
+                    for ended in changes.ended {
+                        self.notify(
+                            fl!("session-ended-title", duration = Self::format_span(ended.duration)),
+                            ended.project,
+                        );
+                    }
+                }
+                self.desktop_process_count = update.desktop_count;
+                self.process_scan_duration = Some(update.scan_duration);
             }
             Message::StatsUpdate(update) => {
+                self.supervisor.record(StreamKind::Stats);
                 self.today_messages = update.today_messages;
                 self.today_sessions = update.today_sessions;
+                self.streak_days = update.streak_days;
                 self.cost_usd = update.total_cost_usd;
+                self.today_cost_usd = update.today_cost_usd;
+                self.week_cost_usd = update.week_cost_usd;
+                self.month_cost_usd = update.month_cost_usd;
+                self.daily_stats = update.daily;
+                self.check_budget_alert();
+            }
+            Message::TranscriptUpdate(update) => {
+                self.supervisor.record(StreamKind::Transcripts);
+                self.transcript_costs = update;
+                self.check_budget_alert();
+            }
+            Message::PricingSynced => {
+                self.pricing_generation = self.pricing_generation.wrapping_add(1);
             }
             Message::ApiUpdate(update) => {
+                if update.error_kind.is_none() && update.has_credentials && !update.usage_unsupported {
+                    self.has_usage_data = true;
+                } else if !update.has_credentials {
+                    self.has_usage_data = false;
+                }
                 debug!(
                     "ApiUpdate received: session={:.1}%, weekly={:.1}%, opus={:.1}%, sonnet={:.1}%",
                     update.session_usage_percent,
@@ -237,7 +970,51 @@ impl cosmic::Application for ClaudeApplet {
                     update.opus_usage_percent,
                     update.sonnet_usage_percent
                 );
+                self.supervisor.record(StreamKind::UsageApi);
+                if self.instance_role == InstanceRole::Primary {
+                    dbus::publish_usage(&update);
+                }
+                if update.has_credentials {
+                    self.last_api_fetch = Some(Utc::now());
+                }
+                // Start each transition from whatever is on screen right now
+                let (session_from, session_color) = self.displayed_session();
+                let (weekly_from, weekly_color) = self.displayed_weekly();
+                if (session_from - update.session_usage_percent).abs() >= 0.1 {
+                    self.session_animation = Some(RingAnimation {
+                        from_percent: session_from,
+                        from_color: session_color,
+                    });
+                    self.timeline
+                        .set_chain(progress_chain(&self.session_ring_track, RING_ANIMATION_DURATION, Cubic::InOut));
+                }
+                if (weekly_from - update.weekly_usage_percent).abs() >= 0.1 {
+                    self.weekly_animation = Some(RingAnimation {
+                        from_percent: weekly_from,
+                        from_color: weekly_color,
+                    });
+                    self.timeline
+                        .set_chain(progress_chain(&self.weekly_ring_track, RING_ANIMATION_DURATION, Cubic::InOut));
+                }
+                self.timeline.start();
+
+                // A window has reset when its previous reset time has passed and a new one was reported.
+                // Failed or credential-less updates carry no trustworthy reset time, so they never count.
+                let reset_now = Utc::now();
+                let clean_update = update.has_credentials && update.last_error.is_none() && update.error_kind.is_none();
+                let session_reset = clean_update
+                    && self.session_reset_time.is_some_and(|t| t <= reset_now)
+                    && update.session_reset_time != self.session_reset_time;
+                let weekly_reset = clean_update
+                    && self.weekly_reset_time.is_some_and(|t| t <= reset_now)
+                    && update.weekly_reset_time != self.weekly_reset_time;
+
                 self.has_credentials = update.has_credentials;
+                self.token_expired = update.token_expired;
+                self.usage_unsupported = update.usage_unsupported;
+                if self.has_credentials {
+                    self.login_started = None;
+                }
                 self.subscription_type = update.subscription_type;
                 self.session_usage_percent = update.session_usage_percent;
                 self.session_reset_time = update.session_reset_time;
@@ -245,477 +1022,3843 @@ impl cosmic::Application for ClaudeApplet {
                 self.weekly_reset_time = update.weekly_reset_time;
                 self.opus_usage_percent = update.opus_usage_percent;
                 self.sonnet_usage_percent = update.sonnet_usage_percent;
+                self.extra_usage = update.extra_usage;
+                self.other_windows = update.other_windows;
                 self.api_error = update.last_error;
+                self.api_error_kind = update.error_kind;
+                if update.raw_response.is_some() {
+                    self.raw_api_response = update.raw_response;
+                }
+
+                // History, hooks and alerts are left to the primary instance so they happen once,
+                // and mock data never reaches them
+                if self.instance_role != InstanceRole::Primary || backend::data_source().is_mock() {
+                    return Task::none();
+                }
+
+                // Limits shift with the plan, so point out upgrades and downgrades,
+                // including ones made while the applet was not running
+                if let Some(tier) = Self::plan_tier(&self.subscription_type).filter(|_| self.has_credentials) {
+                    if self.config.last_plan_tier.as_deref() != Some(tier) {
+                        if let Some(from) = self.config.last_plan_tier.replace(tier.to_string()) {
+                            if self.config.notify_plan_changes {
+                                self.notify(fl!("plan-changed-title"), fl!("plan-changed-body", from = from, to = tier));
+                            }
+                        }
+                        self.save_config();
+                    }
+                }
+
+                if self.has_credentials && self.api_error.is_none() {
+                    let sample = history::UsageSample {
+                        timestamp: Utc::now(),
+                        session_percent: self.session_usage_percent,
+                        weekly_percent: self.weekly_usage_percent,
+                    };
+                    tokio::task::spawn_blocking(move || history::append(sample));
+                }
+
+                // A fresh window re-arms its threshold alerts regardless of hysteresis
+                if session_reset {
+                    self.alerted_session_level = UsageLevel::Low;
+                }
+                if weekly_reset {
+                    self.alerted_weekly_level = UsageLevel::Low;
+                }
+
+                for (reset, window) in [(session_reset, "session"), (weekly_reset, "weekly")] {
+                    if reset {
+                        hooks::run(self.config.hook_on_reset.as_deref(), HookEvent::Reset, self.hook_context(window));
+                    }
+                }
+                self.publish_poll_interval();
+                self.check_forecast_alert();
+                return self.check_threshold_alerts();
             }
-            Message::OpenTerminal => {
-                let mut cmd = std::process::Command::new("cosmic-term");
-                cmd.arg("-e").arg("claude");
+            Message::OrgUsageUpdate(update) => {
+                self.supervisor.record(StreamKind::OrgUsage);
+                self.org_usage = update;
+                self.check_budget_alert();
+            }
+            Message::StatusUpdate(update) => {
+                self.supervisor.record(StreamKind::ServiceStatus);
+                // Keep the previous incidents if the status page could not be reached
+                if update.last_error.is_some() {
+                    return Task::none();
+                }
+
+                for incident in &update.incidents {
+                    let is_new = self.known_incident_ids.insert(incident.id.clone());
+                    if is_new && self.incidents_seeded && self.config.notify_service_incidents {
+                        self.notify(fl!("incident-notification-title"), incident.name.clone());
+                    }
+                }
+                self.known_incident_ids
+                    .retain(|id| update.incidents.iter().any(|i| &i.id == id));
+                self.incidents_seeded = true;
+                self.service_status = update;
+            }
+            Message::TabActivated(entity) => {
+                self.tab_model.activate(entity);
+            }
+            Message::VersionUpdate(update) => {
+                self.claude_version = update;
+            }
+            Message::ReleaseUpdate(update) => {
+                self.applet_release = update;
+            }
+            Message::FocusNext => {
+                return cosmic::iced::widget::focus_next();
+            }
+            Message::FocusPrevious => {
+                return cosmic::iced::widget::focus_previous();
+            }
+            Message::OpenUsagePage => {
+                let cmd = flatpak::open_command(USAGE_PAGE_URL);
                 tokio::spawn(async {
                     if cosmic::process::spawn(cmd).await.is_none() {
-                        tracing::error!("Failed to open terminal: cosmic-term process could not be spawned");
+                        tracing::error!("Failed to open usage page: xdg-open process could not be spawned");
                     }
                 });
             }
-            Message::OpenSettings => {
-                if let Some(home) = dirs::home_dir() {
-                    let claude_dir = home.join(".claude");
-                    let mut cmd = std::process::Command::new("cosmic-files");
-                    cmd.arg(claude_dir);
+            Message::OpenReleasePage => {
+                if let Some(release) = &self.applet_release {
+                    let cmd = flatpak::open_command(&release.url);
                     tokio::spawn(async {
                         if cosmic::process::spawn(cmd).await.is_none() {
-                            tracing::error!("Failed to open file manager: cosmic-files process could not be spawned");
+                            tracing::error!("Failed to open release page: xdg-open process could not be spawned");
                         }
                     });
                 }
             }
-            Message::ToggleSettings => {
-                self.settings_expanded = !self.settings_expanded;
+            Message::PanelClick(action) => {
+                let message = match action {
+                    ClickAction::None => return Task::none(),
+                    ClickAction::TogglePopup => Message::TogglePopup,
+                    ClickAction::OpenTerminal => Message::OpenTerminal,
+                    ClickAction::Refresh => Message::Refresh,
+                    ClickAction::CycleIconDisplay => Message::CycleIconDisplay,
+                };
+                return self.update(message);
             }
-            Message::ConfigChanged(mut config) => {
-                config.validate();
-                self.config = config;
+            Message::PanelHover(entered) => {
+                self.hover_generation = self.hover_generation.wrapping_add(1);
+                if !entered {
+                    if let Some(id) = self.hover_popup.take() {
+                        return destroy_popup(id);
+                    }
+                } else if self.config.hover_preview && self.popup.is_none() && self.hover_popup.is_none() {
+                    let generation = self.hover_generation;
+                    return cosmic::task::future(async move {
+                        tokio::time::sleep(HOVER_PREVIEW_DELAY).await;
+                        Message::ShowHoverPreview(generation)
+                    });
+                }
             }
-            Message::CycleIconDisplay => {
-                self.config.icon_display = match self.config.icon_display {
-                    IconDisplay::Session => IconDisplay::Weekly,
-                    IconDisplay::Weekly => IconDisplay::Both,
-                    IconDisplay::Both => IconDisplay::Session,
+            Message::ShowHoverPreview(generation) => {
+                if generation != self.hover_generation || self.popup.is_some() || self.hover_popup.is_some() {
+                    return Task::none();
+                }
+                let Some(main_id) = self.core.main_window_id() else {
+                    return Task::none();
+                };
+                let new_id = Id::unique();
+                self.hover_popup = Some(new_id);
+                let popup_settings = self.core.applet.get_popup_settings(main_id, new_id, None, None, None);
+                return get_popup(popup_settings);
+            }
+            Message::PanelScroll(delta) => {
+                if !self.config.scroll_cycles_display {
+                    return Task::none();
+                }
+                self.scroll_accumulator += match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y,
+                    mouse::ScrollDelta::Pixels { y, .. } => y / SCROLL_PIXELS_PER_STEP,
+                };
+                if self.scroll_accumulator.abs() < 1.0 {
+                    return Task::none();
+                }
+                // Scrolling up moves backwards through the modes, down moves forwards
+                let forward = self.scroll_accumulator < 0.0;
+                self.scroll_accumulator = 0.0;
+                self.config.icon_display = match (&self.config.icon_display, forward) {
+                    (IconDisplay::Session, true) | (IconDisplay::Both, false) => IconDisplay::Weekly,
+                    (IconDisplay::Weekly, true) | (IconDisplay::Concentric, false) => IconDisplay::Both,
+                    (IconDisplay::Both, true) | (IconDisplay::Session, false) => IconDisplay::Concentric,
+                    (IconDisplay::Concentric, true) | (IconDisplay::Weekly, false) => IconDisplay::Session,
                 };
                 self.save_config();
             }
-            Message::ToggleMascot(enabled) => {
-                self.config.show_mascot = enabled;
+            Message::SnoozeAlerts => {
+                let mut until = Utc::now() + chrono::Duration::hours(self.config.snooze_hours as i64);
+                // A session reset starts a fresh window, so alerts resume with it
+                if let Some(reset) = self.session_reset_time {
+                    until = until.min(reset);
+                }
+                self.alerts_snoozed_until = Some(until);
+            }
+            Message::ResumeAlerts => {
+                self.alerts_snoozed_until = None;
+            }
+            Message::ToggleThresholdNotifications(enabled) => {
+                self.config.notify_thresholds = enabled;
                 self.save_config();
             }
-            Message::SetWarningThreshold(value) => {
-                self.config.warning_threshold = value;
+            Message::SetHookCommand(slot, command) => {
+                let field = match slot {
+                    HookSlot::Warning => &mut self.config.hook_on_warning,
+                    HookSlot::Critical => &mut self.config.hook_on_critical,
+                    HookSlot::Reset => &mut self.config.hook_on_reset,
+                };
+                *field = (!command.is_empty()).then_some(command);
                 self.save_config();
             }
-            Message::SetCriticalThreshold(value) => {
-                self.config.critical_threshold = value;
+            Message::SetCustomMascotPath(path) => {
+                self.config.custom_mascot_path = (!path.is_empty()).then_some(path);
                 self.save_config();
             }
-            Message::TogglePercentageText(enabled) => {
-                self.config.show_percentage_text = enabled;
+            Message::LoadCustomMascot => {
+                self.reload_custom_mascot();
+            }
+            Message::SetClaudeDir(dir) => {
+                self.config.claude_dir = (!dir.is_empty()).then_some(dir);
+                backend::set_claude_dir(self.config.claude_dir.as_deref());
                 self.save_config();
             }
-            Message::SetPollInterval(minutes) => {
-                self.config.poll_interval_minutes = minutes;
+            Message::SetApiBaseUrl(url) => {
+                self.config.api_base_url = (!url.is_empty()).then_some(url);
+                api::set_base_url(self.config.api_base_url.as_deref(), self.config.trust_custom_api_host);
                 self.save_config();
             }
-        }
-        Task::none()
-    }
-
-    fn view(&self) -> Element<'_, Self::Message> {
-        // Create custom colored indicator
-        let indicator = self.create_usage_indicator();
-
-        // Wrap in a button for click handling
-        let indicator_button = button::custom(indicator)
-            .padding(4)
-            .class(cosmic::theme::Button::AppletIcon)
-            .on_press(Message::TogglePopup);
-
-        let content: Element<'_, Self::Message> = if self.config.show_percentage_text && self.has_credentials {
-            let percent_text = match self.config.icon_display {
-                IconDisplay::Session => format!("{:.0}%", self.session_usage_percent),
-                IconDisplay::Weekly => format!("{:.0}%", self.weekly_usage_percent),
-                IconDisplay::Both => format!("{:.0}%", self.session_usage_percent),
-            };
-            row![
-                indicator_button,
-                text::body(percent_text),
-            ]
-            .align_y(Alignment::Center)
-            .spacing(4)
-            .into()
-        } else {
-            indicator_button.into()
-        };
-
-        // Use autosize_window to properly size the panel button
-        self.core.applet.autosize_window(content).into()
-    }
-
-    fn view_window(&self, _id: Id) -> Element<'_, Self::Message> {
-        let Spacing {
-            space_xxs,
-            space_s,
-            ..
-        } = theme::active().cosmic().spacing;
-
-        // Header with subscription type
-        let header = row![
-            text::heading(fl!("claude-code")),
-            horizontal_space(),
-        ]
-        .align_y(Alignment::Center)
-        .padding([0, space_s]);
-
-        let plan_text = if self.has_credentials {
-            format!("{} {}", self.subscription_type, fl!("plan"))
-        } else {
-            fl!("not-logged-in")
-        };
-
-        let plan_section = padded_control(
-            text::body(plan_text)
-        );
-
-        // 5-Hour Session Usage
-        let session_section = padded_control(
-            column![
-                text::body(fl!("session-usage")),
-                progress_bar(0.0..=100.0, self.session_usage_percent)
-                    .width(Length::Fill),
-                row![
-                    text::caption(format!("{:.0}%", self.session_usage_percent)),
-                    horizontal_space(),
-                    text::caption(self.format_reset_time(self.session_reset_time)),
-                ],
-            ]
-            .spacing(space_xxs)
-        );
-
-        // Weekly Usage
-        let weekly_section = padded_control(
-            column![
-                text::body(fl!("weekly-usage")),
-                progress_bar(0.0..=100.0, self.weekly_usage_percent)
-                    .width(Length::Fill),
-                row![
-                    text::caption(format!("{:.0}%", self.weekly_usage_percent)),
-                    horizontal_space(),
-                    text::caption(self.format_reset_date(self.weekly_reset_time)),
-                ],
-            ]
+            Message::ToggleTrustCustomApiHost(enabled) => {
+                self.config.trust_custom_api_host = enabled;
+                api::set_base_url(self.config.api_base_url.as_deref(), enabled);
+                self.save_config();
+            }
+            Message::SetFileManagerCommand(command) => {
+                self.config.file_manager_command = (!command.is_empty()).then_some(command);
+                self.save_config();
+            }
+            Message::SetMonthlyBudget(value) => {
+                let trimmed = value.trim().trim_start_matches('$').replace(',', ".");
+                let parsed = if trimmed.is_empty() {
+                    Some(0.0)
+                } else {
+                    trimmed.parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0)
+                };
+                self.budget_input = value;
+                if let Some(budget) = parsed {
+                    self.config.monthly_budget_usd = budget;
+                    self.budget_alerted_step = 0;
+                    self.save_config();
+                    self.check_budget_alert();
+                }
+            }
+            Message::ToggleModelPricingSync(enabled) => {
+                self.config.sync_model_pricing = enabled;
+                self.save_config();
+            }
+            Message::ToggleRawResponse(enabled) => {
+                self.config.show_raw_response = enabled;
+                self.save_config();
+            }
+            Message::ToggleRawResponseExpanded => {
+                self.raw_response_expanded = !self.raw_response_expanded;
+            }
+            Message::CycleLogLevel => {
+                self.config.log_level = self.config.log_level.next();
+                logs::configure(self.config.log_level, self.config.log_to_file);
+                self.save_config();
+            }
+            Message::ToggleLogFile(enabled) => {
+                self.config.log_to_file = enabled;
+                logs::configure(self.config.log_level, self.config.log_to_file);
+                self.save_config();
+            }
+            Message::ToggleForecastNotifications(enabled) => {
+                self.config.notify_weekly_forecast = enabled;
+                self.save_config();
+            }
+            Message::ToggleQuietHours(enabled) => {
+                self.config.quiet_hours = enabled;
+                self.save_config();
+            }
+            Message::SetQuietHoursStart(hour) => {
+                self.config.quiet_hours_start = hour;
+                self.save_config();
+            }
+            Message::SetQuietHoursEnd(hour) => {
+                self.config.quiet_hours_end = hour;
+                self.save_config();
+            }
+            Message::QuietHoursTick(_) => {
+                self.flush_suppressed_alerts();
+            }
+            Message::ToggleWeeklySummary(enabled) => {
+                self.config.weekly_summary = enabled;
+                self.save_config();
+            }
+            Message::CycleWeeklySummaryDay => {
+                self.config.weekly_summary_day = self.config.weekly_summary_day.succ();
+                self.save_config();
+            }
+            Message::SetWeeklySummaryHour(hour) => {
+                self.config.weekly_summary_hour = hour;
+                self.save_config();
+            }
+            Message::WeeklySummaryTick(_) => {
+                return self.check_weekly_summary();
+            }
+            Message::WeeklySummaryReady(summary) => {
+                self.notify(
+                    fl!("weekly-summary-title"),
+                    fl!(
+                        "weekly-summary-body",
+                        messages = localize::format_count(summary.messages as u64),
+                        sessions = summary.sessions,
+                        peak = format!("{:.0}", summary.peak_weekly_percent),
+                        cost = localize::format_currency(summary.cost_usd)
+                    ),
+                );
+            }
+            Message::TogglePlanChangeNotifications(enabled) => {
+                self.config.notify_plan_changes = enabled;
+                self.save_config();
+            }
+            Message::SetIdleSessionMinutes(minutes) => {
+                self.config.idle_session_minutes = minutes;
+                self.save_config();
+            }
+            Message::ToggleIdleNotifications(enabled) => {
+                self.config.notify_idle_sessions = enabled;
+                self.save_config();
+            }
+            Message::ToggleSessionChangeNotifications(enabled) => {
+                self.config.notify_session_changes = enabled;
+                self.save_config();
+            }
+            Message::SetSnoozeHours(hours) => {
+                self.config.snooze_hours = hours;
+                self.save_config();
+            }
+            Message::ToggleScrollCyclesDisplay(enabled) => {
+                self.config.scroll_cycles_display = enabled;
+                self.save_config();
+            }
+            Message::Refresh => {
+                self.api_key_configured = api::api_key_configured();
+                if self.instance_role == InstanceRole::Secondary {
+                    dbus::request_refresh();
+                }
+                self.refresh_generation = self.refresh_generation.wrapping_add(1);
+            }
+            Message::InstanceRole(primary) => {
+                self.instance_role = if primary {
+                    InstanceRole::Primary
+                } else {
+                    InstanceRole::Secondary
+                };
+            }
+            Message::StartLogin => {
+                Self::spawn_in_terminal(&["claude", "/login"]);
+                self.login_started = Some(Instant::now());
+            }
+            Message::CheckLogin(now) => {
+                if api::has_valid_credentials() {
+                    self.login_started = None;
+                    self.refresh_generation = self.refresh_generation.wrapping_add(1);
+                } else if self.login_started.is_some_and(|started| now.duration_since(started) > LOGIN_WAIT) {
+                    self.login_started = None;
+                }
+            }
+            Message::OpenTerminal => {
+                Self::spawn_in_terminal(&["claude"]);
+            }
+            Message::UpdateClaude => {
+                Self::spawn_in_terminal(&["claude", "update"]);
+            }
+            Message::OpenSettings => {
+                if let Some(dir) = backend::claude_dir() {
+                    self.open_in_file_manager(dir);
+                }
+            }
+            Message::ShowPage(page) => {
+                self.popup_page = page;
+                self.report_copied = false;
+                if page == PopupPage::Diagnostics {
+                    self.probe_diagnostics();
+                }
+            }
+            Message::CopyDiagnostics => {
+                self.report_copied = true;
+                return cosmic::iced::clipboard::write(self.diagnostics_report());
+            }
+            Message::OpenProject(path) => {
+                self.open_in_file_manager(path);
+            }
+            Message::ExportData => {
+                let mut data = history::ExportData {
+                    samples: Vec::new(),
+                    rollups: Vec::new(),
+                    daily: self.daily_stats.clone(),
+                    projects: Vec::new(),
+                };
+                // Projects cover the same range as the retained history, not just the weekly leaderboard
+                let projects_since =
+                    Local::now().date_naive() - chrono::Duration::days(i64::from(self.config.history_retention_days));
+                let title = fl!("export-data");
+                let default_name = format!("claude-usage-{}.csv", Local::now().format("%Y-%m-%d"));
+
+                return cosmic::task::future(async move {
+                    let response = cosmic::dialog::file_chooser::save::Dialog::new()
+                        .title(title)
+                        .current_name(default_name)
+                        .save_file()
+                        .await;
+                    let path = match response {
+                        Ok(response) => response.url().and_then(|url| url.to_file_path().ok()),
+                        // Cancelling the dialog is not an error
+                        Err(cosmic::dialog::file_chooser::Error::Cancelled) => None,
+                        Err(err) => return Message::ExportFinished(Err(err.to_string())),
+                    };
+                    let Some(path) = path else {
+                        return Message::ExportFinished(Ok(None));
+                    };
+
+                    let result = tokio::task::spawn_blocking(move || {
+                        data.samples = history::load();
+                        data.rollups = history::load_rollups();
+                        data.projects = transcripts::project_costs(projects_since)
+                            .into_iter()
+                            .map(|p| history::ProjectStats {
+                                name: p.name,
+                                path: p.path,
+                                cost_usd: p.cost_usd,
+                                messages: p.messages,
+                            })
+                            .collect();
+                        history::export(&data, &path).map(|()| Some(path))
+                    })
+                    .await
+                    .unwrap_or_else(|err| Err(err.to_string()));
+                    Message::ExportFinished(result)
+                });
+            }
+            Message::HistoryCompacted(compacted) => {
+                debug!("Compacted {} usage history samples", compacted);
+            }
+            Message::CycleHistoryRetention => {
+                self.config.history_retention_days = match self.config.history_retention_days {
+                    ..=30 => 90,
+                    31..=90 => 365,
+                    _ => 30,
+                };
+                self.save_config();
+            }
+            Message::ExportFinished(result) => match result {
+                Ok(Some(path)) => {
+                    notifications::send(fl!("export-done"), path.display().to_string());
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::error!("Export failed: {}", err);
+                    notifications::send(fl!("export-failed"), err);
+                }
+            },
+            Message::OpenSettingsWindow => {
+                if self.settings_window.is_some() {
+                    return Task::none();
+                }
+                let (id, open) = window::open(window::Settings {
+                    size: Size::new(480.0, 640.0),
+                    min_size: Some(Size::new(360.0, 300.0)),
+                    ..Default::default()
+                });
+                self.settings_window = Some(id);
+                // Close the popup so the settings window gets focus
+                return match self.popup.take() {
+                    Some(popup) => Task::batch([destroy_popup(popup), open.discard()]),
+                    None => open.discard(),
+                };
+            }
+            Message::RequestResetSettings => {
+                self.confirm_reset = true;
+            }
+            Message::CancelResetSettings => {
+                self.confirm_reset = false;
+            }
+            Message::ConfirmResetSettings => {
+                self.confirm_reset = false;
+                self.config = ClaudeAppletConfig::default();
+                self.color_inputs = Default::default();
+                self.custom_mascot_svg = None;
+                self.svg_cache.borrow_mut().custom_mascots.clear();
+                logs::configure(self.config.log_level, self.config.log_to_file);
+                backend::set_claude_dir(None);
+                api::set_base_url(None, false);
+                localize::set_number_format(self.config.cost_precision, self.config.group_digits);
+                self.publish_poll_interval();
+                self.save_config();
+            }
+            Message::ConfigChanged(mut config) => {
+                config.validate();
+                logs::configure(config.log_level, config.log_to_file);
+                backend::set_claude_dir(config.claude_dir.as_deref());
+                api::set_base_url(config.api_base_url.as_deref(), config.trust_custom_api_host);
+                localize::set_number_format(config.cost_precision, config.group_digits);
+                // Pick up a changed GTK animation setting along with the applet config
+                self.reduced_motion = config::prefers_reduced_motion();
+                self.api_key_configured = api::api_key_configured();
+                let mascot_changed = config.custom_mascot_path != self.config.custom_mascot_path;
+                // Follow colors changed outside the settings window, keeping any half-typed input
+                let colors = Self::custom_colors(&self.config).into_iter().zip(Self::custom_colors(&config));
+                for (input, (old, new)) in self.color_inputs.iter_mut().zip(colors) {
+                    if old != new {
+                        *input = new.unwrap_or_default();
+                    }
+                }
+                self.config = config;
+                if mascot_changed {
+                    self.reload_custom_mascot();
+                }
+                self.publish_poll_interval();
+            }
+            Message::CycleIconDisplay => {
+                self.config.icon_display = match self.config.icon_display {
+                    IconDisplay::Session => IconDisplay::Weekly,
+                    IconDisplay::Weekly => IconDisplay::Both,
+                    IconDisplay::Both => IconDisplay::Concentric,
+                    IconDisplay::Concentric => IconDisplay::Session,
+                };
+                self.save_config();
+            }
+            Message::CycleIndicatorStyle => {
+                self.config.indicator_style = match self.config.indicator_style {
+                    IndicatorStyle::Ring => IndicatorStyle::Battery,
+                    IndicatorStyle::Battery => IndicatorStyle::Text,
+                    IndicatorStyle::Text => IndicatorStyle::Ring,
+                };
+                self.save_config();
+            }
+            Message::SetTextTemplate(template) => {
+                self.config.text_template = (!template.is_empty()).then_some(template);
+                self.save_config();
+            }
+            Message::CycleColorMode => {
+                self.config.color_mode = match self.config.color_mode {
+                    ColorMode::Threshold => ColorMode::Gradient,
+                    ColorMode::Gradient => ColorMode::Symbolic,
+                    ColorMode::Symbolic => ColorMode::Threshold,
+                };
+                self.save_config();
+            }
+            Message::ToggleMascot(enabled) => {
+                self.config.show_mascot = enabled;
+                self.save_config();
+            }
+            Message::ToggleMascotAnimation(enabled) => {
+                self.config.animate_mascot = enabled;
+                self.save_config();
+            }
+            Message::ToggleHideWhenInactive(enabled) => {
+                self.config.hide_when_inactive = enabled;
+                self.save_config();
+            }
+            Message::ToggleClaudeDesktop(enabled) => {
+                self.config.include_claude_desktop = enabled;
+                self.save_config();
+            }
+            Message::ToggleProcessBadge(enabled) => {
+                self.config.show_process_badge = enabled;
+                self.save_config();
+            }
+            Message::SetWarningThreshold(window, value) => {
+                match window {
+                    UsageWindow::Session => self.config.session_warning_threshold = value,
+                    UsageWindow::Weekly => self.config.weekly_warning_threshold = value,
+                }
+                self.save_config();
+            }
+            Message::SetCriticalThreshold(window, value) => {
+                match window {
+                    UsageWindow::Session => self.config.session_critical_threshold = value,
+                    UsageWindow::Weekly => self.config.weekly_critical_threshold = value,
+                }
+                self.save_config();
+            }
+            Message::SetOpusWarningThreshold(value) => {
+                self.config.opus_warning_threshold = value;
+                self.save_config();
+            }
+            Message::SetOpusCriticalThreshold(value) => {
+                self.config.opus_critical_threshold = value;
+                self.save_config();
+            }
+            Message::CycleTextMetric => {
+                self.config.percentage_text_metric = self.config.percentage_text_metric.next();
+                self.save_config();
+            }
+            Message::TogglePercentageText(enabled) => {
+                self.config.show_percentage_text = enabled;
+                self.save_config();
+            }
+            Message::ToggleCostText(enabled) => {
+                self.config.show_cost_text = enabled;
+                self.save_config();
+            }
+            Message::Toggle24HourClock(enabled) => {
+                self.config.use_24_hour_clock = enabled;
+                self.save_config();
+            }
+            Message::SetCostPrecision(precision) => {
+                self.config.cost_precision = precision;
+                localize::set_number_format(precision, self.config.group_digits);
+                self.save_config();
+            }
+            Message::ToggleGroupDigits(enabled) => {
+                self.config.group_digits = enabled;
+                localize::set_number_format(self.config.cost_precision, enabled);
+                self.save_config();
+            }
+            Message::CycleResetTimeFormat => {
+                self.config.reset_time_format = match self.config.reset_time_format {
+                    ResetTimeFormat::Relative => ResetTimeFormat::Absolute,
+                    ResetTimeFormat::Absolute => ResetTimeFormat::Both,
+                    ResetTimeFormat::Both => ResetTimeFormat::Relative,
+                };
+                self.save_config();
+            }
+            Message::ToggleResetCountdown(enabled) => {
+                self.config.show_reset_countdown = enabled;
+                self.save_config();
+            }
+            Message::ToggleHoverPreview(enabled) => {
+                self.config.hover_preview = enabled;
+                self.save_config();
+            }
+            Message::ToggleRemaining(enabled) => {
+                self.config.show_remaining = enabled;
+                self.save_config();
+            }
+            Message::TogglePulse(enabled) => {
+                self.config.pulse_when_critical = enabled;
+                self.save_config();
+            }
+            Message::SetPollInterval(minutes) => {
+                self.config.poll_interval_minutes = minutes;
+                self.publish_poll_interval();
+                self.save_config();
+            }
+            Message::ToggleAdaptivePolling(enabled) => {
+                self.config.adaptive_polling = enabled;
+                self.publish_poll_interval();
+                self.save_config();
+            }
+            Message::ToggleDailySummary(enabled) => {
+                self.config.show_daily_summary = enabled;
+                self.save_config();
+            }
+            Message::SetPopupMaxHeight(height) => {
+                self.config.popup_max_height = height;
+                self.save_config();
+            }
+            Message::SetPopupWidth(width) => {
+                self.config.popup_width = width;
+                self.save_config();
+            }
+            Message::CyclePopupDensity => {
+                self.config.popup_density = match self.config.popup_density {
+                    PopupDensity::Comfortable => PopupDensity::Compact,
+                    PopupDensity::Compact => PopupDensity::Comfortable,
+                };
+                self.save_config();
+            }
+            Message::TogglePopupSection(section, visible) => {
+                self.config.popup_sections.retain(|shown| *shown != section);
+                if visible {
+                    self.config.popup_sections.push(section);
+                }
+                self.save_config();
+            }
+            Message::MovePopupSectionUp(section) => {
+                let sections = &mut self.config.popup_sections;
+                if let Some(index) = sections.iter().position(|shown| *shown == section).filter(|&index| index > 0) {
+                    sections.swap(index, index - 1);
+                    self.save_config();
+                }
+            }
+            Message::MovePopupSectionDown(section) => {
+                let sections = &mut self.config.popup_sections;
+                if let Some(index) = sections
+                    .iter()
+                    .position(|shown| *shown == section)
+                    .filter(|&index| index + 1 < sections.len())
+                {
+                    sections.swap(index, index + 1);
+                    self.save_config();
+                }
+            }
+            Message::ToggleOrganizationUsage(enabled) => {
+                self.config.show_organization_usage = enabled;
+                self.save_config();
+            }
+            Message::ToggleServiceStatus(enabled) => {
+                self.config.show_service_status = enabled;
+                if !enabled {
+                    self.service_status = status::StatusUpdate::default();
+                    self.known_incident_ids.clear();
+                    self.incidents_seeded = false;
+                }
+                self.save_config();
+            }
+            Message::ToggleIncidentNotifications(enabled) => {
+                self.config.notify_service_incidents = enabled;
+                self.save_config();
+            }
+            Message::ToggleClaudeUpdateCheck(enabled) => {
+                self.config.check_claude_updates = enabled;
+                if !enabled {
+                    self.claude_version = version::VersionUpdate::default();
+                }
+                self.save_config();
+            }
+            Message::ToggleAppletUpdateCheck(enabled) => {
+                self.config.check_applet_updates = enabled;
+                if !enabled {
+                    self.applet_release = None;
+                }
+                self.save_config();
+            }
+            Message::SetCustomColor(slot, value) => {
+                let value = value.trim().to_string();
+                // Empty clears the override; anything else is only saved once it parses
+                let parsed = if value.is_empty() {
+                    Some(None)
+                } else {
+                    config::parse_hex_color(&value).map(|_| Some(value.clone()))
+                };
+                let (input, field) = match slot {
+                    ColorSlot::Low => (&mut self.color_inputs[0], &mut self.config.custom_low_color),
+                    ColorSlot::Medium => (&mut self.color_inputs[1], &mut self.config.custom_medium_color),
+                    ColorSlot::High => (&mut self.color_inputs[2], &mut self.config.custom_high_color),
+                    ColorSlot::Mascot => (&mut self.color_inputs[3], &mut self.config.custom_mascot_color),
+                };
+                *input = value;
+                if let Some(new_value) = parsed {
+                    *field = new_value;
+                    self.save_config();
+                }
+            }
+            Message::CycleClickAction(button) => {
+                let action = match button {
+                    ClickButton::Left => &mut self.config.left_click_action,
+                    ClickButton::Middle => &mut self.config.middle_click_action,
+                    ClickButton::Right => &mut self.config.right_click_action,
+                };
+                *action = action.next();
+                self.save_config();
+            }
+            Message::CycleBillingMode => {
+                self.config.billing_mode = match self.config.billing_mode {
+                    BillingMode::Auto => BillingMode::Subscription,
+                    BillingMode::Subscription => BillingMode::ApiKey,
+                    BillingMode::ApiKey => BillingMode::Auto,
+                };
+                self.save_config();
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> Element<'_, Self::Message> {
+        // Create custom colored indicator, or just a dot while there is nothing to show
+        let indicator = if self.is_inactive() {
+            if self.config.indicator_style == IndicatorStyle::Text {
+                text::body("–").class(theme::Text::Color(self.theme_colors().inactive)).into()
+            } else {
+                self.create_color_dot(self.theme_colors().inactive, 6.0)
+            }
+        } else {
+            let theme = theme::active();
+            let cosmic = theme.cosmic();
+            let mut indicator = self.create_usage_indicator();
+            let sessions = self.running_sessions();
+            if self.config.show_process_badge && sessions > 0 {
+                let label = if sessions > 9 {
+                    "9+".to_string()
+                } else {
+                    sessions.to_string()
+                };
+                let badge = Badge {
+                    label,
+                    color: cosmic.accent_color().into(),
+                    foreground: cosmic.on_accent_color().into(),
+                };
+                indicator = self.overlay_badge(indicator, badge, Alignment::Start);
+            }
+            // Offline: the rings show the last known numbers
+            if self.is_offline() {
+                let size = (self.indicator_size() * 0.5).max(8.0);
+                let cloud = icon::from_name("network-offline-symbolic").size(size as u16).icon();
+                indicator = self.overlay_corner(indicator, cloud.into(), Alignment::End);
+            } else if self.has_fetch_error() {
+                // Last fetch failed: the rings show stale numbers
+                let badge = Badge {
+                    label: "!".to_string(),
+                    color: cosmic.warning_color().into(),
+                    foreground: cosmic.on_bg_color().into(),
+                };
+                indicator = self.overlay_badge(indicator, badge, Alignment::End);
+            }
+            indicator
+        };
+
+        // Wrap in a button for click handling
+        let indicator_button = button::custom(indicator)
+            .padding(4)
+            .class(cosmic::theme::Button::AppletIcon)
+            .name(self.accessible_summary())
+            .on_press(Message::PanelClick(self.config.left_click_action));
+
+        // The button consumes left clicks; middle/right clicks and scrolling fall through to the mouse area
+        let indicator_button = mouse_area(indicator_button)
+            .on_middle_press(Message::PanelClick(self.config.middle_click_action))
+            .on_right_press(Message::PanelClick(self.config.right_click_action))
+            .on_scroll(Message::PanelScroll)
+            .on_enter(Message::PanelHover(true))
+            .on_exit(Message::PanelHover(false));
+
+        let indicator_button: Element<'_, Self::Message> = match self.lockout_reset_time() {
+            Some(reset_time) if self.is_locked_out() => tooltip(
+                indicator_button,
+                text::body(fl!("locked-until", countdown = Self::format_countdown(reset_time))),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+            _ if self.token_expired && !self.has_credentials => tooltip(
+                indicator_button,
+                text::body(fl!("login-expired-tooltip")),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+            _ if self.has_fetch_error() => tooltip(
+                indicator_button,
+                text::body(fl!("stale-data", error = self.api_error.clone().unwrap_or_default())),
+                tooltip::Position::Bottom,
+            )
+            .into(),
+            _ => indicator_button.into(),
+        };
+
+        let content: Element<'_, Self::Message> = if self.uses_api_key_billing() {
+            // API-key billing has no usage windows; the organization's month-to-date cost is the useful number
+            self.panel_stack(
+                vec![
+                    indicator_button,
+                    text::body(localize::format_currency(self.org_usage.cost_usd)).into(),
+                ],
+                4.0,
+            )
+        } else if self.local_stats_only() {
+            // Without usage windows, today's local cost stands in for usage
+            self.panel_stack(
+                vec![
+                    indicator_button,
+                    text::body(localize::format_currency(self.local_costs().0)).into(),
+                ],
+                4.0,
+            )
+        } else if self.config.show_cost_text
+            || ((self.config.show_percentage_text || self.config.show_reset_countdown) && self.has_credentials)
+        {
+            let mut items = vec![indicator_button];
+            // The text indicator style already shows the percentages
+            let show_metric = self.config.show_percentage_text
+                && self.has_credentials
+                && self.config.indicator_style != IndicatorStyle::Text;
+            if show_metric {
+                items.push(text::body(self.percentage_text()).into());
+            }
+            if self.config.show_reset_countdown && self.has_credentials {
+                if let Some(reset_time) = self.session_reset_time {
+                    items.push(self.reset_text(text::body(Self::format_compact_countdown(reset_time))));
+                }
+            }
+            // Shown once when the percentage text already shows the cost
+            if self.config.show_cost_text && !(show_metric && self.config.percentage_text_metric == TextMetric::Cost) {
+                items.push(text::body(localize::format_currency(self.local_costs().0)).into());
+            }
+            self.panel_stack(items, 4.0)
+        } else {
+            indicator_button
+        };
+
+        let content: Element<'_, Self::Message> = match self.incident_color() {
+            Some(color) => self.panel_stack(vec![content, self.create_color_dot(color, 8.0)], 2.0),
+            None => content,
+        };
+
+        // Use autosize_window to properly size the panel button
+        self.core.applet.autosize_window(content).into()
+    }
+
+    fn view_window(&self, id: Id) -> Element<'_, Self::Message> {
+        if self.settings_window == Some(id) {
+            return self.view_settings_window();
+        }
+        if self.hover_popup == Some(id) {
+            return self.view_hover_preview();
+        }
+        match self.popup_page {
+            PopupPage::Usage => {}
+            PopupPage::Logs => return self.view_logs_page(),
+            PopupPage::Diagnostics => return self.view_diagnostics_page(),
+        }
+
+        let Spacing {
+            space_xxs,
+            space_s,
+            ..
+        } = self.popup_spacing();
+
+        // Header with the plan tier badge
+        let tier = Self::plan_tier(&self.subscription_type).filter(|_| self.has_credentials);
+        let header = row![text::heading(fl!("claude-code"))]
+            .push_maybe(tier.map(Self::tier_badge))
+            .push(horizontal_space())
+            .push(button::icon(icon::from_name("emblem-system-symbolic")).on_press(Message::OpenSettingsWindow))
+            .spacing(space_xxs)
+            .align_y(Alignment::Center)
+            .padding([0, space_s]);
+
+        let plan_text = if self.has_credentials {
+            format!("{} {}", self.subscription_type, fl!("plan"))
+        } else if self.token_expired {
+            fl!("login-expired")
+        } else {
+            fl!("not-logged-in")
+        };
+
+        let mut plan_column = column![self.body_text(plan_text)].spacing(space_xxs);
+        if !self.has_credentials {
+            plan_column = plan_column.push(match self.login_started {
+                Some(_) => Element::from(text::caption(fl!("waiting-for-login"))),
+                None => {
+                    let label = if self.token_expired { fl!("log-in-again") } else { fl!("log-in") };
+                    button::suggested(label).on_press(Message::StartLogin).into()
+                }
+            });
+        }
+        let plan_section = self.padded(plan_column);
+
+        // Lockout banner when a usage window is exhausted
+        let lockout_section = if self.is_locked_out() {
+            let countdown = self
+                .lockout_reset_time()
+                .map(Self::format_countdown)
+                .unwrap_or_else(|| fl!("unknown"));
+            Some(self.padded(
+                column![
+                    self.body_text(fl!("limit-reached")),
+                    text::caption(fl!("locked-until", countdown = countdown)),
+                ]
+                .spacing(space_xxs)
+            ))
+        } else {
+            None
+        };
+
+        // 5-Hour Session Usage
+        let session_section = self.menu_row(
+            column![
+                self.body_text(fl!("session-usage")),
+                self.usage_bar(UsageWindow::Session, self.session_usage_percent),
+                row![
+                    text::caption(self.format_percent(self.session_usage_percent)),
+                    horizontal_space(),
+                    self.reset_text(text::caption(self.format_reset_time(self.session_reset_time))),
+                ],
+            ]
+            .push_maybe(self.format_block_elapsed().map(text::caption))
+            .spacing(space_xxs)
+        )
+        .name(self.accessible_usage(UsageWindow::Session))
+        .description(fl!("a11y-open-usage-page"))
+        .on_press(Message::OpenUsagePage);
+        let session_section: Element<'_, Message> = match self.view_estimated_session_section() {
+            Some(estimate) => estimate,
+            None => session_section.into(),
+        };
+
+        // Weekly Usage
+        let weekly_section = self.menu_row(
+            column![
+                self.body_text(fl!("weekly-usage")),
+                self.usage_bar(UsageWindow::Weekly, self.weekly_usage_percent),
+                row![
+                    text::caption(self.format_percent(self.weekly_usage_percent)),
+                    horizontal_space(),
+                    self.reset_text(text::caption(self.format_reset_time(self.weekly_reset_time))),
+                ],
+                text::caption(self.format_weekly_forecast()),
+            ]
+            .spacing(space_xxs)
+        )
+        .name(self.accessible_usage(UsageWindow::Weekly))
+        .description(fl!("a11y-open-usage-page"))
+        .on_press(Message::OpenUsagePage);
+
+        // Extra usage (overage) - only shown when enabled on the account
+        let extra_usage_section = self.extra_usage.as_ref().map(|extra| {
+            let spent_text = match extra.monthly_limit_usd {
+                Some(limit) => fl!(
+                    "extra-usage-spent-of",
+                    spent = localize::format_currency(extra.used_usd),
+                    limit = localize::format_currency(limit)
+                ),
+                None => fl!("extra-usage-spent", spent = localize::format_currency(extra.used_usd)),
+            };
+
+            let mut extra_column = column![self.body_text(fl!("extra-usage"))].spacing(space_xxs);
+            if let Some(utilization) = extra.utilization {
+                extra_column = extra_column
+                    .push(progress_bar(0.0..=100.0, utilization).width(Length::Fill));
+            }
+            self.padded(extra_column.push(text::caption(spent_text)))
+        });
+
+        // Status section (process count)
+        let status_text = if self.running_sessions() > 0 {
+            fl!("sessions-running", count = self.running_sessions())
+        } else {
+            fl!("no-sessions")
+        };
+
+        let mut status_column = column![
+            self.body_text(fl!("status")),
+            text::caption(format!("● {}", status_text)),
+        ]
+        .spacing(space_xxs);
+
+        for session in &self.cli_sessions {
+            let label = match &session.branch {
+                Some(branch) => fl!(
+                    "session-project-branch",
+                    project = session.project_name(),
+                    branch = branch.clone()
+                ),
+                None => session.project_name(),
+            };
+            let entry = match self.idle_duration(session) {
+                Some(idle) => text::caption(format!(
+                    "   {label} · {}",
+                    fl!("session-idle", minutes = idle.as_secs() / 60)
+                ))
+                .class(theme::Text::Color(self.theme_colors().medium)),
+                None => text::caption(format!("   {label}")),
+            };
+            status_column = status_column.push(entry);
+        }
+
+        if self.config.include_claude_desktop && self.desktop_process_count > 0 {
+            status_column = status_column.push(text::caption(fl!(
+                "claude-desktop-running",
+                count = self.desktop_process_count
+            )));
+        }
+
+        if self.config.show_service_status && !self.service_status.description.is_empty() {
+            status_column = status_column.push(text::caption(fl!(
+                "service-status",
+                description = self.service_status.description.clone()
+            )));
+            for incident in &self.service_status.incidents {
+                status_column = status_column
+                    .push(text::caption(format!("⚠ {} ({})", incident.name, incident.status)));
+            }
+        }
+
+        if let Some(installed) = &self.claude_version.installed {
+            status_column = status_column.push(text::caption(fl!("claude-version", version = installed.clone())));
+        }
+        if self.claude_version.update_available() {
+            if let Some(latest) = &self.claude_version.latest {
+                status_column = status_column
+                    .push(text::caption(fl!("update-available", version = latest.clone())));
+            }
+        }
+
+        let status_section = self.padded(status_column);
+
+        // Error display if any, with what usually helps for its kind of failure
+        let error_section = self.api_error.as_ref().map(|error| self.view_error_section(error));
+
+        // Action buttons
+        let mut actions = column![
+            self.menu_row(self.body_text(fl!("open-terminal")))
+                .on_press(Message::OpenTerminal),
+            self.menu_row(self.body_text(fl!("open-claude-dir")))
+                .on_press(Message::OpenSettings),
+            self.menu_row(self.body_text(fl!("export-data")))
+                .on_press(Message::ExportData),
+            self.menu_row(self.body_text(fl!("view-logs")))
+                .on_press(Message::ShowPage(PopupPage::Logs)),
+            self.menu_row(self.body_text(fl!("view-diagnostics")))
+                .on_press(Message::ShowPage(PopupPage::Diagnostics)),
+        ];
+        if self.config.notify_thresholds && self.has_credentials {
+            let snooze_button = match self.alerts_snoozed_until.filter(|_| self.alerts_snoozed()) {
+                Some(until) => self.menu_row(self.body_text(fl!(
+                    "alerts-snoozed-until",
+                    time = self.format_clock(until.with_timezone(&Local))
+                )))
+                .on_press(Message::ResumeAlerts),
+                None => self.menu_row(self.body_text(fl!("snooze-alerts", hours = self.config.snooze_hours)))
+                    .on_press(Message::SnoozeAlerts),
+            };
+            actions = actions.push(snooze_button);
+        }
+        if self.claude_version.update_available() {
+            actions = actions.push(
+                self.menu_row(self.body_text(fl!("update-claude")))
+                    .on_press(Message::UpdateClaude),
+            );
+        }
+        if let Some(release) = &self.applet_release {
+            actions = actions.push(
+                self.menu_row(text::caption(fl!("applet-update-available", version = release.version.clone())))
+                    .on_press(Message::OpenReleasePage),
+            );
+        }
+
+        let mut actions = Some(actions);
+        let mut content_list = column![];
+
+        let api_key_billing = self.uses_api_key_billing();
+
+        // In API-key mode the personal view already shows the report data, so no tabs are needed
+        let active_tab = if self.config.show_organization_usage && !api_key_billing {
+            content_list = content_list.push(
+                self.padded(tab_bar::horizontal(&self.tab_model).on_activate(Message::TabActivated))
+            );
+            self.tab_model.active_data::<PopupTab>().copied().unwrap_or(PopupTab::Personal)
+        } else {
+            PopupTab::Personal
+        };
+
+        if active_tab == PopupTab::Organization {
+            content_list = content_list.push(self.view_organization_section());
+        } else {
+            let plan_shown = self.config.popup_sections.contains(&PopupSection::Plan);
+            let mut trailing_error = None;
+
+            // Each section is one or more groups; dividers go between groups as well as between sections
+            let mut groups: Vec<(PopupSection, Element<'_, Message>)> = Vec::new();
+            if self.local_stats_only() {
+                let (heading, note) = match self.cloud_provider() {
+                    Some(backend::Provider::Bedrock) => {
+                        (fl!("cloud-provider", provider = fl!("provider-bedrock")), fl!("cloud-provider-no-limits"))
+                    }
+                    Some(backend::Provider::Vertex) => {
+                        (fl!("cloud-provider", provider = fl!("provider-vertex")), fl!("cloud-provider-no-limits"))
+                    }
+                    _ => (
+                        fl!("gateway-unsupported", url = api::base_url()),
+                        fl!("gateway-unsupported-note"),
+                    ),
+                };
+                if plan_shown {
+                    groups.push((
+                        PopupSection::Plan,
+                        self.padded(column![self.body_text(heading), text::caption(note)].spacing(space_xxs)).into(),
+                    ));
+                }
+                let summary = column![self.view_today_section()]
+                    .push_maybe(self.view_model_mix_section())
+                    .push(self.view_cost_section())
+                    .push_maybe(self.view_top_projects_section());
+                groups.push((PopupSection::Stats, summary.into()));
+                groups.extend(self.view_budget_section().map(|budget| (PopupSection::Stats, budget)));
+                groups.push((PopupSection::Status, status_section.into()));
+            } else if api_key_billing {
+                if plan_shown {
+                    groups.push((PopupSection::Plan, self.padded(self.body_text(fl!("api-key-billing"))).into()));
+                }
+                groups.push((PopupSection::Stats, self.view_organization_section()));
+                groups.extend(self.view_budget_section().map(|budget| (PopupSection::Stats, budget)));
+                groups.push((PopupSection::Status, status_section.into()));
+            } else {
+                // Errors stay with the status section, or go to the bottom while it is hidden
+                let status_error = if self.config.popup_sections.contains(&PopupSection::Status) {
+                    error_section
+                } else {
+                    trailing_error = error_section;
+                    None
+                };
+
+                // Logging in and the lockout banner stay visible while the plan section is hidden
+                if plan_shown || !self.has_credentials || lockout_section.is_some() {
+                    let plan_group = column![]
+                        .push_maybe((plan_shown || !self.has_credentials).then_some(plan_section))
+                        .push_maybe(lockout_section);
+                    groups.push((PopupSection::Plan, plan_group.into()));
+                }
+                groups.push((PopupSection::Session, session_section));
+                groups.push((PopupSection::Weekly, weekly_section.into()));
+                groups.extend(
+                    self.other_windows
+                        .iter()
+                        .map(|window| (PopupSection::Models, self.view_usage_window(window))),
+                );
+                groups.extend(self.view_budget_section().map(|budget| (PopupSection::Stats, budget)));
+                let summary = column![]
+                    .push_maybe(self.config.show_daily_summary.then(|| self.view_today_section()))
+                    .push_maybe(self.view_model_mix_section())
+                    .push(self.view_cost_section())
+                    .push_maybe(self.view_top_projects_section());
+                groups.push((PopupSection::Stats, summary.into()));
+                groups.extend(extra_usage_section.map(|extra| (PopupSection::Stats, extra.into())));
+                groups.push((PopupSection::Status, column![status_section].push_maybe(status_error).into()));
+                groups.extend(self.view_raw_response_section().map(|raw| (PopupSection::Status, raw)));
+            }
+            groups.extend(actions.take().map(|actions| (PopupSection::Actions, actions.into())));
+
+            // A hidden plan section only leaves pinned content behind, which goes first
+            let order = (!plan_shown)
+                .then_some(PopupSection::Plan)
+                .into_iter()
+                .chain(self.config.popup_sections.iter().copied());
+
+            let mut first = true;
+            for section in order {
+                let (matching, rest): (Vec<_>, Vec<_>) =
+                    groups.into_iter().partition(|(group_section, _)| *group_section == section);
+                groups = rest;
+                for (_, group) in matching {
+                    if !first {
+                        content_list = content_list.push(self.popup_divider());
+                    }
+                    first = false;
+                    content_list = content_list.push(group);
+                }
+            }
+
+            if let Some(error_widget) = trailing_error {
+                content_list = content_list.push(error_widget);
+            }
+        }
+
+        if let Some(actions) = actions.filter(|_| self.config.popup_sections.contains(&PopupSection::Actions)) {
+            content_list = content_list
+                .push(self.popup_divider())
+                .push(actions);
+        }
+
+        // Keep the header pinned and scroll everything else once it outgrows the max height
+        let body = container(scrollable(content_list).height(Length::Shrink))
+            .max_height(self.config.popup_max_height as f32);
+
+        self.core
+            .applet
+            .popup_container(column![header, body].padding([8, 0]).width(self.popup_width()))
+            .into()
+    }
+
+    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
+        Some(cosmic::applet::style())
+    }
+}
+
+impl ClaudeApplet {
+    /// Standalone settings window built from libcosmic settings sections
+    fn view_settings_window(&self) -> Element<'_, Message> {
+        let icon_display_text = match self.config.icon_display {
+            IconDisplay::Both => fl!("icon-display-both"),
+            IconDisplay::Concentric => fl!("icon-display-concentric"),
+            IconDisplay::Session => fl!("icon-display-session"),
+            IconDisplay::Weekly => fl!("icon-display-weekly"),
+        };
+
+        let indicator_style_text = match self.config.indicator_style {
+            IndicatorStyle::Ring => fl!("indicator-style-ring"),
+            IndicatorStyle::Battery => fl!("indicator-style-battery"),
+            IndicatorStyle::Text => fl!("indicator-style-text"),
+        };
+
+        let color_mode_text = match self.config.color_mode {
+            ColorMode::Threshold => fl!("color-mode-threshold"),
+            ColorMode::Gradient => fl!("color-mode-gradient"),
+            ColorMode::Symbolic => fl!("color-mode-symbolic"),
+        };
+
+        let reset_time_format_text = match self.config.reset_time_format {
+            ResetTimeFormat::Relative => fl!("reset-time-relative"),
+            ResetTimeFormat::Absolute => fl!("reset-time-absolute"),
+            ResetTimeFormat::Both => fl!("reset-time-both"),
+        };
+
+        let text_metric_text = match self.config.percentage_text_metric {
+            TextMetric::Auto => fl!("text-metric-auto"),
+            TextMetric::Session => fl!("text-metric-session"),
+            TextMetric::Weekly => fl!("text-metric-weekly"),
+            TextMetric::Highest => fl!("text-metric-highest"),
+            TextMetric::Cost => fl!("text-metric-cost"),
+        };
+
+        let popup_density_text = match self.config.popup_density {
+            PopupDensity::Comfortable => fl!("popup-density-comfortable"),
+            PopupDensity::Compact => fl!("popup-density-compact"),
+        };
+
+        let billing_mode_text = match self.config.billing_mode {
+            BillingMode::Auto => fl!("billing-mode-auto"),
+            BillingMode::Subscription => fl!("billing-mode-subscription"),
+            BillingMode::ApiKey => fl!("billing-mode-api-key"),
+        };
+
+        let data_section = settings::section()
+            .title(fl!("settings-data"))
+            .add(settings::item(
+                fl!("claude-dir"),
+                text_input(fl!("claude-dir-placeholder"), self.config.claude_dir.as_deref().unwrap_or_default())
+                    .on_input(Message::SetClaudeDir)
+                    .width(Length::Fixed(200.0)),
+            ))
+            .add(settings::item(
+                fl!("include-claude-desktop"),
+                toggler(self.config.include_claude_desktop).on_toggle(Message::ToggleClaudeDesktop),
+            ))
+            .add(settings::item(
+                fl!("api-base-url"),
+                text_input("https://api.anthropic.com", self.config.api_base_url.as_deref().unwrap_or_default())
+                    .on_input(Message::SetApiBaseUrl)
+                    .width(Length::Fixed(200.0)),
+            ))
+            .add(settings::item(
+                fl!("trust-custom-api-host"),
+                toggler(self.config.trust_custom_api_host).on_toggle(Message::ToggleTrustCustomApiHost),
+            ))
+            .add(settings::item(
+                fl!("billing-mode"),
+                button::standard(billing_mode_text).on_press(Message::CycleBillingMode),
+            ))
+            .add(settings::item(
+                format!("{}: {} min", fl!("poll-interval"), self.config.poll_interval_minutes),
+                slider(5..=120, self.config.poll_interval_minutes.min(120) as u8, |v| Message::SetPollInterval(v as u32))
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("adaptive-polling"),
+                toggler(self.config.adaptive_polling).on_toggle(Message::ToggleAdaptivePolling),
+            ))
+            .add(settings::item(
+                fl!("monthly-budget"),
+                text_input(fl!("monthly-budget-placeholder"), &self.budget_input)
+                    .on_input(Message::SetMonthlyBudget)
+                    .width(Length::Fixed(90.0)),
+            ))
+            .add(settings::item(
+                fl!("sync-model-pricing"),
+                toggler(self.config.sync_model_pricing).on_toggle(Message::ToggleModelPricingSync),
+            ))
+            .add(settings::item(
+                fl!("history-retention"),
+                button::standard(fl!("history-retention-days", days = self.config.history_retention_days))
+                    .on_press(Message::CycleHistoryRetention),
+            ))
+            .add(settings::item(
+                fl!("organization-usage"),
+                toggler(self.config.show_organization_usage).on_toggle(Message::ToggleOrganizationUsage),
+            ));
+
+        let indicator_section = settings::section()
+            .title(fl!("settings-indicator"))
+            .add(settings::item(
+                fl!("icon-display"),
+                button::standard(icon_display_text).on_press(Message::CycleIconDisplay),
+            ))
+            .add(settings::item(
+                fl!("indicator-style"),
+                button::standard(indicator_style_text).on_press(Message::CycleIndicatorStyle),
+            ))
+            .add(settings::item(
+                fl!("text-template"),
+                text_input("{session} · {weekly}", self.config.text_template.as_deref().unwrap_or_default())
+                    .on_input(Message::SetTextTemplate)
+                    .width(Length::Fixed(200.0)),
+            ))
+            .add(settings::item(
+                fl!("show-mascot"),
+                toggler(self.config.show_mascot).on_toggle(Message::ToggleMascot),
+            ))
+            .add(settings::item(
+                fl!("animate-mascot"),
+                toggler(self.config.animate_mascot).on_toggle(Message::ToggleMascotAnimation),
+            ))
+            .add(settings::item(
+                fl!("custom-mascot"),
+                text_input(
+                    fl!("custom-mascot-placeholder"),
+                    self.config.custom_mascot_path.as_deref().unwrap_or_default(),
+                )
+                .on_input(Message::SetCustomMascotPath)
+                .on_submit(|_| Message::LoadCustomMascot)
+                .width(Length::Fixed(200.0)),
+            ))
+            .add(settings::item(
+                fl!("show-process-badge"),
+                toggler(self.config.show_process_badge).on_toggle(Message::ToggleProcessBadge),
+            ))
+            .add(settings::item(
+                fl!("hide-when-inactive"),
+                toggler(self.config.hide_when_inactive).on_toggle(Message::ToggleHideWhenInactive),
+            ))
+            .add(settings::item(
+                fl!("show-percentage"),
+                toggler(self.config.show_percentage_text).on_toggle(Message::TogglePercentageText),
+            ))
+            .add(settings::item(
+                fl!("percentage-text-metric"),
+                button::standard(text_metric_text).on_press(Message::CycleTextMetric),
+            ))
+            .add(settings::item(
+                fl!("show-cost-text"),
+                toggler(self.config.show_cost_text).on_toggle(Message::ToggleCostText),
+            ))
+            .add(settings::item(
+                fl!("show-remaining"),
+                toggler(self.config.show_remaining).on_toggle(Message::ToggleRemaining),
+            ))
+            .add(settings::item(
+                fl!("show-reset-countdown"),
+                toggler(self.config.show_reset_countdown).on_toggle(Message::ToggleResetCountdown),
+            ))
+            .add(settings::item(
+                fl!("hover-preview"),
+                toggler(self.config.hover_preview).on_toggle(Message::ToggleHoverPreview),
+            ))
+            .add(settings::item(
+                fl!("show-daily-summary"),
+                toggler(self.config.show_daily_summary).on_toggle(Message::ToggleDailySummary),
+            ))
+            .add(settings::item(
+                format!("{}: {} px", fl!("popup-max-height"), self.config.popup_max_height),
+                slider(300..=1200, self.config.popup_max_height, Message::SetPopupMaxHeight)
+                    .step(50u32)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {} px", fl!("popup-width"), self.config.popup_width),
+                slider(280..=600, self.config.popup_width, Message::SetPopupWidth)
+                    .step(20u32)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("popup-density"),
+                button::standard(popup_density_text).on_press(Message::CyclePopupDensity),
+            ))
+            .add(settings::item(
+                fl!("pulse-when-critical"),
+                toggler(self.config.pulse_when_critical).on_toggle(Message::TogglePulse),
+            ));
+
+        // Shown sections in popup order, then the hidden ones
+        let shown_sections = self.config.popup_sections.len();
+        let hidden_sections = PopupSection::ALL
+            .into_iter()
+            .filter(|section| !self.config.popup_sections.contains(section));
+        let mut popup_sections_section = settings::section().title(fl!("settings-popup-sections"));
+        for (index, section) in self.config.popup_sections.iter().copied().chain(hidden_sections).enumerate() {
+            let shown = index < shown_sections;
+            let controls = row![
+                button::icon(icon::from_name("go-up-symbolic"))
+                    .on_press_maybe((shown && index > 0).then_some(Message::MovePopupSectionUp(section))),
+                button::icon(icon::from_name("go-down-symbolic"))
+                    .on_press_maybe((shown && index + 1 < shown_sections).then_some(Message::MovePopupSectionDown(section))),
+                toggler(shown).on_toggle(move |visible| Message::TogglePopupSection(section, visible)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+            popup_sections_section = popup_sections_section.add(settings::item(Self::popup_section_name(section), controls));
+        }
+
+        let colors_section = settings::section()
+            .title(fl!("settings-colors"))
+            .add(settings::item(
+                format!("{}: {}%", fl!("session-warning-threshold"), self.config.session_warning_threshold),
+                slider(0..=100, self.config.session_warning_threshold, |value| {
+                    Message::SetWarningThreshold(UsageWindow::Session, value)
+                })
+                .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {}%", fl!("session-critical-threshold"), self.config.session_critical_threshold),
+                slider(0..=100, self.config.session_critical_threshold, |value| {
+                    Message::SetCriticalThreshold(UsageWindow::Session, value)
+                })
+                .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {}%", fl!("weekly-warning-threshold"), self.config.weekly_warning_threshold),
+                slider(0..=100, self.config.weekly_warning_threshold, |value| {
+                    Message::SetWarningThreshold(UsageWindow::Weekly, value)
+                })
+                .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {}%", fl!("weekly-critical-threshold"), self.config.weekly_critical_threshold),
+                slider(0..=100, self.config.weekly_critical_threshold, |value| {
+                    Message::SetCriticalThreshold(UsageWindow::Weekly, value)
+                })
+                .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {}%", fl!("opus-warning-threshold"), self.config.opus_warning_threshold),
+                slider(0..=100, self.config.opus_warning_threshold, Message::SetOpusWarningThreshold)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {}%", fl!("opus-critical-threshold"), self.config.opus_critical_threshold),
+                slider(0..=100, self.config.opus_critical_threshold, Message::SetOpusCriticalThreshold)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("color-mode"),
+                button::standard(color_mode_text).on_press(Message::CycleColorMode),
+            ))
+            .add(self.color_setting_row(ColorSlot::Low, fl!("color-low")))
+            .add(self.color_setting_row(ColorSlot::Medium, fl!("color-medium")))
+            .add(self.color_setting_row(ColorSlot::High, fl!("color-high")))
+            .add(self.color_setting_row(ColorSlot::Mascot, fl!("color-mascot")));
+
+        let click_section = settings::section()
+            .title(fl!("settings-mouse"))
+            .add(self.click_action_row(ClickButton::Left, fl!("left-click")))
+            .add(self.click_action_row(ClickButton::Middle, fl!("middle-click")))
+            .add(self.click_action_row(ClickButton::Right, fl!("right-click")))
+            .add(settings::item(
+                fl!("scroll-cycles-display"),
+                toggler(self.config.scroll_cycles_display).on_toggle(Message::ToggleScrollCyclesDisplay),
+            ));
+
+        let apps_section = settings::section()
+            .title(fl!("settings-apps"))
+            .add(settings::item(
+                fl!("file-manager-command"),
+                text_input("xdg-open", self.config.file_manager_command.as_deref().unwrap_or_default())
+                    .on_input(Message::SetFileManagerCommand)
+                    .width(Length::Fixed(200.0)),
+            ));
+
+        let hooks_section = settings::section()
+            .title(fl!("settings-hooks"))
+            .add(self.hook_setting_row(HookSlot::Warning, fl!("hook-on-warning")))
+            .add(self.hook_setting_row(HookSlot::Critical, fl!("hook-on-critical")))
+            .add(self.hook_setting_row(HookSlot::Reset, fl!("hook-on-reset")));
+
+        let time_section = settings::section()
+            .title(fl!("settings-time"))
+            .add(settings::item(
+                fl!("use-24-hour-clock"),
+                toggler(self.config.use_24_hour_clock).on_toggle(Message::Toggle24HourClock),
+            ))
+            .add(settings::item(
+                fl!("reset-time-format"),
+                button::standard(reset_time_format_text).on_press(Message::CycleResetTimeFormat),
+            ));
+
+        let numbers_section = settings::section()
+            .title(fl!("settings-numbers"))
+            .add(settings::item(
+                format!("{}: {}", fl!("cost-precision"), localize::format_currency(1234.5678)),
+                slider(0..=4, self.config.cost_precision, Message::SetCostPrecision).width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("group-digits"),
+                toggler(self.config.group_digits).on_toggle(Message::ToggleGroupDigits),
+            ));
+
+        let notifications_section = settings::section()
+            .title(fl!("settings-notifications"))
+            .add(settings::item(
+                fl!("show-service-status"),
+                toggler(self.config.show_service_status).on_toggle(Message::ToggleServiceStatus),
+            ))
+            .add(settings::item(
+                fl!("notify-incidents"),
+                toggler(self.config.notify_service_incidents).on_toggle(Message::ToggleIncidentNotifications),
+            ))
+            .add(settings::item(
+                fl!("notify-plan-changes"),
+                toggler(self.config.notify_plan_changes).on_toggle(Message::TogglePlanChangeNotifications),
+            ))
+            .add(settings::item(
+                fl!("notify-thresholds"),
+                toggler(self.config.notify_thresholds).on_toggle(Message::ToggleThresholdNotifications),
+            ))
+            .add(settings::item(
+                fl!("notify-weekly-forecast"),
+                toggler(self.config.notify_weekly_forecast).on_toggle(Message::ToggleForecastNotifications),
+            ))
+            .add(settings::item(
+                fl!("notify-session-changes"),
+                toggler(self.config.notify_session_changes).on_toggle(Message::ToggleSessionChangeNotifications),
+            ))
+            .add(settings::item(
+                fl!("notify-idle-sessions"),
+                toggler(self.config.notify_idle_sessions).on_toggle(Message::ToggleIdleNotifications),
+            ))
+            .add(settings::item(
+                format!("{}: {} min", fl!("idle-session-after"), self.config.idle_session_minutes),
+                slider(5..=240, self.config.idle_session_minutes, Message::SetIdleSessionMinutes)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {} h", fl!("snooze-duration"), self.config.snooze_hours),
+                slider(1..=24, self.config.snooze_hours, Message::SetSnoozeHours)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("quiet-hours"),
+                toggler(self.config.quiet_hours).on_toggle(Message::ToggleQuietHours),
+            ))
+            .add(settings::item(
+                format!("{}: {:02}:00", fl!("quiet-hours-start"), self.config.quiet_hours_start),
+                slider(0..=23, self.config.quiet_hours_start, Message::SetQuietHoursStart)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                format!("{}: {:02}:00", fl!("quiet-hours-end"), self.config.quiet_hours_end),
+                slider(0..=23, self.config.quiet_hours_end, Message::SetQuietHoursEnd)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("weekly-summary"),
+                toggler(self.config.weekly_summary).on_toggle(Message::ToggleWeeklySummary),
+            ))
+            .add(settings::item(
+                fl!("weekly-summary-day"),
+                button::standard(localize::format_weekday_name(self.config.weekly_summary_day))
+                    .on_press(Message::CycleWeeklySummaryDay),
+            ))
+            .add(settings::item(
+                format!("{}: {:02}:00", fl!("weekly-summary-time"), self.config.weekly_summary_hour),
+                slider(0..=23, self.config.weekly_summary_hour, Message::SetWeeklySummaryHour)
+                    .width(Length::Fixed(160.0)),
+            ))
+            .add(settings::item(
+                fl!("check-claude-updates"),
+                toggler(self.config.check_claude_updates).on_toggle(Message::ToggleClaudeUpdateCheck),
+            ))
+            .add(settings::item(
+                fl!("check-applet-updates"),
+                toggler(self.config.check_applet_updates).on_toggle(Message::ToggleAppletUpdateCheck),
+            ));
+
+        let log_level_text = match self.config.log_level {
+            LogLevel::Error => fl!("log-level-error"),
+            LogLevel::Warn => fl!("log-level-warn"),
+            LogLevel::Info => fl!("log-level-info"),
+            LogLevel::Debug => fl!("log-level-debug"),
+            LogLevel::Trace => fl!("log-level-trace"),
+        };
+
+        let log_file_label = match logs::log_file_path() {
+            Some(path) => fl!("log-to-file-path", path = path.display().to_string()),
+            None => fl!("log-to-file"),
+        };
+
+        let diagnostics_section = settings::section()
+            .title(fl!("settings-diagnostics"))
+            .add(settings::item(
+                fl!("show-raw-response"),
+                toggler(self.config.show_raw_response).on_toggle(Message::ToggleRawResponse),
+            ))
+            .add(settings::item(
+                fl!("log-level"),
+                button::standard(log_level_text).on_press(Message::CycleLogLevel),
+            ))
+            .add(settings::item(
+                log_file_label,
+                toggler(self.config.log_to_file).on_toggle(Message::ToggleLogFile),
+            ));
+
+        let reset_control: Element<'_, Message> = if self.confirm_reset {
+            row![
+                button::text(fl!("cancel")).on_press(Message::CancelResetSettings),
+                button::destructive(fl!("reset")).on_press(Message::ConfirmResetSettings),
+            ]
+            .spacing(8)
+            .into()
+        } else {
+            button::standard(fl!("restore-defaults"))
+                .on_press(Message::RequestResetSettings)
+                .into()
+        };
+
+        let reset_label = if self.confirm_reset {
+            fl!("restore-defaults-confirm")
+        } else {
+            fl!("restore-defaults-description")
+        };
+
+        let reset_section = settings::section().add(settings::item(reset_label, reset_control));
+
+        let content = settings::view_column(vec![
+            text::title3(fl!("settings")).into(),
+            data_section.into(),
+            indicator_section.into(),
+            popup_sections_section.into(),
+            colors_section.into(),
+            click_section.into(),
+            time_section.into(),
+            numbers_section.into(),
+            notifications_section.into(),
+            apps_section.into(),
+            hooks_section.into(),
+            diagnostics_section.into(),
+            reset_section.into(),
+        ]);
+
+        scrollable(container(content).padding(16)).into()
+    }
+
+    /// Hover preview: the two usage percentages with their reset countdowns, nothing interactive
+    fn view_hover_preview(&self) -> Element<'_, Message> {
+        let Spacing {
+            space_xxs,
+            space_s,
+            ..
+        } = theme::active().cosmic().spacing;
+
+        let content: Element<'_, Message> = if self.local_stats_only() {
+            text::body(format!("{}: {}", fl!("today"), localize::format_currency(self.local_costs().0))).into()
+        } else if !self.has_credentials {
+            text::body(fl!("not-logged-in")).into()
+        } else {
+            let window_row = |label: String, percent: f32, reset_time: Option<DateTime<Utc>>| {
+                column![
+                    row![text::body(label), horizontal_space(), text::body(self.format_percent(percent))]
+                        .spacing(space_s),
+                    text::caption(self.format_reset_time(reset_time)),
+                ]
+            };
+            column![
+                window_row(fl!("session-usage"), self.session_usage_percent, self.session_reset_time),
+                window_row(fl!("weekly-usage"), self.weekly_usage_percent, self.weekly_reset_time),
+            ]
+            .spacing(space_xxs)
+            .into()
+        };
+
+        self.core
+            .applet
+            .popup_container(container(content).padding([space_xxs, space_s]).width(Length::Fixed(220.0)))
+            .into()
+    }
+
+    /// Fixed popup width from the config
+    fn popup_width(&self) -> Length {
+        Length::Fixed(self.config.popup_width as f32)
+    }
+
+    /// Theme spacing for the popup; the compact density tightens the gaps between rows
+    fn popup_spacing(&self) -> Spacing {
+        let mut spacing = theme::active().cosmic().spacing;
+        if self.config.popup_density == PopupDensity::Compact {
+            spacing.space_xxs = spacing.space_xxxs;
+            spacing.space_s = spacing.space_xs;
+        }
+        spacing
+    }
+
+    /// Padding of popup rows in the compact density
+    fn compact_padding(&self) -> [u16; 2] {
+        let Spacing { space_xxxs, space_s, .. } = theme::active().cosmic().spacing;
+        [space_xxxs, space_s]
+    }
+
+    /// `padded_control` with tighter padding in the compact density
+    fn padded<'a>(
+        &self,
+        content: impl Into<Element<'a, Message>>,
+    ) -> cosmic::widget::Container<'a, Message, cosmic::Theme, cosmic::Renderer> {
+        let control = padded_control(content);
+        match self.config.popup_density {
+            PopupDensity::Comfortable => control,
+            PopupDensity::Compact => control.padding(self.compact_padding()),
+        }
+    }
+
+    /// `menu_button` with tighter padding in the compact density
+    fn menu_row<'a>(&self, content: impl Into<Element<'a, Message>>) -> cosmic::widget::Button<'a, Message> {
+        let row = menu_button(content);
+        match self.config.popup_density {
+            PopupDensity::Comfortable => row,
+            PopupDensity::Compact => row.padding(self.compact_padding()),
+        }
+    }
+
+    /// Horizontal divider between popup sections
+    fn popup_divider(&self) -> cosmic::widget::Container<'_, Message, cosmic::Theme, cosmic::Renderer> {
+        let Spacing { space_xxs, space_s, .. } = self.popup_spacing();
+        padded_control(divider::horizontal::default()).padding([space_xxs, space_s])
+    }
+
+    /// Body text, shrunk to caption size in the compact density
+    fn body_text<'a>(&self, label: impl Into<Cow<'a, str>>) -> cosmic::widget::Text<'a, cosmic::Theme, cosmic::Renderer> {
+        match self.config.popup_density {
+            PopupDensity::Comfortable => text::body(label),
+            PopupDensity::Compact => text::caption(label),
+        }
+    }
+
+    /// Secondary popup page: a back button and title above scrollable content
+    fn view_popup_page<'a>(&'a self, title: String, content: Element<'a, Message>) -> Element<'a, Message> {
+        let Spacing {
+            space_xxs,
+            space_s,
+            ..
+        } = self.popup_spacing();
+
+        let header = row![
+            button::icon(icon::from_name("go-previous-symbolic"))
+                .on_press(Message::ShowPage(PopupPage::Usage)),
+            text::heading(title),
+        ]
+        .spacing(space_xxs)
+        .align_y(Alignment::Center)
+        .padding([0, space_s]);
+
+        let body = container(scrollable(content).height(Length::Shrink))
+            .max_height(self.config.popup_max_height as f32);
+
+        self.core
+            .applet
+            .popup_container(column![header, body].padding([8, 0]).width(self.popup_width()))
+            .into()
+    }
+
+    /// Log page: recent warnings and errors captured from tracing
+    fn view_logs_page(&self) -> Element<'_, Message> {
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+
+        let entries = logs::recent();
+        let mut list = column![].spacing(space_xxs);
+        if entries.is_empty() {
+            list = list.push(self.padded(text::caption(fl!("logs-empty"))));
+        }
+        for entry in entries {
+            list = list.push(self.padded(
+                column![
+                    text::caption(format!(
+                        "{} {} {}",
+                        entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                        entry.level,
+                        entry.target
+                    )),
+                    self.body_text(entry.message),
+                ]
+            ));
+        }
+
+        self.view_popup_page(fl!("logs"), list.into())
+    }
+
+    /// Diagnostics page: data file locations, credential state and backend stream health
+    fn view_diagnostics_page(&self) -> Element<'_, Message> {
+        let Spacing {
+            space_xxs,
+            space_s,
+            ..
+        } = self.popup_spacing();
+
+        let mut paths_column = column![self.body_text(fl!("diagnostics-paths"))].spacing(space_xxs);
+        for (label, path, status) in &self.diagnostic_probes {
+            let (location, state) = match path {
+                Some(path) => (path.display().to_string(), Self::path_state(*status)),
+                None => (fl!("unknown"), fl!("path-missing")),
+            };
+            paths_column = paths_column.push(text::caption(format!("{}: {}", label, state)));
+            paths_column = paths_column.push(text::caption(location));
+        }
+
+        let fetch_text = match self.last_api_fetch {
+            Some(time) => match &self.api_error {
+                Some(error) => fl!("last-fetch-failed", time = self.format_timestamp(time), error = error.clone()),
+                None => fl!("last-fetch-ok", time = self.format_timestamp(time)),
+            },
+            None => fl!("last-fetch-never"),
+        };
+        let expiry_text = match self.diagnostic_token_expiry {
+            Some(expiry) if expiry <= Utc::now() => fl!("token-expired", time = self.format_timestamp(expiry)),
+            Some(expiry) => fl!("token-expires", time = self.format_timestamp(expiry)),
+            None => fl!("token-expiry-unknown"),
+        };
+        let mut account_column = column![
+            self.body_text(fl!("diagnostics-account")),
+            text::caption(fetch_text),
+            text::caption(expiry_text),
+        ]
+        .spacing(space_xxs);
+        if let Some((local, api)) = self.session_discrepancy() {
+            account_column = account_column.push(text::caption(fl!(
+                "session-discrepancy",
+                local = format!("{:.0}", local),
+                api = format!("{:.0}", api)
+            )));
+            if api - local >= DISCREPANCY_NOTE_POINTS {
+                account_column = account_column.push(text::caption(fl!("session-discrepancy-note")));
+            }
+        }
+
+        let mut streams_column = column![self.body_text(fl!("diagnostics-streams"))].spacing(space_xxs);
+        for (kind, label) in self.active_streams() {
+            let state = match self.supervisor.last_update(kind) {
+                _ if self.supervisor.is_stalled(kind) => {
+                    fl!("stream-stalled", restarts = self.supervisor.restarts(kind))
+                }
+                Some(time) => fl!("stream-updated", time = self.format_timestamp(time)),
+                None => fl!("stream-waiting"),
+            };
+            streams_column = streams_column.push(text::caption(format!("{}: {}", label, state)));
+        }
+        if let Some(duration) = self.process_scan_duration {
+            streams_column = streams_column.push(text::caption(fl!(
+                "process-scan-duration",
+                ms = localize::format_decimal(duration.as_secs_f64() * 1000.0, 1)
+            )));
+        }
+
+        let copy_label = if self.report_copied {
+            fl!("report-copied")
+        } else {
+            fl!("copy-report")
+        };
+
+        let content = column![
+            self.padded(paths_column),
+            self.popup_divider(),
+            self.padded(account_column),
+            self.popup_divider(),
+            self.padded(streams_column),
+            self.popup_divider(),
+            self.menu_row(self.body_text(copy_label)).on_press(Message::CopyDiagnostics),
+        ];
+
+        self.view_popup_page(fl!("diagnostics"), content.into())
+    }
+
+    /// Plain-text diagnostics for bug reports; never includes tokens, and home paths are shortened to `~`
+    fn diagnostics_report(&self) -> String {
+        let home = dirs::home_dir().map(|h| h.display().to_string());
+        let redact_home = |path: &std::path::Path| {
+            let path = path.display().to_string();
+            match &home {
+                Some(home) if path.starts_with(home.as_str()) => format!("~{}", &path[home.len()..]),
+                _ => path,
+            }
+        };
+        let timestamp = |time: Option<DateTime<Utc>>| {
+            time.map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                .unwrap_or_else(|| "never".to_string())
+        };
+
+        let mut report = String::from("## cosmic-applet-claude diagnostics\n\n");
+        report.push_str(&format!("Applet version: {}\n", env!("CARGO_PKG_VERSION")));
+        report.push_str(&format!(
+            "Claude Code version: {}\n",
+            self.claude_version.installed.as_deref().unwrap_or("unknown")
+        ));
+        report.push_str(&format!("Billing mode: {:?}\n", self.config.billing_mode));
+        report.push_str(&format!(
+            "Log level: {:?}{}\n",
+            self.config.log_level,
+            if self.config.log_to_file { " (also to file)" } else { "" }
+        ));
+        report.push_str(&format!("Logged in: {}\n", self.has_credentials));
+        report.push_str(&format!("Plan: {}\n\n", self.subscription_type));
+
+        report.push_str("### Files\n");
+        for (name, path) in [
+            ("credentials", api::get_credentials_path()),
+            ("stats", stats::get_stats_path()),
+            ("projects", transcripts::projects_dir()),
+            ("desktop config", backend::claude_desktop_dir()),
+            ("desktop logs", backend::claude_desktop_dir().map(|dir| dir.join("logs"))),
+        ] {
+            let line = match path {
+                Some(path) => {
+                    let state = match Self::path_status(&path) {
+                        PathStatus::Ok => "ok",
+                        PathStatus::Missing => "missing",
+                        PathStatus::Unreadable => "not readable",
+                    };
+                    format!("- {}: {} ({})\n", name, redact_home(&path), state)
+                }
+                None => format!("- {}: no home directory\n", name),
+            };
+            report.push_str(&line);
+        }
+
+        report.push_str("\n### Usage API\n");
+        report.push_str(&format!("Last fetch: {}\n", timestamp(self.last_api_fetch)));
+        report.push_str(&format!("Last error: {}", self.api_error.as_deref().unwrap_or("none")));
+        if let Some(kind) = self.api_error_kind {
+            report.push_str(&format!(" ({kind:?})"));
+        }
+        report.push('\n');
+        report.push_str(&format!("Token expiry: {}\n", timestamp(api::token_expiry())));
+        if let Some((local, api)) = self.session_discrepancy() {
+            report.push_str(&format!("Session: local estimate {:.0}%, API {:.0}%\n", local, api));
+        }
+
+        report.push_str("\n### Data sources\n");
+        for (kind, _) in self.active_streams() {
+            let stalled = if self.supervisor.is_stalled(kind) {
+                format!(" (stalled, {} restarts)", self.supervisor.restarts(kind))
+            } else {
+                String::new()
+            };
+            report.push_str(&format!("- {:?}: {}{}\n", kind, timestamp(self.supervisor.last_update(kind)), stalled));
+        }
+        if let Some(duration) = self.process_scan_duration {
+            report.push_str(&format!("Process scan: {:.1} ms\n", duration.as_secs_f64() * 1000.0));
+        }
+
+        let entries = logs::recent();
+        if !entries.is_empty() {
+            report.push_str("\n### Recent warnings and errors\n");
+            for entry in entries.iter().take(20) {
+                report.push_str(&format!(
+                    "{} {} {}: {}\n",
+                    entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                    entry.level,
+                    entry.target,
+                    match &home {
+                        Some(home) => entry.message.replace(home.as_str(), "~"),
+                        None => entry.message.clone(),
+                    }
+                ));
+            }
+        }
+
+        report
+    }
+
+    /// Check the files the applet reads and the token expiry, so the diagnostics page renders without file I/O
+    fn probe_diagnostics(&mut self) {
+        self.diagnostic_probes = [
+            (fl!("path-credentials"), api::get_credentials_path()),
+            (fl!("path-stats"), stats::get_stats_path()),
+            (fl!("path-projects"), transcripts::projects_dir()),
+            (fl!("path-desktop-config"), backend::claude_desktop_dir()),
+            (fl!("path-desktop-logs"), backend::claude_desktop_dir().map(|dir| dir.join("logs"))),
+        ]
+        .into_iter()
+        .map(|(label, path)| {
+            let status = path.as_deref().map_or(PathStatus::Missing, Self::path_status);
+            (label, path, status)
+        })
+        .collect();
+        self.diagnostic_token_expiry = api::token_expiry();
+    }
+
+    /// Whether a path exists and can be read
+    fn path_status(path: &std::path::Path) -> PathStatus {
+        let readable = if path.is_dir() {
+            std::fs::read_dir(path).is_ok()
+        } else {
+            std::fs::File::open(path).is_ok()
+        };
+        if !path.exists() {
+            PathStatus::Missing
+        } else if readable {
+            PathStatus::Ok
+        } else {
+            PathStatus::Unreadable
+        }
+    }
+
+    fn path_state(status: PathStatus) -> String {
+        match status {
+            PathStatus::Ok => fl!("path-ok"),
+            PathStatus::Missing => fl!("path-missing"),
+            PathStatus::Unreadable => fl!("path-unreadable"),
+        }
+    }
+
+    /// Scans of local processes and transcripts, which every instance runs for itself
+    fn local_subscriptions(&self) -> Vec<Subscription<Message>> {
+        vec![
+            backend::data_source()
+                .processes(self.supervisor.generation(StreamKind::Processes))
+                .map(Message::ProcessUpdate),
+            transcripts::transcripts_subscription(
+                self.pricing_generation.wrapping_add(self.supervisor.generation(StreamKind::Transcripts)),
+            )
+            .map(Message::TranscriptUpdate),
+        ]
+    }
+
+    /// Network pollers and history compaction, run by the primary instance only so duplicates never repeat them
+    fn poller_subscriptions(&self) -> Vec<Subscription<Message>> {
+        let mut subscriptions =
+            vec![history::compaction_subscription(self.config.history_retention_days).map(Message::HistoryCompacted)];
+
+        // Automatic mode keeps watching OAuth credentials so a later login switches back
+        if self.config.billing_mode != BillingMode::ApiKey && self.cloud_provider().is_none() {
+            subscriptions.push(
+                backend::data_source()
+                    .usage(
+                        self.poll_interval.subscribe(),
+                        self.refresh_generation.wrapping_add(self.supervisor.generation(StreamKind::UsageApi)),
+                    )
+                    .map(Message::ApiUpdate),
+            );
+        }
+
+        if self.uses_api_key_billing() || self.config.show_organization_usage {
+            subscriptions.push(
+                admin::admin_subscription(
+                    self.config.poll_interval_minutes,
+                    self.refresh_generation.wrapping_add(self.supervisor.generation(StreamKind::OrgUsage)),
+                )
+                .map(Message::OrgUsageUpdate),
+            );
+        }
+
+        if self.config.show_service_status {
+            subscriptions.push(
+                status::status_subscription(self.supervisor.generation(StreamKind::ServiceStatus)).map(Message::StatusUpdate),
+            );
+        }
+
+        if self.config.sync_model_pricing {
+            subscriptions.push(pricing::pricing_sync_subscription().map(|()| Message::PricingSynced));
+        }
+
+        if self.config.check_claude_updates {
+            subscriptions.push(version::version_subscription().map(Message::VersionUpdate));
+        }
+
+        if self.config.check_applet_updates {
+            subscriptions.push(release::release_subscription().map(Message::ReleaseUpdate));
+        }
+
+        subscriptions
+    }
+
+    /// Backend streams the current configuration and instance role subscribe to
+    fn active_streams(&self) -> Vec<(StreamKind, String)> {
+        let primary = self.instance_role == InstanceRole::Primary;
+        let mut streams = vec![
+            (StreamKind::Processes, fl!("stream-processes")),
+            (StreamKind::Stats, fl!("stream-stats")),
+            (StreamKind::Transcripts, fl!("stream-transcripts")),
+        ];
+        // A duplicate mirrors the primary's usage updates instead
+        if primary && self.config.billing_mode != BillingMode::ApiKey && self.cloud_provider().is_none() {
+            streams.push((StreamKind::UsageApi, fl!("stream-usage-api")));
+        }
+        if primary && (self.uses_api_key_billing() || self.config.show_organization_usage) {
+            streams.push((StreamKind::OrgUsage, fl!("stream-org-usage")));
+        }
+        if primary && self.config.show_service_status {
+            streams.push((StreamKind::ServiceStatus, fl!("stream-service-status")));
+        }
+        streams
+    }
+
+    /// Collapsible view of the last usage API response, to tell parsing bugs from API changes
+    fn view_raw_response_section(&self) -> Option<Element<'_, Message>> {
+        if !self.config.show_raw_response {
+            return None;
+        }
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+
+        let arrow = if self.raw_response_expanded { "▾" } else { "▸" };
+        let header = self.menu_row(self.body_text(format!("{} {}", arrow, fl!("raw-response"))))
+            .on_press(Message::ToggleRawResponseExpanded);
+
+        let mut raw_column = column![header].spacing(space_xxs);
+        if self.raw_response_expanded {
+            let body = match &self.raw_api_response {
+                Some(raw) => self.padded(text::monotext(raw.as_str())),
+                None => self.padded(text::caption(fl!("raw-response-empty"))),
+            };
+            raw_column = raw_column.push(body);
+        }
+        Some(raw_column.into())
+    }
+
+    /// Explanation of the last failed fetch and a suggested action for its category
+    fn view_error_section(&self, error: &str) -> Element<'_, Message> {
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+        let (title, hint, action) = match self.api_error_kind {
+            Some(api::ErrorKind::Offline) => (
+                fl!("error-offline"),
+                fl!("error-offline-hint"),
+                Some((fl!("retry"), Message::Refresh)),
+            ),
+            Some(api::ErrorKind::AuthExpired) => (
+                fl!("error-auth"),
+                fl!("error-auth-hint"),
+                Some((fl!("log-in-again"), Message::StartLogin)),
+            ),
+            Some(api::ErrorKind::RateLimited) => (fl!("error-rate-limited"), fl!("error-rate-limited-hint"), None),
+            Some(api::ErrorKind::ServerError) => (
+                fl!("error-server"),
+                fl!("error-server-hint"),
+                Some((fl!("retry"), Message::Refresh)),
+            ),
+            Some(api::ErrorKind::ParseError) => (
+                fl!("error-parse"),
+                fl!("error-parse-hint"),
+                Some((fl!("view-diagnostics"), Message::ShowPage(PopupPage::Diagnostics))),
+            ),
+            Some(api::ErrorKind::UntrustedBaseUrl) => (
+                fl!("error-untrusted-base-url", url = api::base_url()),
+                fl!("error-untrusted-base-url-hint"),
+                Some((fl!("settings"), Message::OpenSettingsWindow)),
+            ),
+            Some(api::ErrorKind::Other) | None => (fl!("api-error"), error.to_string(), None),
+        };
+
+        let mut error_column = column![self.body_text(title), text::caption(hint)].spacing(space_xxs);
+        if let Some((label, message)) = action {
+            error_column = error_column.push(button::standard(label).on_press(message));
+        }
+        self.padded(error_column).into()
+    }
+
+    /// Organization tab: month-to-date tokens and cost from the Admin API
+    fn view_organization_section(&self) -> Element<'_, Message> {
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+        let org = &self.org_usage;
+
+        if !org.has_admin_key {
+            return self.padded(text::caption(fl!("org-no-admin-key"))).into();
+        }
+
+        if let Some(error) = &org.last_error {
+            return self.padded(text::caption(format!("{}: {}", fl!("api-error"), error))).into();
+        }
+
+        let period = org
+            .period_start
+            .map(|start| fl!("org-since", date = localize::format_date(&start)))
+            .unwrap_or_else(|| fl!("unknown"));
+
+        self.padded(
+            column![
+                self.body_text(fl!("organization-usage")),
+                text::caption(period),
+                row![
+                    text::caption(fl!("org-input-tokens")),
+                    horizontal_space(),
+                    text::caption(Self::format_tokens(org.input_tokens)),
+                ],
+                row![
+                    text::caption(fl!("org-output-tokens")),
+                    horizontal_space(),
+                    text::caption(Self::format_tokens(org.output_tokens)),
+                ],
+                row![
+                    text::caption(fl!("org-cache-tokens")),
+                    horizontal_space(),
+                    text::caption(Self::format_tokens(org.cache_read_tokens + org.cache_creation_tokens)),
+                ],
+                row![
+                    text::caption(fl!("cost")),
+                    horizontal_space(),
+                    text::caption(localize::format_currency(org.cost_usd)),
+                ],
+            ]
             .spacing(space_xxs)
-        );
+        )
+        .into()
+    }
+
+    /// Today's activity from the stats cache, plus token and cache figures from transcripts
+    fn view_today_section(&self) -> Element<'_, Message> {
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+
+        let mut today_column = column![
+            row![
+                self.body_text(fl!("today")),
+                horizontal_space(),
+                text::caption(fl!("streak-days", count = self.streak_days)),
+            ]
+            .align_y(Alignment::Center),
+            text::caption(format!(
+                "{} · {}",
+                Self::format_messages(self.today_messages),
+                fl!("sessions", count = self.today_sessions)
+            )),
+        ]
+        .spacing(space_xxs);
+
+        let active = self.session_tracker.active_today();
+        if !active.is_zero() {
+            today_column = today_column.push(row![
+                text::caption(fl!("active-time-today")),
+                horizontal_space(),
+                text::caption(Self::format_span(active)),
+            ]);
+        }
+
+        let tokens = &self.transcript_costs.today_tokens;
+        if self.transcript_costs.available {
+            today_column = today_column.push(row![
+                text::caption(fl!("tokens-today")),
+                horizontal_space(),
+                text::caption(fl!(
+                    "tokens-in-out",
+                    input = Self::format_tokens(tokens.input + tokens.cache_write + tokens.cache_read),
+                    output = Self::format_tokens(tokens.output)
+                )),
+            ]);
+        }
+
+        if let Some(ratio) = tokens.cache_hit_ratio() {
+            today_column = today_column.push(row![
+                text::caption(fl!("cache-efficiency")),
+                horizontal_space(),
+                text::caption(fl!(
+                    "cache-efficiency-value",
+                    percent = format!("{:.0}", ratio * 100.0),
+                    cached = Self::format_tokens(tokens.cache_read),
+                    fresh = Self::format_tokens(tokens.input + tokens.cache_write)
+                )),
+            ]);
+        }
+
+        let burn = self.transcript_costs.burn_rate;
+        if burn.tokens_per_minute > 0.0 || burn.previous_tokens_per_minute > 0.0 {
+            // A quarter up or down counts as a trend; anything closer reads as steady
+            let trend = if burn.tokens_per_minute > burn.previous_tokens_per_minute * 1.25 {
+                "↑"
+            } else if burn.tokens_per_minute < burn.previous_tokens_per_minute * 0.75 {
+                "↓"
+            } else {
+                "→"
+            };
+            today_column = today_column.push(row![
+                text::caption(fl!("burn-rate")),
+                horizontal_space(),
+                text::caption(fl!(
+                    "burn-rate-value",
+                    tokens = Self::format_tokens(burn.tokens_per_minute.round() as u64),
+                    trend = trend
+                )),
+            ]);
+        }
+
+        self.padded(today_column).into()
+    }
+
+    /// Session estimate from local transcripts, replacing the session bar while logged out or the usage
+    /// fetch fails. The muted bar and "estimated" title keep it apart from real API numbers.
+    fn view_estimated_session_section(&self) -> Option<Element<'_, Message>> {
+        // Offline with earlier data, the last known session usage stays in place
+        if self.has_credentials && (self.api_error.is_none() || (self.is_offline() && self.has_usage_data)) {
+            return None;
+        }
+        let block = self.transcript_costs.current_block?;
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+
+        let share = match block.estimated_percent {
+            Some(percent) => fl!("estimated-share", percent = format!("{:.0}", percent)),
+            None => fl!("estimated-no-baseline"),
+        };
+        Some(
+            self.padded(
+                column![
+                    self.body_text(fl!("session-estimated")),
+                    Self::tinted_bar(block.estimated_percent.unwrap_or(0.0), self.theme_colors().inactive),
+                    row![
+                        text::caption(share),
+                        horizontal_space(),
+                        text::caption(self.format_reset_time(Some(block.end))),
+                    ],
+                    text::caption(fl!(
+                        "estimated-block-totals",
+                        tokens = Self::format_tokens(block.tokens),
+                        cost = localize::format_currency(block.cost_usd)
+                    )),
+                ]
+                .spacing(space_xxs),
+            )
+            .into(),
+        )
+    }
+
+    /// Local session estimate and the API's session utilization, when both are available
+    fn session_discrepancy(&self) -> Option<(f32, f32)> {
+        if !self.has_credentials || self.api_error.is_some() {
+            return None;
+        }
+        let local = self.transcript_costs.current_block?.estimated_percent?;
+        Some((local, self.session_usage_percent))
+    }
+
+    /// Section for a usage window beyond the session and weekly ones
+    fn view_usage_window(&self, window: &api::NamedWindow) -> Element<'_, Message> {
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+        self.padded(
+            column![
+                self.body_text(Self::window_label(&window.name)),
+                self.level_bar(
+                    self.display_percent(window.utilization),
+                    self.named_window_color(&window.name, window.utilization),
+                ),
+                row![
+                    text::caption(self.format_percent(window.utilization)),
+                    horizontal_space(),
+                    text::caption(self.format_reset_time(window.resets_at)),
+                ],
+            ]
+            .spacing(space_xxs),
+        )
+        .into()
+    }
+
+    /// Display name for an API window key; unknown keys are humanized ("seven_day_foo" -> "Seven day foo")
+    fn window_label(name: &str) -> String {
+        match name {
+            "seven_day_opus" => fl!("window-weekly-opus"),
+            "seven_day_sonnet" => fl!("window-weekly-sonnet"),
+            _ => {
+                let words = name.replace('_', " ");
+                let mut chars = words.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        }
+    }
+
+    /// Share of today's tokens and cost per model family, as a stacked bar with a legend
+    fn view_model_mix_section(&self) -> Option<Element<'_, Message>> {
+        let models = &self.transcript_costs.today_models;
+        let total_tokens: u64 = models.values().map(|m| m.tokens).sum();
+        if total_tokens == 0 {
+            return None;
+        }
+        let total_cost: f64 = models.values().map(|m| m.cost_usd).sum();
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+
+        let segments: Vec<(f32, Color)> = models
+            .iter()
+            .map(|(family, totals)| (totals.tokens as f32 / total_tokens as f32, Self::model_family_color(*family)))
+            .collect();
+
+        let mut mix_column = column![
+            self.body_text(fl!("model-mix")),
+            Self::create_stacked_bar(&segments),
+        ]
+        .spacing(space_xxs);
+
+        for (family, totals) in models {
+            let name = match family {
+                pricing::ModelFamily::Opus => fl!("model-opus"),
+                pricing::ModelFamily::Sonnet => fl!("model-sonnet"),
+                pricing::ModelFamily::Haiku => fl!("model-haiku"),
+                pricing::ModelFamily::Other => fl!("model-other"),
+            };
+            let cost_share = if total_cost > 0.0 { totals.cost_usd / total_cost * 100.0 } else { 0.0 };
+            mix_column = mix_column.push(
+                row![
+                    self.create_color_dot(Self::model_family_color(*family), 8.0),
+                    text::caption(name),
+                    horizontal_space(),
+                    text::caption(fl!(
+                        "model-mix-value",
+                        tokens = format!("{:.0}", totals.tokens as f64 / total_tokens as f64 * 100.0),
+                        cost = format!("{:.0}", cost_share),
+                        amount = localize::format_currency(totals.cost_usd)
+                    )),
+                ]
+                .spacing(space_xxs)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        Some(self.padded(mix_column).into())
+    }
+
+    /// Most expensive projects of the last 7 days; each row opens the project directory
+    fn view_top_projects_section(&self) -> Option<Element<'_, Message>> {
+        let projects = &self.transcript_costs.top_projects;
+        if projects.is_empty() {
+            return None;
+        }
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+
+        let mut projects_column = column![self.padded(self.body_text(fl!("top-projects")))];
+        for project in projects {
+            let entry = row![
+                column![
+                    text::caption(project.name.clone()),
+                    text::caption(Self::format_messages(project.messages)),
+                ],
+                horizontal_space(),
+                text::caption(localize::format_currency(project.cost_usd)),
+            ]
+            .spacing(space_xxs)
+            .align_y(Alignment::Center);
+
+            projects_column = projects_column
+                .push(self.menu_row(entry).on_press_maybe(project.path.clone().map(Message::OpenProject)));
+        }
+
+        Some(projects_column.into())
+    }
+
+    /// Legend color for a model family
+    fn model_family_color(family: pricing::ModelFamily) -> Color {
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        match family {
+            pricing::ModelFamily::Opus => COLOR_CLAUDE,
+            pricing::ModelFamily::Sonnet => cosmic.accent_color().into(),
+            pricing::ModelFamily::Haiku => cosmic.success_color().into(),
+            pricing::ModelFamily::Other => cosmic.palette.neutral_6.into(),
+        }
+    }
+
+    /// Local cost totals for today, the last 7 days and this month
+    fn view_cost_section(&self) -> Element<'_, Message> {
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+        let (today, week, month) = self.local_costs();
+
+        let cost_row = |label: String, amount: f64| {
+            row![
+                text::caption(label),
+                horizontal_space(),
+                text::caption(localize::format_currency(amount)),
+            ]
+        };
+
+        self.padded(
+            column![
+                self.body_text(fl!("cost")),
+                cost_row(fl!("today"), today),
+                cost_row(fl!("cost-last-7-days"), week),
+                cost_row(fl!("cost-this-month"), month),
+            ]
+            .spacing(space_xxs),
+        )
+        .into()
+    }
+
+    /// Month-to-date spending against the configured budget, if one is set
+    fn view_budget_section(&self) -> Option<Element<'_, Message>> {
+        let budget = self.config.monthly_budget_usd;
+        if budget <= 0.0 {
+            return None;
+        }
+        let Spacing { space_xxs, .. } = self.popup_spacing();
+        let spent = self.month_to_date_cost();
+
+        Some(
+            self.padded(
+                column![
+                    self.body_text(fl!("monthly-budget")),
+                    progress_bar(0.0..=100.0, (spent / budget * 100.0).min(100.0) as f32)
+                        .width(Length::Fill),
+                    text::caption(fl!(
+                        "budget-spent-of",
+                        spent = localize::format_currency(spent),
+                        budget = localize::format_currency(budget)
+                    )),
+                ]
+                .spacing(space_xxs),
+            )
+            .into(),
+        )
+    }
+
+    /// Settings row cycling the action bound to a mouse button
+    fn click_action_row(&self, button: ClickButton, label: String) -> Element<'_, Message> {
+        let action = match button {
+            ClickButton::Left => self.config.left_click_action,
+            ClickButton::Middle => self.config.middle_click_action,
+            ClickButton::Right => self.config.right_click_action,
+        };
+        let action_text = match action {
+            ClickAction::None => fl!("click-action-none"),
+            ClickAction::TogglePopup => fl!("click-action-toggle-popup"),
+            ClickAction::OpenTerminal => fl!("click-action-open-terminal"),
+            ClickAction::Refresh => fl!("click-action-refresh"),
+            ClickAction::CycleIconDisplay => fl!("click-action-cycle-display"),
+        };
+
+        settings::item(
+            label,
+            button::standard(action_text).on_press(Message::CycleClickAction(button)),
+        )
+        .into()
+    }
 
-        // Status section (process count)
-        let status_text = if self.process_count > 0 {
-            fl!("sessions-running", count = self.process_count)
-        } else {
-            fl!("no-sessions")
+    /// Settings row with the shell command run for a usage event
+    fn hook_setting_row(&self, slot: HookSlot, label: String) -> Element<'_, Message> {
+        let command = match slot {
+            HookSlot::Warning => &self.config.hook_on_warning,
+            HookSlot::Critical => &self.config.hook_on_critical,
+            HookSlot::Reset => &self.config.hook_on_reset,
         };
 
-        let status_section = padded_control(
-            column![
-                text::body(fl!("status")),
-                text::caption(format!("● {}", status_text)),
+        settings::item(
+            label,
+            text_input(fl!("hook-placeholder"), command.as_deref().unwrap_or_default())
+                .on_input(move |value| Message::SetHookCommand(slot, value))
+                .width(Length::Fixed(200.0)),
+        )
+        .into()
+    }
+
+    /// Settings row with a hex input and a preview of the effective color
+    fn color_setting_row(&self, slot: ColorSlot, label: String) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        let (index, effective) = match slot {
+            ColorSlot::Low => (0, colors.low),
+            ColorSlot::Medium => (1, colors.medium),
+            ColorSlot::High => (2, colors.high),
+            ColorSlot::Mascot => (3, colors.mascot),
+        };
+
+        settings::item(
+            label,
+            row![
+                self.create_color_dot(effective, 12.0),
+                text_input(fl!("color-default"), &self.color_inputs[index])
+                    .on_input(move |value| Message::SetCustomColor(slot, value))
+                    .width(Length::Fixed(90.0)),
             ]
-            .spacing(space_xxs)
-        );
+            .spacing(4)
+            .align_y(Alignment::Center),
+        )
+        .into()
+    }
 
-        // Error display if any
-        let error_section = self.api_error.as_ref().map(|error| padded_control(
-            text::caption(format!("{}: {}", fl!("api-error"), error))
-        ));
+    /// Message count with plural form and digit grouping, e.g. "1,204 messages"
+    fn format_messages(count: u32) -> String {
+        fl!("messages", count = count, formatted = localize::format_count(count as u64))
+    }
 
-        // Settings section (collapsible)
-        let settings_header = padded_control(
-            mouse_area(
-                row![
-                    text::body(fl!("settings")),
-                    horizontal_space(),
-                    text::body(if self.settings_expanded { "▼" } else { "▶" }),
-                ]
-                .align_y(Alignment::Center)
-            )
-            .on_press(Message::ToggleSettings)
-        );
+    /// Format a token count compactly (e.g. 1.2M, 34.5k)
+    fn format_tokens(tokens: u64) -> String {
+        if tokens >= 1_000_000 {
+            format!("{}M", localize::format_decimal(tokens as f64 / 1_000_000.0, 1))
+        } else if tokens >= 1_000 {
+            format!("{}k", localize::format_decimal(tokens as f64 / 1_000.0, 1))
+        } else {
+            tokens.to_string()
+        }
+    }
 
-        let icon_display_text = match self.config.icon_display {
-            IconDisplay::Both => fl!("icon-display-both"),
-            IconDisplay::Session => fl!("icon-display-session"),
-            IconDisplay::Weekly => fl!("icon-display-weekly"),
+    /// Reset time in the configured format: time left, local clock time or both
+    fn format_reset_time(&self, reset_time: Option<DateTime<Utc>>) -> String {
+        match reset_time {
+            Some(time) => {
+                let now = Utc::now();
+                let duration = time.signed_duration_since(now);
+
+                if duration.num_seconds() <= 0 {
+                    return fl!("resetting");
+                }
+
+                let days = duration.num_days();
+                let hours = duration.num_hours();
+                let minutes = duration.num_minutes() % 60;
+                let relative = if days > 0 {
+                    fl!("resets-in-days", days = days, hours = hours % 24)
+                } else if hours > 0 {
+                    fl!("resets-in-hours", hours = hours, minutes = minutes)
+                } else {
+                    fl!("resets-in-minutes", minutes = minutes)
+                };
+
+                // Resets on a later day also name the date
+                let local = time.with_timezone(&Local);
+                let clock = if local.date_naive() == Local::now().date_naive() {
+                    self.format_clock(local)
+                } else {
+                    format!("{} {}", localize::format_date(&local), self.format_clock(local))
+                };
+
+                match self.config.reset_time_format {
+                    ResetTimeFormat::Relative => relative,
+                    ResetTimeFormat::Absolute => fl!("resets-at", time = clock),
+                    ResetTimeFormat::Both => fl!("resets-in-at", countdown = relative, time = clock),
+                }
+            }
+            None => fl!("unknown"),
+        }
+    }
+
+    /// Time since the current session window started (its reset time minus five hours)
+    fn format_block_elapsed(&self) -> Option<String> {
+        let start = self.session_reset_time? - transcripts::BLOCK_LENGTH;
+        let elapsed = Utc::now().signed_duration_since(start);
+        if elapsed < chrono::Duration::zero() || elapsed >= transcripts::BLOCK_LENGTH {
+            return None;
+        }
+        let hours = elapsed.num_hours();
+        let minutes = elapsed.num_minutes() % 60;
+        Some(if hours > 0 {
+            fl!("block-elapsed-hours", hours = hours, minutes = minutes)
+        } else {
+            fl!("block-elapsed-minutes", minutes = minutes)
+        })
+    }
+
+    /// When to poll for a window that is about to reset (or just did), unless that poll already happened
+    fn pending_reset_poll(&self) -> Option<DateTime<Utc>> {
+        if !self.has_credentials || self.config.billing_mode == BillingMode::ApiKey {
+            return None;
+        }
+        [self.session_reset_time, self.weekly_reset_time]
+            .into_iter()
+            .flatten()
+            .map(|reset| reset + RESET_POLL_DELAY)
+            .filter(|due| self.last_api_fetch.is_none_or(|fetched| fetched < *due))
+            .filter(|due| self.reset_poll_triggered.is_none_or(|triggered| triggered < *due))
+            .min()
+    }
+
+    /// Poll interval to use right now: shortened near the session limit or a window reset when adaptive polling is on
+    fn effective_poll_interval(&self) -> u32 {
+        let configured = self.config.poll_interval_minutes;
+        if !self.config.adaptive_polling || !self.has_credentials {
+            return configured;
+        }
+
+        let now = Utc::now();
+        let reset_near = [self.session_reset_time, self.weekly_reset_time]
+            .into_iter()
+            .flatten()
+            .any(|reset| reset > now && reset - now <= chrono::Duration::minutes(ADAPTIVE_RESET_WINDOW_MINUTES));
+        let above_warning = self.session_usage_percent >= self.config.session_warning_threshold as f32;
+
+        if reset_near || above_warning {
+            configured.min(ADAPTIVE_POLL_INTERVAL_MINUTES)
+        } else {
+            configured
+        }
+    }
+
+    /// Hand the current poll interval to the running usage API subscription
+    fn publish_poll_interval(&self) {
+        let minutes = self.effective_poll_interval();
+        self.poll_interval.send_if_modified(|current| {
+            let changed = *current != minutes;
+            *current = minutes;
+            changed
+        });
+    }
+
+    /// Format a point in time as local date and clock time
+    fn format_timestamp(&self, time: DateTime<Utc>) -> String {
+        let local = time.with_timezone(&Local);
+        format!("{} {}", localize::format_date(&local), self.format_clock(local))
+    }
+
+    /// Format a local time of day according to the 12/24-hour preference
+    fn format_clock(&self, time: DateTime<Local>) -> String {
+        if self.config.use_24_hour_clock {
+            time.format("%H:%M").to_string()
+        } else {
+            time.format("%-I:%M %p").to_string()
+        }
+    }
+
+    /// Today, last-7-days and month-to-date cost, priced from transcripts when they exist
+    fn local_costs(&self) -> (f64, f64, f64) {
+        let t = &self.transcript_costs;
+        if t.available {
+            (t.today_cost_usd, t.week_cost_usd, t.month_cost_usd)
+        } else {
+            (self.today_cost_usd, self.week_cost_usd, self.month_cost_usd)
+        }
+    }
+
+    /// Month-to-date cost: report API data for API-key billing, local data otherwise
+    fn month_to_date_cost(&self) -> f64 {
+        if self.uses_api_key_billing() {
+            self.org_usage.cost_usd
+        } else {
+            self.local_costs().2
+        }
+    }
+
+    /// Custom color overrides in `color_inputs` order: low, medium, high, mascot
+    fn custom_colors(config: &ClaudeAppletConfig) -> [Option<String>; 4] {
+        [
+            config.custom_low_color.clone(),
+            config.custom_medium_color.clone(),
+            config.custom_high_color.clone(),
+            config.custom_mascot_color.clone(),
+        ]
+    }
+
+    /// Budget input text for a stored budget (empty when disabled)
+    fn budget_text(budget: f64) -> String {
+        if budget > 0.0 {
+            format!("{budget:.2}")
+        } else {
+            String::new()
+        }
+    }
+
+    /// Notify once per step when month-to-date cost reaches 80% and 100% of the budget.
+    /// The alerts re-arm when a new month starts, not when the cost briefly reads lower.
+    fn check_budget_alert(&mut self) {
+        let budget = self.config.monthly_budget_usd;
+        if budget <= 0.0 {
+            return;
+        }
+        let today = Local::now();
+        let month = (today.year(), today.month());
+        if self.budget_alert_month != Some(month) {
+            self.budget_alert_month = Some(month);
+            self.budget_alerted_step = 0;
+        }
+        let spent = self.month_to_date_cost();
+        let percent = spent / budget * 100.0;
+
+        let reached = BUDGET_ALERT_STEPS
+            .iter()
+            .copied()
+            .filter(|step| percent >= *step as f64)
+            .max()
+            .unwrap_or(0);
+
+        if reached > self.budget_alerted_step {
+            let summary = if reached >= 100 {
+                fl!("budget-exceeded-title")
+            } else {
+                fl!("budget-warning-title", percent = reached)
+            };
+            self.notify(
+                summary,
+                fl!(
+                    "budget-spent-of",
+                    spent = localize::format_currency(spent),
+                    budget = localize::format_currency(budget)
+                ),
+            );
+            self.budget_alerted_step = reached;
+        }
+    }
+
+    /// When the weekly limit runs out at the average pace since the window opened.
+    /// `None` without usage data or before any usage in this window.
+    fn weekly_exhaustion_forecast(&self) -> Option<DateTime<Utc>> {
+        let reset = self.weekly_reset_time?;
+        if !self.has_credentials {
+            return None;
+        }
+        Self::exhaustion_forecast(self.weekly_usage_percent, reset, Utc::now())
+    }
+
+    /// When a weekly window with `percent` used and resetting at `reset` runs out, as of `now`
+    fn exhaustion_forecast(percent: f32, reset: DateTime<Utc>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if percent <= 0.0 {
+            return None;
+        }
+        if percent >= 100.0 {
+            return Some(now);
+        }
+
+        let elapsed = now.signed_duration_since(reset.checked_sub_signed(WEEKLY_WINDOW)?).num_seconds();
+        if elapsed <= 0 {
+            return None;
+        }
+        let percent_per_second = percent as f64 / elapsed as f64;
+        let remaining_seconds = (100.0 - percent as f64) / percent_per_second;
+        // A tiny usage rate can put the forecast beyond what chrono represents
+        now.checked_add_signed(chrono::TimeDelta::try_seconds(remaining_seconds as i64)?)
+    }
+
+    /// Forecast line for the weekly section, e.g. "On track to run out Fri ~15:00"
+    fn format_weekly_forecast(&self) -> String {
+        match (self.weekly_exhaustion_forecast(), self.weekly_reset_time) {
+            (Some(forecast), Some(reset)) if forecast < reset => {
+                let local = forecast.with_timezone(&Local);
+                fl!(
+                    "forecast-run-out",
+                    day = localize::format_weekday(&local),
+                    time = self.format_clock(local)
+                )
+            }
+            (Some(_), Some(_)) => fl!("forecast-lasts"),
+            _ => String::new(),
+        }
+    }
+
+    /// Notify once per weekly window when the forecast falls before the reset
+    fn check_forecast_alert(&mut self) {
+        let (Some(forecast), Some(reset)) = (self.weekly_exhaustion_forecast(), self.weekly_reset_time) else {
+            return;
         };
+        if forecast >= reset || self.forecast_notified_for == Some(reset) {
+            return;
+        }
+        self.forecast_notified_for = Some(reset);
 
-        let settings_content: Option<Element<'_, Message>> = if self.settings_expanded {
-            Some(padded_control(
-                column![
-                    row![
-                        text::caption(fl!("icon-display")),
-                        horizontal_space(),
-                        menu_button(text::caption(icon_display_text))
-                            .on_press(Message::CycleIconDisplay),
-                    ]
-                    .align_y(Alignment::Center),
-                    row![
-                        text::caption(fl!("show-mascot")),
-                        horizontal_space(),
-                        toggler(self.config.show_mascot)
-                            .on_toggle(Message::ToggleMascot),
-                    ]
-                    .align_y(Alignment::Center),
-                    row![
-                        text::caption(format!("{}: {}%", fl!("warning-threshold"), self.config.warning_threshold)),
-                        horizontal_space(),
-                        slider(0..=100, self.config.warning_threshold, Message::SetWarningThreshold)
-                            .width(Length::Fixed(120.0)),
-                    ]
-                    .align_y(Alignment::Center),
-                    row![
-                        text::caption(format!("{}: {}%", fl!("critical-threshold"), self.config.critical_threshold)),
-                        horizontal_space(),
-                        slider(0..=100, self.config.critical_threshold, Message::SetCriticalThreshold)
-                            .width(Length::Fixed(120.0)),
-                    ]
-                    .align_y(Alignment::Center),
-                    row![
-                        text::caption(fl!("show-percentage")),
-                        horizontal_space(),
-                        toggler(self.config.show_percentage_text)
-                            .on_toggle(Message::TogglePercentageText),
-                    ]
-                    .align_y(Alignment::Center),
-                    row![
-                        text::caption(format!("{}: {} min", fl!("poll-interval"), self.config.poll_interval_minutes)),
-                        horizontal_space(),
-                        slider(5..=120, self.config.poll_interval_minutes.min(120) as u8, |v| Message::SetPollInterval(v as u32))
-                            .width(Length::Fixed(120.0)),
-                    ]
-                    .align_y(Alignment::Center),
-                ]
-                .spacing(space_xxs)
-            ).into())
+        if self.config.notify_weekly_forecast {
+            let local = forecast.with_timezone(&Local);
+            self.notify(
+                fl!("forecast-notification-title"),
+                fl!(
+                    "forecast-run-out",
+                    day = localize::format_weekday(&local),
+                    time = self.format_clock(local)
+                ),
+            );
+        }
+    }
+
+    /// How long a CLI session's transcripts have gone unwritten, once that passes the idle threshold
+    fn idle_duration(&self, session: &process::CliSession) -> Option<Duration> {
+        let idle = session.last_activity?.elapsed().ok()?;
+        (idle.as_secs() >= self.config.idle_session_minutes as u64 * 60).then_some(idle)
+    }
+
+    /// Notify once per idle stretch of each CLI session; activity in the session re-arms it
+    fn check_idle_sessions(&mut self) {
+        let idle: Vec<(u32, String, u64)> = self
+            .cli_sessions
+            .iter()
+            .filter_map(|session| {
+                let idle = self.idle_duration(session)?;
+                Some((session.pid, session.project_name(), idle.as_secs() / 60))
+            })
+            .collect();
+        self.idle_notified.retain(|pid| idle.iter().any(|(idle_pid, ..)| idle_pid == pid));
+
+        // A session that went idle while notifications were off still notifies once they are turned on
+        if !self.config.notify_idle_sessions {
+            return;
+        }
+        for (pid, project, minutes) in idle {
+            if self.idle_notified.insert(pid) {
+                self.notify(
+                    fl!("idle-notification-title"),
+                    fl!("idle-notification-body", project = project, minutes = minutes),
+                );
+            }
+        }
+    }
+
+    /// Whether the current local time falls within the configured quiet hours (which may span midnight)
+    fn in_quiet_hours(&self) -> bool {
+        if !self.config.quiet_hours {
+            return false;
+        }
+        Self::hour_in_range(Local::now().hour(), self.config.quiet_hours_start, self.config.quiet_hours_end)
+    }
+
+    /// Whether `hour` is in `start..end`, wrapping past midnight when `start > end`; an empty range never matches
+    fn hour_in_range(hour: u32, start: u32, end: u32) -> bool {
+        if start <= end {
+            (start..end).contains(&hour)
         } else {
-            None
+            hour >= start || hour < end
+        }
+    }
+
+    /// Show a notification, or hold it back for the end-of-quiet-hours summary
+    fn notify(&mut self, summary: String, body: String) {
+        if self.instance_role != InstanceRole::Primary || backend::data_source().is_mock() {
+            return;
+        }
+        if self.in_quiet_hours() {
+            self.suppressed_alerts.push(summary);
+        } else {
+            notifications::send(summary, body);
+        }
+    }
+
+    /// Once quiet hours are over, list the notifications that were held back in a single one
+    fn flush_suppressed_alerts(&mut self) {
+        if self.suppressed_alerts.is_empty() || self.in_quiet_hours() {
+            return;
+        }
+        let alerts = std::mem::take(&mut self.suppressed_alerts);
+        notifications::send(fl!("quiet-hours-summary-title", count = alerts.len()), alerts.join("\n"));
+    }
+
+    /// Start of the most recent weekly summary slot at or before `now`
+    fn last_weekly_summary_slot(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let day = self.config.weekly_summary_day;
+        let today = now.date_naive();
+        let days_back = (today.weekday().num_days_from_monday() + 7 - day.num_days_from_monday()) % 7;
+        let slot = |date: NaiveDate| {
+            let time = date.and_hms_opt(self.config.weekly_summary_hour, 0, 0)?;
+            Local.from_local_datetime(&time).earliest()
         };
+        let this_week = slot(today - chrono::Duration::days(days_back as i64))?;
+        if this_week <= now {
+            Some(this_week)
+        } else {
+            slot(this_week.date_naive() - chrono::Duration::days(7))
+        }
+    }
 
-        // Action buttons
-        let actions = column![
-            menu_button(text::body(fl!("open-terminal")))
-                .on_press(Message::OpenTerminal),
-            menu_button(text::body(fl!("open-claude-dir")))
-                .on_press(Message::OpenSettings),
-        ];
+    /// Build the weekly summary from the history store once the configured slot has passed.
+    /// A slot missed by more than an hour (the applet was not running) is skipped rather than sent late.
+    fn check_weekly_summary(&mut self) -> app::Task<Message> {
+        let now = Local::now();
+        let Some(slot) = self.last_weekly_summary_slot(now) else {
+            return Task::none();
+        };
+        let slot_day = slot.date_naive();
+        if self.config.weekly_summary_sent_for == Some(slot_day) {
+            return Task::none();
+        }
+        self.config.weekly_summary_sent_for = Some(slot_day);
+        self.save_config();
+        if now - slot > chrono::Duration::hours(1) {
+            return Task::none();
+        }
 
-        let mut content_list = column![
-            header,
-            plan_section,
-            padded_control(divider::horizontal::default()).padding([space_xxs, space_s]),
-            session_section,
-            padded_control(divider::horizontal::default()).padding([space_xxs, space_s]),
-            weekly_section,
-            padded_control(divider::horizontal::default()).padding([space_xxs, space_s]),
-            status_section,
-        ]
-        .padding([8, 0]);
+        let daily = self.daily_stats.clone();
+        Task::future(async move {
+            tokio::task::spawn_blocking(move || history::weekly_summary(slot_day, &daily))
+                .await
+                .ok()
+        })
+        .and_then(|summary| cosmic::task::message(Message::WeeklySummaryReady(summary)))
+    }
+
+    /// Current usage as exported to hook commands
+    fn hook_context(&self, window: &'static str) -> HookContext {
+        HookContext {
+            window,
+            session_percent: self.session_usage_percent,
+            weekly_percent: self.weekly_usage_percent,
+        }
+    }
+
+    /// Whether threshold notifications are currently snoozed
+    fn alerts_snoozed(&self) -> bool {
+        self.alerts_snoozed_until.is_some_and(|until| Utc::now() < until)
+    }
+
+    /// Notify when a window climbs into a higher usage level than was last announced.
+    /// An alert re-arms once usage falls `ALERT_HYSTERESIS_PERCENT` below its threshold or the window resets,
+    /// so usage hovering around a threshold does not notify on every poll.
+    fn check_threshold_alerts(&mut self) -> app::Task<Message> {
+        if !self.has_credentials || self.api_error.is_some() {
+            return Task::none();
+        }
+
+        let quiet = self.in_quiet_hours();
+        let session_thresholds = self.thresholds(UsageWindow::Session);
+        let weekly_thresholds = self.thresholds(UsageWindow::Weekly);
+        // Opus has its own thresholds since it is usually the first weekly limit to run out
+        let opus_thresholds = (self.config.opus_warning_threshold, self.config.opus_critical_threshold);
+        let mut tasks = Vec::new();
+
+        let session_context = self.hook_context("session");
+        let weekly_context = self.hook_context("weekly");
+        let opus_context = self.hook_context("opus");
+
+        for ((warning, critical), alerted, percent, name, context) in [
+            (session_thresholds, &mut self.alerted_session_level, self.session_usage_percent, fl!("session-usage"), session_context),
+            (weekly_thresholds, &mut self.alerted_weekly_level, self.weekly_usage_percent, fl!("weekly-usage"), weekly_context),
+            (opus_thresholds, &mut self.alerted_opus_level, self.opus_usage_percent, fl!("window-weekly-opus"), opus_context),
+        ] {
+            let Some(level) = Self::advance_alert_level(alerted, percent, warning, critical) else {
+                continue;
+            };
+
+            // Hooks are automation, so they run regardless of notification settings and snoozing
+            let (hook, event) = match level {
+                UsageLevel::High => (&self.config.hook_on_critical, HookEvent::Critical),
+                _ => (&self.config.hook_on_warning, HookEvent::Warning),
+            };
+            hooks::run(hook.as_deref(), event, context);
+
+            if !self.config.notify_thresholds || self.alerts_snoozed_until.is_some_and(|until| Utc::now() < until) {
+                continue;
+            }
+
+            let summary = match level {
+                UsageLevel::High => fl!("threshold-critical-title", window = name),
+                _ => fl!("threshold-warning-title", window = name),
+            };
+            if quiet {
+                self.suppressed_alerts.push(summary);
+                continue;
+            }
+            let body = fl!("threshold-body", percent = format!("{:.0}", percent));
+            tasks.push(
+                Task::future(notifications::send_with_actions(
+                    summary,
+                    body,
+                    vec![
+                        ("details", fl!("show-details")),
+                        ("open-terminal", fl!("open-terminal")),
+                        ("snooze", fl!("snooze-alerts", hours = self.config.snooze_hours)),
+                    ],
+                ))
+                .and_then(|action| {
+                    cosmic::task::message(match action {
+                        "details" => Message::OpenPopup,
+                        "open-terminal" => Message::OpenTerminal,
+                        _ => Message::SnoozeAlerts,
+                    })
+                }),
+            );
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Whether usage comes from the report API (Console API key) instead of the OAuth endpoint
+    fn uses_api_key_billing(&self) -> bool {
+        match self.config.billing_mode {
+            BillingMode::Subscription => false,
+            BillingMode::ApiKey => true,
+            BillingMode::Auto => self.api_key_configured && !self.has_credentials,
+        }
+    }
 
-        if let Some(error_widget) = error_section {
-            content_list = content_list.push(error_widget);
+    /// Color for the panel status dot, or None when no incident affects Claude
+    fn incident_color(&self) -> Option<Color> {
+        let impact = self.service_status.incidents.iter().map(|i| i.impact).max()?;
+        let colors = self.theme_colors();
+        Some(match impact {
+            status::StatusIndicator::Critical | status::StatusIndicator::Major => colors.high,
+            status::StatusIndicator::Minor => colors.medium,
+            status::StatusIndicator::Maintenance | status::StatusIndicator::None => colors.inactive,
+        })
+    }
+
+    /// The last usage fetch failed, so the displayed numbers may be stale
+    fn has_fetch_error(&self) -> bool {
+        self.has_credentials && self.api_error.is_some() && !self.uses_api_key_billing()
+    }
+
+    /// The last fetch could not reach the API, so last known usage is shown
+    fn is_offline(&self) -> bool {
+        self.has_fetch_error() && self.api_error_kind == Some(api::ErrorKind::Offline)
+    }
+
+    /// Reset time or countdown text, grayed while offline since the window may have reset unseen
+    fn reset_text<'a>(&self, content: cosmic::widget::Text<'a, cosmic::Theme, cosmic::Renderer>) -> Element<'a, Message> {
+        if self.is_offline() {
+            content.class(theme::Text::Color(self.theme_colors().inactive)).into()
+        } else {
+            content.into()
+        }
+    }
+
+    /// Nothing to report: logged out, no claude processes and no login in progress
+    fn is_inactive(&self) -> bool {
+        self.config.hide_when_inactive
+            && !self.has_credentials
+            && self.running_sessions() == 0
+            && !self.token_expired
+            && self.login_started.is_none()
+            && !self.uses_api_key_billing()
+            && !self.local_stats_only()
+    }
+
+    /// No usage windows are available (cloud provider or a gateway without the usage endpoint),
+    /// so local token and cost stats take their place
+    fn local_stats_only(&self) -> bool {
+        self.cloud_provider().is_some() || self.usage_unsupported
+    }
+
+    /// Bedrock or Vertex when Claude Code is configured for one; they have no usage windows
+    fn cloud_provider(&self) -> Option<backend::Provider> {
+        match backend::data_source().provider() {
+            backend::Provider::Anthropic => None,
+            provider => Some(provider),
+        }
+    }
+
+    /// Claude Code sessions, plus Claude Desktop instances when they are included
+    fn running_sessions(&self) -> usize {
+        if self.config.include_claude_desktop {
+            self.process_count + self.desktop_process_count
+        } else {
+            self.process_count
+        }
+    }
+
+    /// Whether the session or weekly window has been fully consumed
+    fn is_locked_out(&self) -> bool {
+        self.has_credentials && (self.session_usage_percent >= 100.0 || self.weekly_usage_percent >= 100.0)
+    }
+
+    /// Time at which the lockout lifts: the latest reset among the exhausted windows
+    fn lockout_reset_time(&self) -> Option<DateTime<Utc>> {
+        Self::latest_exhausted_reset(&[
+            (self.session_usage_percent, self.session_reset_time),
+            (self.weekly_usage_percent, self.weekly_reset_time),
+        ])
+    }
+
+    /// Latest reset among the `(used percent, reset time)` windows that are fully consumed
+    fn latest_exhausted_reset(windows: &[(f32, Option<DateTime<Utc>>)]) -> Option<DateTime<Utc>> {
+        windows
+            .iter()
+            .filter(|(percent, _)| *percent >= 100.0)
+            .filter_map(|(_, reset)| *reset)
+            .max()
+    }
+
+    /// Format the remaining time until `reset_time` as a second-resolution countdown
+    fn format_countdown(reset_time: DateTime<Utc>) -> String {
+        Self::format_countdown_seconds(reset_time.signed_duration_since(Utc::now()).num_seconds())
+    }
+
+    /// Format seconds left as "1h 02m 03s", or "2m 03s" under an hour
+    fn format_countdown_seconds(remaining: i64) -> String {
+        let remaining = remaining.max(0);
+        let hours = remaining / 3600;
+        let minutes = (remaining % 3600) / 60;
+        let seconds = remaining % 60;
+
+        if hours > 0 {
+            format!("{hours}h {minutes:02}m {seconds:02}s")
+        } else {
+            format!("{minutes}m {seconds:02}s")
         }
+    }
+
+    /// Percentage to draw for a usage value: used, or remaining when that mode is on
+    fn display_percent(&self, used_percent: f32) -> f32 {
+        if self.config.show_remaining && self.has_credentials {
+            (100.0 - used_percent).max(0.0)
+        } else {
+            used_percent
+        }
+    }
+
+    /// Screen reader name for a usage window, e.g. "Session usage 43 percent"
+    fn accessible_usage(&self, window: UsageWindow) -> String {
+        let used = match window {
+            UsageWindow::Session => self.session_usage_percent,
+            UsageWindow::Weekly => self.weekly_usage_percent,
+        };
+        let percent = format!("{:.0}", self.display_percent(used));
+        match (window, self.config.show_remaining && self.has_credentials) {
+            (UsageWindow::Session, false) => fl!("a11y-session-usage", percent = percent),
+            (UsageWindow::Weekly, false) => fl!("a11y-weekly-usage", percent = percent),
+            (UsageWindow::Session, true) => fl!("a11y-session-remaining", percent = percent),
+            (UsageWindow::Weekly, true) => fl!("a11y-weekly-remaining", percent = percent),
+        }
+    }
+
+    /// Screen reader name for the panel button, covering the rings it shows
+    fn accessible_summary(&self) -> String {
+        if !self.has_credentials {
+            return if self.token_expired {
+                format!("{}, {}", fl!("claude-code"), fl!("login-expired"))
+            } else {
+                fl!("claude-code")
+            };
+        }
+        let summary = match self.config.icon_display {
+            IconDisplay::Session => self.accessible_usage(UsageWindow::Session),
+            IconDisplay::Weekly => self.accessible_usage(UsageWindow::Weekly),
+            IconDisplay::Both | IconDisplay::Concentric => format!(
+                "{}, {}",
+                self.accessible_usage(UsageWindow::Session),
+                self.accessible_usage(UsageWindow::Weekly)
+            ),
+        };
+        if self.has_fetch_error() {
+            format!("{}, {}", summary, fl!("a11y-stale"))
+        } else {
+            summary
+        }
+    }
+
+    /// Panel text for the configured percentage text metric
+    fn percentage_text(&self) -> String {
+        let percent = match self.config.percentage_text_metric {
+            TextMetric::Auto => match self.config.icon_display {
+                IconDisplay::Weekly => self.weekly_usage_percent,
+                IconDisplay::Session | IconDisplay::Both | IconDisplay::Concentric => self.session_usage_percent,
+            },
+            TextMetric::Session => self.session_usage_percent,
+            TextMetric::Weekly => self.weekly_usage_percent,
+            TextMetric::Highest => self.session_usage_percent.max(self.weekly_usage_percent),
+            TextMetric::Cost => return localize::format_currency(self.local_costs().0),
+        };
+        self.format_percent(percent)
+    }
+
+    /// Percentage text for a usage value, e.g. "63%" or "37% left"
+    fn format_percent(&self, used_percent: f32) -> String {
+        if self.config.show_remaining {
+            fl!("percent-left", percent = format!("{:.0}", (100.0 - used_percent).max(0.0)))
+        } else {
+            format!("{:.0}%", used_percent)
+        }
+    }
+
+    /// Minute-resolution length of a span of time, e.g. "1h 23m" or "23m"
+    fn format_span(duration: Duration) -> String {
+        let minutes = duration.as_secs() / 60;
+        if minutes >= 60 {
+            format!("{}h {:02}m", minutes / 60, minutes % 60)
+        } else {
+            format!("{minutes}m")
+        }
+    }
 
-        content_list = content_list
-            .push(padded_control(divider::horizontal::default()).padding([space_xxs, space_s]))
-            .push(settings_header);
+    /// Compact minute-resolution countdown for the panel, e.g. "2h14m" or "14m"
+    fn format_compact_countdown(reset_time: DateTime<Utc>) -> String {
+        let minutes = reset_time.signed_duration_since(Utc::now()).num_minutes().max(0);
+        if minutes >= 60 {
+            format!("{}h{:02}m", minutes / 60, minutes % 60)
+        } else {
+            format!("{minutes}m")
+        }
+    }
 
-        if let Some(settings_widget) = settings_content {
-            content_list = content_list.push(settings_widget);
+    /// Warning and critical thresholds configured for a window
+    fn thresholds(&self, window: UsageWindow) -> (u8, u8) {
+        match window {
+            UsageWindow::Session => (self.config.session_warning_threshold, self.config.session_critical_threshold),
+            UsageWindow::Weekly => (self.config.weekly_warning_threshold, self.config.weekly_critical_threshold),
         }
+    }
 
-        content_list = content_list
-            .push(padded_control(divider::horizontal::default()).padding([space_xxs, space_s]))
-            .push(actions);
+    /// Get usage level of a window based on percentage and its configured thresholds
+    fn get_usage_level(&self, window: UsageWindow, percent: f32) -> UsageLevel {
+        let (warning, critical) = self.thresholds(window);
+        Self::level_for(percent, warning, critical)
+    }
 
-        self.core.applet.popup_container(content_list).into()
+    /// Usage level of a percentage against explicit warning and critical thresholds
+    /// Record a new reading against the last announced level; returns the level to announce when usage climbed
+    /// past it. The announced level only steps down once usage is clearly below the threshold.
+    fn advance_alert_level(alerted: &mut UsageLevel, percent: f32, warning: u8, critical: u8) -> Option<UsageLevel> {
+        let level = Self::level_for(percent, warning, critical);
+        if level <= *alerted {
+            let rearm_level = Self::level_for(percent + ALERT_HYSTERESIS_PERCENT, warning, critical);
+            *alerted = (*alerted).min(rearm_level);
+            return None;
+        }
+        *alerted = level;
+        Some(level)
     }
 
-    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
-        Some(cosmic::applet::style())
+    fn level_for(percent: f32, warning: u8, critical: u8) -> UsageLevel {
+        if percent <= warning as f32 {
+            UsageLevel::Low
+        } else if percent <= critical as f32 {
+            UsageLevel::Medium
+        } else {
+            UsageLevel::High
+        }
     }
-}
 
-impl ClaudeApplet {
-    fn format_reset_time(&self, reset_time: Option<DateTime<Utc>>) -> String {
-        match reset_time {
-            Some(time) => {
-                let now = Utc::now();
-                let duration = time.signed_duration_since(now);
+    /// Get color for a usage level
+    fn get_level_color(&self, level: UsageLevel) -> Color {
+        let colors = self.theme_colors();
+        match level {
+            UsageLevel::Low => colors.low,
+            UsageLevel::Medium => colors.medium,
+            UsageLevel::High => colors.high,
+        }
+    }
 
-                if duration.num_seconds() <= 0 {
-                    fl!("resetting")
+    /// Ring color for a usage percentage according to the configured color mode
+    fn usage_color(&self, window: UsageWindow, percent: f32) -> Color {
+        match self.config.color_mode {
+            ColorMode::Threshold => self.get_level_color(self.get_usage_level(window, percent)),
+            ColorMode::Gradient => {
+                let colors = self.theme_colors();
+                let t = (percent / 100.0).clamp(0.0, 1.0);
+                if t < 0.5 {
+                    lerp_color(colors.low, colors.medium, t * 2.0)
                 } else {
-                    let hours = duration.num_hours();
-                    let minutes = duration.num_minutes() % 60;
-
-                    if hours > 0 {
-                        fl!("resets-in-hours", hours = hours, minutes = minutes)
-                    } else {
-                        fl!("resets-in-minutes", minutes = minutes)
-                    }
+                    lerp_color(colors.medium, colors.high, (t - 0.5) * 2.0)
                 }
             }
-            None => fl!("unknown"),
+            ColorMode::Symbolic => self.theme_colors().label,
         }
     }
 
-    fn format_reset_date(&self, reset_time: Option<DateTime<Utc>>) -> String {
-        match reset_time {
-            Some(time) => {
-                fl!("resets-on", date = time.format("%b %d").to_string())
+    /// Progress of a ring transition track, complete once the track ran out or was cleared
+    fn track_progress(&self, track: &id::Space) -> f32 {
+        self.timeline
+            .get(&track.clone().into(), 0)
+            .map_or(1.0, |interped| interped.value)
+    }
+
+    /// Session ring percentage and color as currently displayed, mid-transition if animating
+    fn displayed_session(&self) -> (f32, Color) {
+        let target = self.usage_color(UsageWindow::Session, self.session_usage_percent);
+        match self.session_animation {
+            Some(animation) => {
+                animation.sample(self.track_progress(&self.session_ring_track), self.session_usage_percent, target)
             }
-            None => fl!("unknown"),
+            None => (self.session_usage_percent, target),
         }
     }
 
-    /// Get usage level based on percentage and configured thresholds
-    fn get_usage_level(&self, percent: f32) -> UsageLevel {
-        if percent <= self.config.warning_threshold as f32 {
-            UsageLevel::Low
-        } else if percent <= self.config.critical_threshold as f32 {
-            UsageLevel::Medium
-        } else {
-            UsageLevel::High
+    /// Weekly ring percentage and color as currently displayed, mid-transition if animating
+    fn displayed_weekly(&self) -> (f32, Color) {
+        let target = self.usage_color(UsageWindow::Weekly, self.weekly_usage_percent);
+        match self.weekly_animation {
+            Some(animation) => {
+                animation.sample(self.track_progress(&self.weekly_ring_track), self.weekly_usage_percent, target)
+            }
+            None => (self.weekly_usage_percent, target),
         }
     }
 
-    /// Get color for a usage level
-    fn get_level_color(&self, level: UsageLevel) -> Color {
-        match level {
-            UsageLevel::Low => COLOR_LOW,
-            UsageLevel::Medium => COLOR_MEDIUM,
-            UsageLevel::High => COLOR_HIGH,
+    /// Whether the critical-usage pulse should currently run
+    fn is_pulsing(&self) -> bool {
+        self.config.pulse_when_critical
+            && !self.reduced_motion
+            && self.has_credentials
+            && (self.get_usage_level(UsageWindow::Session, self.session_usage_percent) == UsageLevel::High
+                || self.get_usage_level(UsageWindow::Weekly, self.weekly_usage_percent) == UsageLevel::High)
+    }
+
+    /// Position of a looping ambient track within its cycle, from 0 to 1
+    fn cycle_phase(&self, track: &id::Space) -> f32 {
+        self.ambient
+            .get(&track.clone().into(), 0)
+            .map_or(0.0, |interped| interped.value)
+    }
+
+    /// Apply the breathing opacity to a ring color when its window is critical
+    fn pulse_color(&self, color: Color, window: UsageWindow, percent: f32) -> Color {
+        if !self.is_pulsing() || self.get_usage_level(window, percent) != UsageLevel::High {
+            return color;
         }
+        let phase = self.cycle_phase(&self.pulse_track);
+        // Breathe between 55% and 100% opacity
+        let alpha = 0.775 + 0.225 * (phase * 2.0 * std::f32::consts::PI).cos();
+        Color { a: color.a * alpha, ..color }
     }
 
-    /// Generate SVG markup for a circular progress ring
-    fn generate_progress_svg(percent: f32, color: Color, label: &str) -> String {
-        let progress = (percent / 100.0).clamp(0.0, 1.0);
-        let radius = 10.0;
-        let circumference = 2.0 * PI * radius;
-        let dash_offset = circumference * (1.0 - progress);
+    /// Usage level to encode in the ring shape, only in symbolic mode with live data
+    fn symbolic_level(&self, window: UsageWindow, percent: f32) -> Option<UsageLevel> {
+        (self.config.color_mode == ColorMode::Symbolic && self.has_credentials)
+            .then(|| self.get_usage_level(window, percent))
+    }
 
-        // Convert Color to hex string
-        let color_hex = format!(
-            "#{:02x}{:02x}{:02x}",
-            (color.r * 255.0) as u8,
-            (color.g * 255.0) as u8,
-            (color.b * 255.0) as u8
-        );
+    /// Theme colors for the current render, dropping cached SVGs when the theme or overrides changed
+    fn theme_colors(&self) -> ThemeColors {
+        let colors = ThemeColors::current(&self.config);
+        let mut cache = self.svg_cache.borrow_mut();
+        let key = (colors, self.config.color_mode);
+        if cache.theme_colors != Some(key) {
+            *cache = SvgCacheInner {
+                theme_colors: Some(key),
+                ..Default::default()
+            };
+        }
+        colors
+    }
 
-        let track_color = "#4d4d4d";
+    /// Mascot pose for the current state: drooping at critical usage, wiggling while sessions run
+    /// and breathing otherwise. Reduced motion keeps the drooping pose but stops the animations.
+    fn mascot_pose(&self) -> MascotPose {
+        if !self.config.animate_mascot {
+            return MascotPose::Rest;
+        }
+        if self.has_credentials
+            && (self.get_usage_level(UsageWindow::Session, self.session_usage_percent) == UsageLevel::High
+                || self.get_usage_level(UsageWindow::Weekly, self.weekly_usage_percent) == UsageLevel::High)
+        {
+            return MascotPose::Droop;
+        }
+        if self.reduced_motion {
+            return MascotPose::Rest;
+        }
+        let frame = |track: &id::Space, frames: u8| ((self.cycle_phase(track) * frames as f32) as u8).min(frames - 1);
+        if self.running_sessions() > 0 {
+            MascotPose::Wiggle(frame(&self.wiggle_track, WIGGLE_FRAMES))
+        } else {
+            MascotPose::Breathe(frame(&self.breathe_track, BREATHE_FRAMES))
+        }
+    }
 
-        format!(
-            r##"<svg viewBox="0 0 24 24" xmlns="http://www.w3.org/2000/svg">
-                <circle cx="12" cy="12" r="{radius}" fill="none" stroke="{track_color}" stroke-width="3"/>
-                <circle cx="12" cy="12" r="{radius}" fill="none" stroke="{color_hex}" stroke-width="3"
-                    stroke-dasharray="{circumference}" stroke-dashoffset="{dash_offset}"
-                    stroke-linecap="round" transform="rotate(-90 12 12)"/>
-                <text x="12" y="16" text-anchor="middle" fill="white" font-size="10">{label}</text>
-            </svg>"##
-        )
+    /// Time between mascot animation frames, while the panel shows an animated pose
+    fn mascot_frame_interval(&self) -> Option<Duration> {
+        if !self.config.show_mascot || self.custom_mascot_svg.is_some() {
+            return None;
+        }
+        match self.mascot_pose() {
+            MascotPose::Breathe(_) => Some(BREATHE_PERIOD / BREATHE_FRAMES as u32),
+            MascotPose::Wiggle(_) => Some(WIGGLE_PERIOD / WIGGLE_FRAMES as u32),
+            MascotPose::Rest | MascotPose::Droop => None,
+        }
     }
 
     /// Generate SVG markup for the Claude mascot with color based on usage level
-    /// Pixel-perfect match to the ASCII art:
+    /// Pixel-perfect match to the ASCII art in the resting pose:
     ///    ▐▛███▜▌
     ///   ▝▜█████▛▘
     ///     ▘▘ ▝▝
-    fn generate_mascot_svg(color: Color) -> String {
-        let color_hex = format!(
-            "#{:02x}{:02x}{:02x}",
-            (color.r * 255.0) as u8,
-            (color.g * 255.0) as u8,
-            (color.b * 255.0) as u8
-        );
+    fn generate_mascot_svg(color: Color, pose: MascotPose) -> String {
+        let color_hex = color_to_hex(color);
+
+        // Top edges of the ears and head, and the heights of the feet
+        let (left_ear, right_ear, head, left_foot, right_foot) = match pose {
+            MascotPose::Rest => (0.0, 0.0, 1.0, 2.0, 2.0),
+            MascotPose::Breathe(frame) => {
+                // Ears and head sink slightly and rise again over one cycle
+                let phase = frame as f32 / BREATHE_FRAMES as f32;
+                let sink = 0.15 * (1.0 - (phase * 2.0 * std::f32::consts::PI).cos());
+                (sink, sink, 1.0 + sink, 2.0, 2.0)
+            }
+            // One ear twitches while the foot on the same side steps
+            MascotPose::Wiggle(1) => (0.5, 0.0, 1.0, 1.5, 2.0),
+            MascotPose::Wiggle(3) => (0.0, 0.5, 1.0, 2.0, 1.5),
+            MascotPose::Wiggle(_) => (0.0, 0.0, 1.0, 2.0, 2.0),
+            MascotPose::Droop => (1.5, 1.5, 1.5, 2.0, 2.0),
+        };
 
         // Using 9x8 grid for proper proportions
         format!(
             r##"<svg viewBox="0 0 9 8" xmlns="http://www.w3.org/2000/svg">
                 <!-- Left ear -->
-                <rect x="1" y="0" width="2" height="3" fill="{color_hex}"/>
+                <rect x="1" y="{left_ear:.2}" width="2" height="{:.2}" fill="{color_hex}"/>
                 <!-- Right ear -->
-                <rect x="6" y="0" width="2" height="3" fill="{color_hex}"/>
+                <rect x="6" y="{right_ear:.2}" width="2" height="{:.2}" fill="{color_hex}"/>
                 <!-- Head/body connecting ears -->
-                <rect x="3" y="1" width="3" height="2" fill="{color_hex}"/>
+                <rect x="3" y="{head:.2}" width="3" height="{:.2}" fill="{color_hex}"/>
                 <!-- Main body (wider) -->
                 <rect x="0" y="3" width="9" height="3" fill="{color_hex}"/>
                 <!-- Left foot -->
-                <rect x="1" y="6" width="2" height="2" fill="{color_hex}"/>
+                <rect x="1" y="6" width="2" height="{left_foot:.2}" fill="{color_hex}"/>
                 <!-- Right foot -->
-                <rect x="6" y="6" width="2" height="2" fill="{color_hex}"/>
-            </svg>"##
+                <rect x="6" y="6" width="2" height="{right_foot:.2}" fill="{color_hex}"/>
+            </svg>"##,
+            3.0 - left_ear,
+            3.0 - right_ear,
+            3.0 - head,
         )
     }
 
-    /// Create a session progress ring using SVG (with caching)
-    fn create_session_ring(&self, percent: f32, color: Color) -> Element<'_, Message> {
-        let svg_data = {
-            let mut cache = self.svg_cache.borrow_mut();
-            // Check if cached value is still valid
-            if cache.session_svg.is_some()
-                && (cache.session_percent - percent).abs() < 0.1
-                && cache.session_color == Some(color)
-            {
-                cache.session_svg.clone().unwrap()
-            } else {
-                // Generate new SVG and cache it
-                let svg = Self::generate_progress_svg(percent, color, "S");
-                cache.session_percent = percent;
-                cache.session_color = Some(color);
-                cache.session_svg = Some(svg);
-                cache.session_svg.clone().unwrap()
+    /// Popup progress bar for a usage window, with tick marks at its warning and critical thresholds
+    fn usage_bar(&self, window: UsageWindow, used_percent: f32) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        let (warning, critical) = self.thresholds(window);
+        // In remaining mode the bar drains, so the marks move to the capacity left at each threshold
+        let marks = vec![
+            (self.display_percent(warning as f32), colors.medium),
+            (self.display_percent(critical as f32), colors.high),
+        ];
+        stack![
+            self.level_bar(self.display_percent(used_percent), self.usage_color(window, used_percent)),
+            canvas(BarMarkers { marks }).width(Length::Fill).height(Length::Fill),
+        ]
+        .into()
+    }
+
+    /// Popup progress bar filled with a usage level color, so urgency shows in the popup as well.
+    /// Symbolic mode keeps the theme accent, matching its monochrome rings.
+    fn level_bar(&self, drawn_percent: f32, color: Color) -> Element<'static, Message> {
+        if self.config.color_mode == ColorMode::Symbolic {
+            return progress_bar(0.0..=100.0, drawn_percent).width(Length::Fill).into();
+        }
+        Self::tinted_bar(drawn_percent, color)
+    }
+
+    /// Label of a popup section in the settings list
+    fn popup_section_name(section: PopupSection) -> String {
+        match section {
+            PopupSection::Plan => fl!("popup-section-plan"),
+            PopupSection::Session => fl!("popup-section-session"),
+            PopupSection::Weekly => fl!("popup-section-weekly"),
+            PopupSection::Models => fl!("popup-section-models"),
+            PopupSection::Status => fl!("popup-section-status"),
+            PopupSection::Stats => fl!("popup-section-stats"),
+            PopupSection::Actions => fl!("popup-section-actions"),
+        }
+    }
+
+    /// Short plan name for the tier badge, from the OAuth `subscription_type` (e.g. "claude_max" or "pro")
+    fn plan_tier(subscription_type: &str) -> Option<&'static str> {
+        let plan = subscription_type.to_ascii_lowercase();
+        [
+            ("enterprise", "Enterprise"),
+            ("team", "Team"),
+            ("max", "Max"),
+            ("pro", "Pro"),
+            ("free", "Free"),
+        ]
+        .into_iter()
+        .find(|(marker, _)| plan.contains(marker))
+        .map(|(_, tier)| tier)
+    }
+
+    /// Small accent-colored pill with the plan tier, shown next to the popup title
+    fn tier_badge(tier: &'static str) -> Element<'static, Message> {
+        container(text::caption(tier))
+            .padding([0, 6])
+            .class(theme::Container::custom(|theme: &cosmic::Theme| {
+                let cosmic = theme.cosmic();
+                container::Style {
+                    text_color: Some(cosmic.on_accent_color().into()),
+                    background: Some(Color::from(cosmic.accent_color()).into()),
+                    border: cosmic::iced::Border {
+                        radius: cosmic.corner_radii.radius_xl.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            }))
+            .into()
+    }
+
+    /// Popup progress bar filled with `color` on the theme's track
+    fn tinted_bar(drawn_percent: f32, color: Color) -> Element<'static, Message> {
+        let bar = progress_bar(0.0..=100.0, drawn_percent).width(Length::Fill);
+        bar.class(theme::ProgressBar::Custom(Box::new(move |theme: &cosmic::Theme| {
+            let cosmic = theme.cosmic();
+            cosmic::iced::widget::progress_bar::Style {
+                background: Color::from(cosmic.background.divider).into(),
+                bar: color.into(),
+                border: cosmic::iced::Border {
+                    radius: cosmic.corner_radii.radius_xs.into(),
+                    ..Default::default()
+                },
             }
-        };
-        let handle = svg::Handle::from_memory(svg_data.into_bytes());
+        })))
+        .into()
+    }
+
+    /// Level color for an additional usage window: the weekly Opus limit has its own thresholds,
+    /// every other window follows the weekly ones
+    fn named_window_color(&self, name: &str, percent: f32) -> Color {
+        match (self.config.color_mode, name) {
+            (ColorMode::Threshold, "seven_day_opus") => self.get_level_color(Self::level_for(
+                percent,
+                self.config.opus_warning_threshold,
+                self.config.opus_critical_threshold,
+            )),
+            _ => self.usage_color(UsageWindow::Weekly, percent),
+        }
+    }
+
+    /// Create a usage ring drawn on a canvas
+    /// `used_percent` drives the level; the drawn fill follows the used/remaining display mode.
+    fn create_ring(&self, window: UsageWindow, used_percent: f32, color: Color, label: &'static str) -> Element<'_, Message> {
+        self.usage_glyph(self.display_percent(used_percent), color, label, self.ring_style(window, used_percent))
+    }
+
+    /// A single usage glyph in the configured indicator style: a ring, or a battery that is
+    /// half again as long as the panel is thick
+    fn usage_glyph(&self, percent: f32, color: Color, label: &'static str, style: RingStyle) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        let size = self.indicator_size();
+        match self.config.indicator_style {
+            IndicatorStyle::Ring => canvas(Ring {
+                percent,
+                color,
+                track: colors.track,
+                foreground: colors.label,
+                label,
+                style,
+            })
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size))
+            .into(),
+            IndicatorStyle::Battery => {
+                let vertical = !self.core.applet.is_horizontal();
+                let (width, height) = if vertical { (size, size * 1.5) } else { (size * 1.5, size) };
+                canvas(Battery {
+                    percent,
+                    color,
+                    track: colors.track,
+                    foreground: colors.label,
+                    label,
+                    style,
+                    vertical,
+                })
+                .width(Length::Fixed(width))
+                .height(Length::Fixed(height))
+                .into()
+            }
+        }
+    }
+
+    /// Ring shape for a window's usage level; only symbolic mode varies it
+    fn ring_style(&self, window: UsageWindow, used_percent: f32) -> RingStyle {
+        match self.symbolic_level(window, used_percent) {
+            Some(UsageLevel::Medium) => RingStyle::Dashed,
+            Some(UsageLevel::High) => RingStyle::Double,
+            Some(UsageLevel::Low) | None => RingStyle::Solid,
+        }
+    }
+
+    /// Arc of a usage window in the concentric glyph, shaped like its single ring would be
+    fn ring_arc(&self, window: UsageWindow, used_percent: f32, drawn_percent: f32, color: Color) -> RingArc {
+        if used_percent >= 100.0 {
+            let color = if self.config.color_mode == ColorMode::Symbolic { self.theme_colors().label } else { COLOR_LOCKED };
+            return RingArc {
+                percent: 100.0,
+                color,
+                style: RingStyle::Locked,
+            };
+        }
+        RingArc {
+            percent: self.display_percent(drawn_percent),
+            color,
+            style: self.ring_style(window, used_percent),
+        }
+    }
+
+    /// Session (outer) and weekly (inner) usage as concentric arcs in a single glyph
+    fn create_concentric_rings(&self, outer: RingArc, inner: RingArc) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        canvas(ConcentricRings {
+            outer,
+            inner,
+            track: colors.track,
+            foreground: colors.label,
+        })
+        .width(Length::Fixed(self.indicator_size()))
+        .height(Length::Fixed(self.indicator_size()))
+        .into()
+    }
+
+    /// Create a session progress ring
+    fn create_session_ring(&self, percent: f32, color: Color) -> Element<'_, Message> {
+        self.create_ring(UsageWindow::Session, percent, color, "S")
+    }
+
+    /// Create a weekly progress ring
+    fn create_weekly_ring(&self, percent: f32, color: Color) -> Element<'_, Message> {
+        self.create_ring(UsageWindow::Weekly, percent, color, "W")
+    }
+
+    /// Create the locked-out ring with a padlock in place of the label
+    fn create_locked_ring(&self) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        let color = if self.config.color_mode == ColorMode::Symbolic { colors.label } else { COLOR_LOCKED };
+        self.usage_glyph(100.0, color, "", RingStyle::Locked)
+    }
+
+    /// Full dashed ring marking an expired login
+    fn create_expired_ring(&self, label: &'static str) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        let color = if self.config.color_mode == ColorMode::Symbolic { colors.label } else { colors.high };
+        self.usage_glyph(100.0, color, label, RingStyle::Dashed)
+    }
+
+    /// SVG markup for a filled dot
+    fn generate_dot_svg(color: Color) -> String {
+        let color_hex = color_to_hex(color);
+        format!(
+            r##"<svg viewBox="0 0 8 8" xmlns="http://www.w3.org/2000/svg"><circle cx="4" cy="4" r="4" fill="{color_hex}"/></svg>"##
+        )
+    }
+
+    /// Create a filled dot, used for incident markers and color previews
+    fn create_color_dot(&self, color: Color, size: f32) -> Element<'static, Message> {
+        let handle = SvgCacheInner::handle(&mut self.svg_cache.borrow_mut().dots, color.into_rgba8(), || {
+            Self::generate_dot_svg(color)
+        });
         cosmic::iced_widget::Svg::new(handle)
-            .width(Length::Fixed(24.0))
-            .height(Length::Fixed(24.0))
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size))
             .into()
     }
 
-    /// Create a weekly progress ring using SVG (with caching)
-    fn create_weekly_ring(&self, percent: f32, color: Color) -> Element<'_, Message> {
-        let svg_data = {
-            let mut cache = self.svg_cache.borrow_mut();
-            // Check if cached value is still valid
-            if cache.weekly_svg.is_some()
-                && (cache.weekly_percent - percent).abs() < 0.1
-                && cache.weekly_color == Some(color)
-            {
-                cache.weekly_svg.clone().unwrap()
-            } else {
-                // Generate new SVG and cache it
-                let svg = Self::generate_progress_svg(percent, color, "W");
-                cache.weekly_percent = percent;
-                cache.weekly_color = Some(color);
-                cache.weekly_svg = Some(svg);
-                cache.weekly_svg.clone().unwrap()
-            }
-        };
+    /// Horizontal bar split into colored segments by fraction (fractions should sum to 1)
+    fn create_stacked_bar(segments: &[(f32, Color)]) -> Element<'static, Message> {
+        let mut x = 0.0;
+        let mut rects = String::new();
+        for (fraction, color) in segments {
+            let width = fraction * 100.0;
+            rects.push_str(&format!(
+                r##"<rect x="{x:.2}" y="0" width="{width:.2}" height="4" fill="{}"/>"##,
+                color_to_hex(*color)
+            ));
+            x += width;
+        }
+        let svg_data = format!(
+            r##"<svg viewBox="0 0 100 4" preserveAspectRatio="none" xmlns="http://www.w3.org/2000/svg">{rects}</svg>"##
+        );
         let handle = svg::Handle::from_memory(svg_data.into_bytes());
         cosmic::iced_widget::Svg::new(handle)
-            .width(Length::Fixed(24.0))
-            .height(Length::Fixed(24.0))
+            .width(Length::Fill)
+            .height(Length::Fixed(6.0))
             .into()
     }
 
-    /// Create the Claude mascot icon using SVG (with caching)
+    /// Read the custom mascot file, falling back to the built-in mascot when it is unset or unusable
+    fn reload_custom_mascot(&mut self) {
+        self.svg_cache.borrow_mut().custom_mascots.clear();
+        self.custom_mascot_svg = self
+            .config
+            .custom_mascot_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .and_then(|path| {
+                let path = config::expand_home(path);
+                match std::fs::metadata(&path).and_then(|meta| {
+                    if meta.len() > CUSTOM_MASCOT_LIMIT {
+                        Err(std::io::Error::other("file too large"))
+                    } else {
+                        std::fs::read_to_string(&path)
+                    }
+                }) {
+                    Ok(contents) if contents.contains("<svg") => Some(contents),
+                    Ok(_) => {
+                        tracing::warn!("Custom mascot {} is not an SVG file", path.display());
+                        None
+                    }
+                    Err(err) => {
+                        tracing::warn!(?err, "Failed to read custom mascot {}", path.display());
+                        None
+                    }
+                }
+            });
+    }
+
+    /// Create the Claude mascot icon using SVG (with caching), posed for the current state.
+    /// A custom mascot file replaces it, recolored when it uses `currentColor` and never animated.
     fn create_mascot(&self, color: Color) -> Element<'_, Message> {
-        let svg_data = {
-            let mut cache = self.svg_cache.borrow_mut();
-            // Check if cached value is still valid
-            if cache.mascot_svg.is_some() && cache.mascot_color == Some(color) {
-                cache.mascot_svg.clone().unwrap()
-            } else {
-                // Generate new SVG and cache it
-                let svg = Self::generate_mascot_svg(color);
-                cache.mascot_color = Some(color);
-                cache.mascot_svg = Some(svg);
-                cache.mascot_svg.clone().unwrap()
+        let handle = match &self.custom_mascot_svg {
+            Some(custom) => {
+                let recolor = custom.contains("currentColor");
+                let key = if recolor { color.into_rgba8() } else { [0; 4] };
+                SvgCacheInner::handle(&mut self.svg_cache.borrow_mut().custom_mascots, key, || {
+                    if recolor {
+                        custom.replace("currentColor", &color_to_hex(color))
+                    } else {
+                        custom.clone()
+                    }
+                })
+            }
+            None => {
+                let pose = self.mascot_pose();
+                SvgCacheInner::handle(&mut self.svg_cache.borrow_mut().mascots, (color.into_rgba8(), pose), || {
+                    Self::generate_mascot_svg(color, pose)
+                })
             }
         };
-        let handle = svg::Handle::from_memory(svg_data.into_bytes());
         cosmic::iced_widget::Svg::new(handle)
-            .width(Length::Fixed(24.0))
-            .height(Length::Fixed(24.0))
+            .width(Length::Fixed(self.indicator_size()))
+            .height(Length::Fixed(self.indicator_size()))
             .into()
     }
 
-    /// Create the visual usage indicator widget - SVG circular progress rings with optional mascot
+    /// Create the visual usage indicator widget - canvas progress rings with optional mascot
     fn create_usage_indicator(&self) -> Element<'_, Message> {
         let spacing = 4.0;
 
@@ -724,67 +4867,257 @@ impl ClaudeApplet {
             self.session_usage_percent, self.weekly_usage_percent, self.has_credentials, self.config.show_mascot
         );
 
+        if self.uses_api_key_billing() || self.local_stats_only() {
+            // No usage windows to draw; the mascot alone marks the applet, or the cost text next to it
+            if self.config.indicator_style == IndicatorStyle::Text {
+                return cosmic::iced::widget::Space::new(Length::Shrink, Length::Shrink).into();
+            }
+            return self.create_mascot(self.theme_colors().mascot);
+        }
+
+        if self.config.indicator_style == IndicatorStyle::Text {
+            return self.create_text_indicator(spacing);
+        }
+
+        if !self.has_credentials && self.token_expired {
+            // Expired login: dashed full rings in the critical color ask for a fresh login
+            let rings: Element<'_, Message> = match self.config.icon_display {
+                IconDisplay::Session => self.create_expired_ring("S"),
+                IconDisplay::Weekly => self.create_expired_ring("W"),
+                IconDisplay::Both => self.panel_stack(
+                    vec![self.create_expired_ring("S"), self.create_expired_ring("W")],
+                    spacing,
+                ),
+                IconDisplay::Concentric => {
+                    let colors = self.theme_colors();
+                    let color = if self.config.color_mode == ColorMode::Symbolic { colors.label } else { colors.high };
+                    let expired = RingArc {
+                        percent: 100.0,
+                        color,
+                        style: RingStyle::Dashed,
+                    };
+                    self.create_concentric_rings(expired, expired)
+                }
+            };
+            return if self.config.show_mascot {
+                self.panel_stack(vec![self.create_mascot(self.theme_colors().inactive), rings], spacing)
+            } else {
+                rings
+            };
+        }
+
         if !self.has_credentials {
-            // Inactive state - show appropriate icon in gray
+            // Inactive state - show appropriate icon in the theme's neutral color
+            let inactive = self.theme_colors().inactive;
             let rings: Element<'_, Message> = match self.config.icon_display {
-                IconDisplay::Session => self.create_session_ring(0.0, COLOR_INACTIVE),
-                IconDisplay::Weekly => self.create_weekly_ring(0.0, COLOR_INACTIVE),
-                IconDisplay::Both => row![
-                    self.create_session_ring(0.0, COLOR_INACTIVE),
-                    self.create_weekly_ring(0.0, COLOR_INACTIVE),
-                ]
-                .spacing(spacing)
-                .align_y(Alignment::Center)
-                .into(),
+                IconDisplay::Session => self.create_session_ring(0.0, inactive),
+                IconDisplay::Weekly => self.create_weekly_ring(0.0, inactive),
+                IconDisplay::Both => self.panel_stack(
+                    vec![
+                        self.create_session_ring(0.0, inactive),
+                        self.create_weekly_ring(0.0, inactive),
+                    ],
+                    spacing,
+                ),
+                IconDisplay::Concentric => {
+                    let empty = RingArc {
+                        percent: 0.0,
+                        color: inactive,
+                        style: RingStyle::Solid,
+                    };
+                    self.create_concentric_rings(empty, empty)
+                }
             };
 
             return if self.config.show_mascot {
-                row![
-                    self.create_mascot(COLOR_INACTIVE),
-                    rings,
-                ]
-                .spacing(spacing)
-                .align_y(Alignment::Center)
-                .into()
+                self.panel_stack(vec![self.create_mascot(inactive), rings], spacing)
             } else {
                 rings
             };
         }
 
-        let session_color = self.get_level_color(self.get_usage_level(self.session_usage_percent));
-        let weekly_color = self.get_level_color(self.get_usage_level(self.weekly_usage_percent));
+        let (session_percent, session_color) = self.displayed_session();
+        let (weekly_percent, weekly_color) = self.displayed_weekly();
+        let session_color = self.pulse_color(session_color, UsageWindow::Session, self.session_usage_percent);
+        let weekly_color = self.pulse_color(weekly_color, UsageWindow::Weekly, self.weekly_usage_percent);
 
-        let rings: Element<'_, Message> = match self.config.icon_display {
-            IconDisplay::Session => {
-                self.create_session_ring(self.session_usage_percent, session_color)
-            }
-            IconDisplay::Weekly => {
-                self.create_weekly_ring(self.weekly_usage_percent, weekly_color)
+        // Exhausted windows swap their ring for the padlock glyph
+        let session_ring = || {
+            if self.session_usage_percent >= 100.0 {
+                self.create_locked_ring()
+            } else {
+                self.create_session_ring(session_percent, session_color)
             }
-            IconDisplay::Both => {
-                row![
-                    self.create_session_ring(self.session_usage_percent, session_color),
-                    self.create_weekly_ring(self.weekly_usage_percent, weekly_color),
-                ]
-                .spacing(spacing)
-                .align_y(Alignment::Center)
-                .into()
+        };
+        let weekly_ring = || {
+            if self.weekly_usage_percent >= 100.0 {
+                self.create_locked_ring()
+            } else {
+                self.create_weekly_ring(weekly_percent, weekly_color)
             }
         };
 
+        let rings: Element<'_, Message> = match self.config.icon_display {
+            IconDisplay::Session => session_ring(),
+            IconDisplay::Weekly => weekly_ring(),
+            IconDisplay::Both => self.panel_stack(vec![session_ring(), weekly_ring()], spacing),
+            IconDisplay::Concentric => self.create_concentric_rings(
+                self.ring_arc(UsageWindow::Session, self.session_usage_percent, session_percent, session_color),
+                self.ring_arc(UsageWindow::Weekly, self.weekly_usage_percent, weekly_percent, weekly_color),
+            ),
+        };
+
+        let mascot_color = if self.config.color_mode == ColorMode::Symbolic {
+            self.theme_colors().label
+        } else if self.is_locked_out() {
+            COLOR_LOCKED
+        } else {
+            self.theme_colors().mascot
+        };
+
         if self.config.show_mascot {
-            row![
-                self.create_mascot(COLOR_CLAUDE),
-                rings,
-            ]
-            .spacing(spacing)
-            .align_y(Alignment::Center)
-            .into()
+            self.panel_stack(vec![self.create_mascot(mascot_color), rings], spacing)
         } else {
             rings
         }
     }
 
+    /// Colored text in place of the glyphs: the filled-in template in the color of the fullest
+    /// window it mentions, or each displayed window's percentage in its own color
+    fn create_text_indicator(&self, spacing: f32) -> Element<'_, Message> {
+        let colors = self.theme_colors();
+        if !self.has_credentials {
+            let color = if self.token_expired { colors.high } else { colors.inactive };
+            return text::body("–").class(theme::Text::Color(color)).into();
+        }
+
+        let window_text = |window: UsageWindow, percent: f32| -> Element<'_, Message> {
+            text::body(self.format_percent(percent))
+                .class(theme::Text::Color(self.text_color(window, percent)))
+                .into()
+        };
+        let template = self.config.text_template.as_deref().map(str::trim).unwrap_or_default();
+        if template.is_empty() {
+            return match self.config.icon_display {
+                IconDisplay::Session => window_text(UsageWindow::Session, self.session_usage_percent),
+                IconDisplay::Weekly => window_text(UsageWindow::Weekly, self.weekly_usage_percent),
+                IconDisplay::Both | IconDisplay::Concentric => self.panel_stack(
+                    vec![
+                        window_text(UsageWindow::Session, self.session_usage_percent),
+                        window_text(UsageWindow::Weekly, self.weekly_usage_percent),
+                    ],
+                    spacing,
+                ),
+            };
+        }
+
+        let mut content = template
+            .replace("{session}", &self.format_percent(self.session_usage_percent))
+            .replace("{weekly}", &self.format_percent(self.weekly_usage_percent))
+            .replace("{cost}", &localize::format_currency(self.local_costs().0));
+        if content.contains("{reset}") {
+            let reset = self.session_reset_time.map(Self::format_compact_countdown).unwrap_or_default();
+            content = content.replace("{reset}", &reset);
+        }
+        let mentioned = [
+            (template.contains("{session}"), UsageWindow::Session, self.session_usage_percent),
+            (template.contains("{weekly}"), UsageWindow::Weekly, self.weekly_usage_percent),
+        ];
+        let color = mentioned
+            .into_iter()
+            .filter(|(shown, ..)| *shown)
+            .max_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+            .map_or(colors.label, |(_, window, percent)| self.text_color(window, percent));
+        text::body(content).class(theme::Text::Color(color)).into()
+    }
+
+    /// Text color for a window's usage in the text indicator style; exhausted windows use the lockout color
+    fn text_color(&self, window: UsageWindow, used_percent: f32) -> Color {
+        if used_percent >= 100.0 && self.config.color_mode != ColorMode::Symbolic {
+            COLOR_LOCKED
+        } else {
+            self.pulse_color(self.usage_color(window, used_percent), window, used_percent)
+        }
+    }
+
+    /// Overlay a small badge on the indicator's right edge, at the top or bottom corner
+    fn overlay_badge<'a>(&self, indicator: Element<'a, Message>, badge: Badge, vertical: Alignment) -> Element<'a, Message> {
+        let size = (self.indicator_size() * 0.5).max(8.0);
+        let badge = canvas(badge)
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size));
+        self.overlay_corner(indicator, badge.into(), vertical)
+    }
+
+    /// Overlay any element on the indicator's right edge, at the top or bottom corner
+    fn overlay_corner<'a>(
+        &self,
+        indicator: Element<'a, Message>,
+        overlay: Element<'a, Message>,
+        vertical: Alignment,
+    ) -> Element<'a, Message> {
+        stack![
+            indicator,
+            container(overlay)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .align_x(Alignment::End)
+                .align_y(vertical),
+        ]
+        .into()
+    }
+
+    /// Edge length of each indicator glyph, following the panel's suggested icon size
+    fn indicator_size(&self) -> f32 {
+        let (width, height) = self.core.applet.suggested_size(false);
+        f32::from(width.min(height))
+    }
+
+    /// Lay out panel items along the panel: a row on horizontal panels, a column on vertical ones
+    fn panel_stack<'a>(&self, items: Vec<Element<'a, Message>>, spacing: f32) -> Element<'a, Message> {
+        if self.core.applet.is_horizontal() {
+            cosmic::iced_widget::Row::with_children(items)
+                .spacing(spacing)
+                .align_y(Alignment::Center)
+                .into()
+        } else {
+            cosmic::iced_widget::Column::with_children(items)
+                .spacing(spacing)
+                .align_x(Alignment::Center)
+                .into()
+        }
+    }
+
+    /// Open a directory with the configured file manager command (run on the host), or `xdg-open`
+    fn open_in_file_manager(&self, path: std::path::PathBuf) {
+        let command = self.config.file_manager_command.as_deref().unwrap_or_default();
+        let mut parts = command.split_whitespace();
+        let (cmd, program) = match parts.next() {
+            Some(program) => {
+                let mut cmd = flatpak::host_command(program);
+                cmd.args(parts).arg(path);
+                (cmd, program.to_string())
+            }
+            None => (flatpak::open_command(path), "xdg-open".to_string()),
+        };
+        tokio::spawn(async move {
+            if cosmic::process::spawn(cmd).await.is_none() {
+                tracing::error!("Failed to open file manager: {} process could not be spawned", program);
+            }
+        });
+    }
+
+    /// Launch cosmic-term running the given command
+    fn spawn_in_terminal(args: &[&str]) {
+        let mut cmd = flatpak::host_command("cosmic-term");
+        cmd.arg("-e").args(args);
+        tokio::spawn(async {
+            if cosmic::process::spawn(cmd).await.is_none() {
+                tracing::error!("Failed to open terminal: cosmic-term process could not be spawned");
+            }
+        });
+    }
+
     /// Save current config to cosmic-config
     fn save_config(&self) {
         if let Ok(config_helper) =
@@ -796,3 +5129,120 @@ impl ClaudeApplet {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countdown_shows_hours_only_when_needed() {
+        assert_eq!(ClaudeApplet::format_countdown_seconds(3 * 3600 + 2 * 60 + 1), "3h 02m 01s");
+        assert_eq!(ClaudeApplet::format_countdown_seconds(59 * 60 + 5), "59m 05s");
+        assert_eq!(ClaudeApplet::format_countdown_seconds(7), "0m 07s");
+    }
+
+    #[test]
+    fn countdown_stops_at_zero_once_the_reset_passed() {
+        assert_eq!(ClaudeApplet::format_countdown_seconds(-42), "0m 00s");
+    }
+
+    #[test]
+    fn lockout_lifts_at_the_latest_exhausted_reset() {
+        let soon = Utc::now() + chrono::Duration::hours(1);
+        let later = Utc::now() + chrono::Duration::days(2);
+        assert_eq!(ClaudeApplet::latest_exhausted_reset(&[(100.0, Some(soon)), (100.0, Some(later))]), Some(later));
+        // A window with capacity left does not hold the lockout
+        assert_eq!(ClaudeApplet::latest_exhausted_reset(&[(100.0, Some(soon)), (99.9, Some(later))]), Some(soon));
+        assert_eq!(ClaudeApplet::latest_exhausted_reset(&[(80.0, Some(soon)), (50.0, Some(later))]), None);
+        assert_eq!(ClaudeApplet::latest_exhausted_reset(&[(100.0, None), (50.0, Some(later))]), None);
+    }
+
+    #[test]
+    fn lerp_color_blends_channels_and_clamps_the_factor() {
+        let black = Color::from_rgb(0.0, 0.0, 0.0);
+        let target = Color::from_rgb(1.0, 0.5, 0.25);
+        assert_eq!(lerp_color(black, target, 0.0), black);
+        assert_eq!(lerp_color(black, target, 0.5), Color::from_rgb(0.5, 0.25, 0.125));
+        assert_eq!(lerp_color(black, target, 1.0), target);
+        assert_eq!(lerp_color(black, target, 2.0), target);
+        assert_eq!(lerp_color(black, target, -1.0), black);
+    }
+
+    #[test]
+    fn forecast_extrapolates_the_pace_since_the_window_opened() {
+        let now = Utc::now();
+        // Two days into the window with half the limit used: two more days to go
+        let reset = now + chrono::Duration::days(5);
+        let forecast = ClaudeApplet::exhaustion_forecast(50.0, reset, now).unwrap();
+        assert!((forecast - (now + chrono::Duration::days(2))).num_seconds().abs() <= 1);
+    }
+
+    #[test]
+    fn forecast_edges() {
+        let now = Utc::now();
+        let reset = now + chrono::Duration::days(5);
+        assert_eq!(ClaudeApplet::exhaustion_forecast(0.0, reset, now), None);
+        assert_eq!(ClaudeApplet::exhaustion_forecast(100.0, reset, now), Some(now));
+        // A reset more than a week out means the window has not started yet
+        assert_eq!(ClaudeApplet::exhaustion_forecast(10.0, now + chrono::Duration::days(8), now), None);
+    }
+
+    #[test]
+    fn forecast_beyond_the_representable_range_is_dropped() {
+        let now = Utc::now();
+        let reset = now + chrono::Duration::days(5);
+        assert_eq!(ClaudeApplet::exhaustion_forecast(f32::MIN_POSITIVE, reset, now), None);
+    }
+
+    #[test]
+    fn alert_fires_once_when_crossing_a_threshold() {
+        let mut alerted = UsageLevel::Low;
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 75.0, 70, 90), Some(UsageLevel::Medium));
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 76.0, 70, 90), None);
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 95.0, 70, 90), Some(UsageLevel::High));
+        assert_eq!(alerted, UsageLevel::High);
+    }
+
+    #[test]
+    fn alert_does_not_rearm_while_hovering_around_the_threshold() {
+        let mut alerted = UsageLevel::Low;
+        ClaudeApplet::advance_alert_level(&mut alerted, 71.0, 70, 90);
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 68.0, 70, 90), None);
+        assert_eq!(alerted, UsageLevel::Medium);
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 71.0, 70, 90), None);
+    }
+
+    #[test]
+    fn alert_rearms_once_usage_falls_below_the_hysteresis_band() {
+        let mut alerted = UsageLevel::Low;
+        ClaudeApplet::advance_alert_level(&mut alerted, 71.0, 70, 90);
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 64.0, 70, 90), None);
+        assert_eq!(alerted, UsageLevel::Low);
+        assert_eq!(ClaudeApplet::advance_alert_level(&mut alerted, 71.0, 70, 90), Some(UsageLevel::Medium));
+    }
+
+    #[test]
+    fn quiet_hours_within_a_day() {
+        assert!(!ClaudeApplet::hour_in_range(8, 9, 17));
+        assert!(ClaudeApplet::hour_in_range(9, 9, 17));
+        assert!(ClaudeApplet::hour_in_range(16, 9, 17));
+        assert!(!ClaudeApplet::hour_in_range(17, 9, 17));
+    }
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        assert!(ClaudeApplet::hour_in_range(22, 22, 7));
+        assert!(ClaudeApplet::hour_in_range(23, 22, 7));
+        assert!(ClaudeApplet::hour_in_range(0, 22, 7));
+        assert!(ClaudeApplet::hour_in_range(6, 22, 7));
+        assert!(!ClaudeApplet::hour_in_range(7, 22, 7));
+        assert!(!ClaudeApplet::hour_in_range(21, 22, 7));
+    }
+
+    #[test]
+    fn quiet_hours_with_equal_start_and_end_are_never_active() {
+        for hour in 0..24 {
+            assert!(!ClaudeApplet::hour_in_range(hour, 5, 5));
+        }
+    }
+}
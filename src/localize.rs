@@ -4,8 +4,10 @@ use i18n_embed::{
     DefaultLocalizer, LanguageLoader, Localizer,
     fluent::{FluentLanguageLoader, fluent_language_loader},
 };
+use chrono::{DateTime, NaiveDate, TimeZone, Weekday};
 use rust_embed::RustEmbed;
-use std::sync::LazyLock;
+use std::fmt::Display;
+use std::sync::{LazyLock, RwLock};
 
 #[derive(RustEmbed)]
 #[folder = "i18n/"]
@@ -45,3 +47,144 @@ pub fn localize() {
         eprintln!("Error while loading language for Claude applet: {error}");
     }
 }
+
+/// Locale used for dates and numbers, from the usual POSIX environment variables
+static FORMAT_LOCALE: LazyLock<String> = LazyLock::new(|| {
+    ["LC_ALL", "LC_TIME", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| value.split(['.', '@']).next().unwrap_or_default().to_string())
+        .unwrap_or_else(|| "en_US".to_string())
+});
+
+/// Languages that write decimals with a comma and group digits with a space or period
+const COMMA_DECIMAL_LANGUAGES: &[&str] = &[
+    "bg", "cs", "da", "de", "es", "fi", "fr", "hu", "it", "nb", "nl", "nn", "pl", "pt", "ro", "ru", "sk",
+    "sl", "sv", "tr", "uk",
+];
+
+/// Decimal-comma languages that group digits with a space rather than a period
+const SPACE_GROUPING_LANGUAGES: &[&str] = &[
+    "bg", "cs", "fi", "fr", "hu", "nb", "nn", "pl", "ru", "sk", "sv", "uk",
+];
+
+fn format_language() -> &'static str {
+    FORMAT_LOCALE.split('_').next().unwrap_or_default()
+}
+
+fn uses_decimal_comma() -> bool {
+    COMMA_DECIMAL_LANGUAGES.contains(&format_language())
+}
+
+/// Digit group separator for the locale: "," in English, "." in German, a no-break space in Swedish
+fn group_separator() -> char {
+    if !uses_decimal_comma() {
+        ','
+    } else if SPACE_GROUPING_LANGUAGES.contains(&format_language()) {
+        '\u{a0}'
+    } else {
+        '.'
+    }
+}
+
+/// Number formatting chosen in the settings
+#[derive(Debug, Clone, Copy)]
+struct NumberFormat {
+    cost_precision: usize,
+    group_digits: bool,
+}
+
+static NUMBER_FORMAT: RwLock<NumberFormat> = RwLock::new(NumberFormat {
+    cost_precision: 2,
+    group_digits: true,
+});
+
+fn number_format() -> NumberFormat {
+    *NUMBER_FORMAT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Apply the number formatting settings
+pub fn set_number_format(cost_precision: u8, group_digits: bool) {
+    *NUMBER_FORMAT.write().unwrap_or_else(|e| e.into_inner()) = NumberFormat {
+        cost_precision: cost_precision as usize,
+        group_digits,
+    };
+}
+
+/// Decimals shown for dollar amounts
+fn cost_precision() -> usize {
+    number_format().cost_precision
+}
+
+/// Insert the locale's group separator every three digits when grouping is enabled
+fn group_digits(digits: &str) -> String {
+    if !number_format().group_digits || digits.len() <= 3 {
+        return digits.to_string();
+    }
+    let separator = group_separator();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Format a count with digit grouping (e.g. "12,345" or "12 345")
+pub fn format_count(value: u64) -> String {
+    group_digits(&value.to_string())
+}
+
+/// Format a date with the localized short pattern and month names (e.g. "Oct 03" or "3 okt")
+pub fn format_date<Tz: TimeZone>(date: &DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    let locale = chrono::Locale::try_from(FORMAT_LOCALE.as_str()).unwrap_or(chrono::Locale::en_US);
+    date.format_localized(&crate::fl!("date-format"), locale).to_string()
+}
+
+/// Format the abbreviated weekday name in the user's locale (e.g. "Fri", "fre")
+pub fn format_weekday<Tz: TimeZone>(date: &DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    let locale = chrono::Locale::try_from(FORMAT_LOCALE.as_str()).unwrap_or(chrono::Locale::en_US);
+    date.format_localized("%a", locale).to_string()
+}
+
+/// Full weekday name in the user's locale (e.g. "Sunday", "söndag")
+pub fn format_weekday_name(day: Weekday) -> String {
+    let locale = chrono::Locale::try_from(FORMAT_LOCALE.as_str()).unwrap_or(chrono::Locale::en_US);
+    // Any date falling on the weekday will do
+    NaiveDate::from_isoywd_opt(2024, 1, day)
+        .and_then(|date| date.and_hms_opt(12, 0, 0))
+        .map(|time| time.and_utc().format_localized("%A", locale).to_string())
+        .unwrap_or_else(|| format!("{day:?}"))
+}
+
+/// Format a number with a fixed number of decimals using the locale's decimal and group separators
+pub fn format_decimal(value: f64, precision: usize) -> String {
+    let formatted = format!("{:.precision$}", value.abs());
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let sign = if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        "-"
+    } else {
+        ""
+    };
+    let integer = group_digits(integer);
+    if fraction.is_empty() {
+        format!("{sign}{integer}")
+    } else {
+        let decimal = if uses_decimal_comma() { ',' } else { '.' };
+        format!("{sign}{integer}{decimal}{fraction}")
+    }
+}
+
+/// Format a US dollar amount for display with the configured precision (e.g. "$3.42" or "3,42 US$")
+pub fn format_currency(usd: f64) -> String {
+    crate::fl!("currency-usd", amount = format_decimal(usd, cost_precision()))
+}
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::LogLevel;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{LazyLock, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::filter::{self, LevelFilter};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Number of events kept for the popup's log page
+const CAPACITY: usize = 200;
+
+/// Size at which the log file is rotated
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Rotated files kept next to the current one (`applet.log.1` ..)
+const ROTATED_FILES: usize = 3;
+
+/// Configured level as an index into `LEVELS`; read on every event so changes apply immediately
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static FILE_ENABLED: AtomicBool = AtomicBool::new(false);
+static FILE: Mutex<Option<RotatingFile>> = Mutex::new(None);
+
+const LEVELS: [Level; 5] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE];
+
+/// A tracing event captured for display in the popup
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+static BUFFER: LazyLock<Mutex<VecDeque<LogEntry>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(CAPACITY)));
+
+/// Layer that keeps the most recent events in a fixed-size ring buffer
+struct BufferLayer;
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            timestamp: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+        };
+
+        let Ok(mut buffer) = BUFFER.lock() else {
+            return;
+        };
+        if buffer.len() == CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(entry);
+    }
+}
+
+/// Formats an event's message followed by its other fields as `name=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            if !self.message.is_empty() {
+                self.message.push(' ');
+            }
+            let _ = write!(self.message, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Captured warnings and errors, newest first
+pub fn recent() -> Vec<LogEntry> {
+    BUFFER
+        .lock()
+        .map(|buffer| buffer.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Log file that moves itself aside once it grows past `MAX_FILE_BYTES`
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self { path, file, written })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..ROTATED_FILES).rev() {
+            let _ = std::fs::rename(Self::rotated_path(&self.path, index), Self::rotated_path(&self.path, index + 1));
+        }
+        std::fs::rename(&self.path, Self::rotated_path(&self.path, 1))?;
+        *self = Self::open(self.path.clone())?;
+        Ok(())
+    }
+
+    fn rotated_path(path: &std::path::Path, index: usize) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+}
+
+impl io::Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written >= MAX_FILE_BYTES {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writer handed to the file layer; drops output while file logging is off
+struct FileWriter;
+
+impl io::Write for FileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match FILE.lock().ok().as_deref_mut().and_then(Option::as_mut) {
+            Some(file) => file.write(buf),
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match FILE.lock().ok().as_deref_mut().and_then(Option::as_mut) {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Path of the current log file, `$XDG_STATE_HOME/cosmic-applet-claude/applet.log`
+pub fn log_file_path() -> Option<PathBuf> {
+    dirs::state_dir().map(|dir| dir.join("cosmic-applet-claude").join("applet.log"))
+}
+
+fn level_enabled(metadata: &Metadata<'_>) -> bool {
+    let configured = LEVELS[LEVEL.load(Ordering::Relaxed) as usize];
+    *metadata.level() <= configured
+}
+
+/// Apply the configured level and file output; takes effect for the next event
+pub fn configure(level: LogLevel, to_file: bool) {
+    let index = match level {
+        LogLevel::Error => 0,
+        LogLevel::Warn => 1,
+        LogLevel::Info => 2,
+        LogLevel::Debug => 3,
+        LogLevel::Trace => 4,
+    };
+    LEVEL.store(index, Ordering::Relaxed);
+
+    if to_file == FILE_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Ok(mut slot) = FILE.lock() else {
+        return;
+    };
+    *slot = if to_file {
+        match log_file_path().map(RotatingFile::open) {
+            Some(Ok(opened)) => Some(opened),
+            Some(Err(err)) => {
+                drop(slot);
+                tracing::error!("Failed to open log file: {}", err);
+                return;
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+    FILE_ENABLED.store(slot.is_some(), Ordering::Relaxed);
+}
+
+/// Install the global subscriber: stderr, the optional log file and the in-memory buffer of warnings and errors.
+/// Stderr follows `RUST_LOG` when it is set and the configured level otherwise.
+pub fn init() {
+    let env_filter = std::env::var_os(EnvFilter::DEFAULT_ENV).map(|_| EnvFilter::from_default_env());
+    let stderr_layer = match env_filter {
+        Some(env_filter) => tracing_subscriber::fmt::layer().with_filter(env_filter).boxed(),
+        None => tracing_subscriber::fmt::layer()
+            .with_filter(filter::filter_fn(level_enabled))
+            .boxed(),
+    };
+
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(|| FileWriter)
+        .with_filter(filter::filter_fn(|metadata| {
+            FILE_ENABLED.load(Ordering::Relaxed) && level_enabled(metadata)
+        }));
+
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(file_layer)
+        .with(BufferLayer.with_filter(LevelFilter::WARN))
+        .init();
+}
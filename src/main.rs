@@ -3,7 +3,7 @@
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 fn main() -> cosmic::iced::Result {
-    tracing_subscriber::fmt::init();
+    cosmic_applet_claude::init_logging();
     let _ = tracing_log::LogTracer::init();
 
     tracing::info!("Starting Claude applet with version {VERSION}");
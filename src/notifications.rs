@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::APP_ID;
+use notify_rust::Notification;
+
+/// Show a desktop notification without blocking the UI thread
+pub fn send(summary: String, body: String) {
+    tokio::task::spawn_blocking(move || {
+        if let Err(err) = Notification::new()
+            .appname("Claude Code")
+            .icon(&format!("{APP_ID}-symbolic"))
+            .summary(&summary)
+            .body(&body)
+            .show()
+        {
+            tracing::error!(?err, "Failed to show notification");
+        }
+    });
+}
+
+/// Show a notification with action buttons, resolving once it is closed.
+/// Yields the id of the action the user picked, if any.
+pub async fn send_with_actions(
+    summary: String,
+    body: String,
+    actions: Vec<(&'static str, String)>,
+) -> Option<&'static str> {
+    tokio::task::spawn_blocking(move || {
+        let mut notification = Notification::new();
+        notification
+            .appname("Claude Code")
+            .icon(&format!("{APP_ID}-symbolic"))
+            .summary(&summary)
+            .body(&body);
+        for (action, label) in &actions {
+            notification.action(action, label);
+        }
+        let handle = notification
+            .show()
+            .map_err(|err| tracing::error!(?err, "Failed to show notification"))
+            .ok()?;
+
+        // The notification server reports the chosen action over D-Bus; closing yields "__closed"
+        let mut invoked = None;
+        handle.wait_for_action(|id| invoked = actions.iter().map(|(action, _)| *action).find(|action| *action == id));
+        invoked
+    })
+    .await
+    .ok()
+    .flatten()
+}
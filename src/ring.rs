@@ -0,0 +1,389 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::widget::canvas::{self, path::Arc, Frame, Geometry, LineCap, LineDash, Path, Stroke};
+use cosmic::iced::{alignment, mouse, Color, Pixels, Point, Radians, Rectangle};
+use std::f32::consts::PI;
+
+/// How the usage arc is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingStyle {
+    /// Plain solid arc
+    Solid,
+    /// Dashed arc (symbolic warning level)
+    Dashed,
+    /// Solid arc with an extra inner ring (symbolic critical level)
+    Double,
+    /// Full ring with a padlock instead of the label (window exhausted)
+    Locked,
+}
+
+/// Canvas program drawing a circular usage ring scaled to its bounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ring {
+    pub percent: f32,
+    pub color: Color,
+    pub track: Color,
+    pub foreground: Color,
+    pub label: &'static str,
+    pub style: RingStyle,
+}
+
+/// Padlock glyph centered on `center`, marking an exhausted window
+fn draw_padlock(frame: &mut Frame, center: Point, unit: f32, color: Color) {
+    let shackle = Path::new(|b| {
+        b.arc(Arc {
+            center: Point::new(center.x, center.y - 1.5 * unit),
+            radius: 2.5 * unit,
+            start_angle: Radians(PI),
+            end_angle: Radians(2.0 * PI),
+        });
+    });
+    frame.stroke(&shackle, Stroke::default().with_color(color).with_width(1.5 * unit));
+
+    let body = Path::rectangle(
+        Point::new(center.x - 4.0 * unit, center.y - 1.0 * unit),
+        cosmic::iced::Size::new(8.0 * unit, 6.0 * unit),
+    );
+    frame.fill(&body, color);
+}
+
+impl<Message> canvas::Program<Message, cosmic::Theme, cosmic::Renderer> for Ring {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<cosmic::Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        // Geometry is laid out on a 24-unit grid, matching the original SVG viewBox
+        let unit = bounds.width.min(bounds.height) / 24.0;
+        let center = frame.center();
+        let radius = 10.0 * unit;
+        let stroke_width = 3.0 * unit;
+
+        let track = Path::circle(center, radius);
+        frame.stroke(&track, Stroke::default().with_color(self.track).with_width(stroke_width));
+
+        let progress = if self.style == RingStyle::Locked {
+            1.0
+        } else {
+            (self.percent / 100.0).clamp(0.0, 1.0)
+        };
+
+        stroke_progress(
+            &mut frame,
+            center,
+            radius,
+            stroke_width,
+            progress,
+            self.color,
+            self.style == RingStyle::Dashed,
+        );
+
+        if self.style == RingStyle::Double {
+            let inner = Path::circle(center, 6.5 * unit);
+            frame.stroke(&inner, Stroke::default().with_color(self.foreground).with_width(unit));
+        }
+
+        if self.style == RingStyle::Locked {
+            draw_padlock(&mut frame, center, unit, self.foreground);
+        } else {
+            frame.fill_text(canvas::Text {
+                content: self.label.to_string(),
+                position: center,
+                color: self.foreground,
+                size: Pixels(10.0 * unit),
+                align_x: cosmic::iced::widget::text::Alignment::Center,
+                align_y: alignment::Vertical::Center,
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Canvas program drawing a usage window as a battery: an outlined bar filled to the usage,
+/// lying along horizontal panels and standing on vertical ones. Symbolic levels dash or
+/// double the outline, the way they change the ring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Battery {
+    pub percent: f32,
+    pub color: Color,
+    pub track: Color,
+    pub foreground: Color,
+    pub label: &'static str,
+    pub style: RingStyle,
+    /// Fill from the bottom with the terminal on top, for vertical panels
+    pub vertical: bool,
+}
+
+impl<Message> canvas::Program<Message, cosmic::Theme, cosmic::Renderer> for Battery {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<cosmic::Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        // The short side is laid out on the ring's 24-unit grid
+        let unit = bounds.width.min(bounds.height) / 24.0;
+        let outline_width = 1.5 * unit;
+        let terminal = 2.0 * unit;
+        let short = 14.0 * unit;
+        let long = (if self.vertical { bounds.height } else { bounds.width }) - terminal - 2.0 * unit;
+
+        // Rectangle `along` the battery from its base and `across` from its side
+        let rect = |along: f32, across: f32, length: f32, thickness: f32| {
+            if self.vertical {
+                Path::rectangle(
+                    Point::new((bounds.width - short) / 2.0 + across, bounds.height - unit - along - length),
+                    cosmic::iced::Size::new(thickness, length),
+                )
+            } else {
+                Path::rectangle(
+                    Point::new(unit + along, (bounds.height - short) / 2.0 + across),
+                    cosmic::iced::Size::new(length, thickness),
+                )
+            }
+        };
+
+        let dashes = [3.0 * unit, 2.0 * unit];
+        let mut outline = Stroke::default().with_color(self.track).with_width(outline_width);
+        if self.style == RingStyle::Dashed {
+            outline = outline.with_color(self.color);
+            outline.line_dash = LineDash {
+                segments: &dashes,
+                offset: 0,
+            };
+        }
+        frame.stroke(&rect(0.0, 0.0, long, short), outline);
+        frame.fill(&rect(long, short / 4.0, terminal, short / 2.0), self.track);
+
+        let progress = if self.style == RingStyle::Locked {
+            1.0
+        } else {
+            (self.percent / 100.0).clamp(0.0, 1.0)
+        };
+        let inset = 2.5 * unit;
+        if progress > 0.0 {
+            frame.fill(
+                &rect(inset, inset, (long - 2.0 * inset) * progress, short - 2.0 * inset),
+                self.color,
+            );
+        }
+        if self.style == RingStyle::Double {
+            let gap = 1.5 * unit;
+            frame.stroke(
+                &rect(gap, gap, long - 2.0 * gap, short - 2.0 * gap),
+                Stroke::default().with_color(self.foreground).with_width(0.75 * unit),
+            );
+        }
+
+        let center = if self.vertical {
+            Point::new(bounds.width / 2.0, bounds.height - unit - long / 2.0)
+        } else {
+            Point::new(unit + long / 2.0, bounds.height / 2.0)
+        };
+        if self.style == RingStyle::Locked {
+            draw_padlock(&mut frame, center, 0.8 * unit, self.foreground);
+        } else {
+            frame.fill_text(canvas::Text {
+                content: self.label.to_string(),
+                position: center,
+                color: self.foreground,
+                size: Pixels(9.0 * unit),
+                align_x: cosmic::iced::widget::text::Alignment::Center,
+                align_y: alignment::Vertical::Center,
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Stroke a usage arc from 12 o'clock clockwise over `progress` (0-1) of the circle
+fn stroke_progress(
+    frame: &mut Frame,
+    center: Point,
+    radius: f32,
+    width: f32,
+    progress: f32,
+    color: Color,
+    dashed: bool,
+) {
+    if progress <= 0.0 {
+        return;
+    }
+    let start = -PI / 2.0;
+    let arc = Path::new(|b| {
+        b.arc(Arc {
+            center,
+            radius,
+            start_angle: Radians(start),
+            end_angle: Radians(start + progress * 2.0 * PI),
+        });
+    });
+
+    // Dash lengths scale with the stroke, which is 3 units wide in the single ring
+    let dashes = [width, width * 2.0 / 3.0];
+    let mut stroke = Stroke::default()
+        .with_color(color)
+        .with_width(width)
+        .with_line_cap(LineCap::Round);
+    if dashed {
+        stroke.line_cap = LineCap::Butt;
+        stroke.line_dash = LineDash {
+            segments: &dashes,
+            offset: 0,
+        };
+    }
+    frame.stroke(&arc, stroke);
+}
+
+/// One window's arc in [`ConcentricRings`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RingArc {
+    pub percent: f32,
+    pub color: Color,
+    pub style: RingStyle,
+}
+
+/// Canvas program drawing session and weekly usage as two concentric arcs in one glyph:
+/// session outside, weekly inside. There is no room for labels; a critical level in
+/// symbolic mode fills the center instead of doubling the ring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcentricRings {
+    pub outer: RingArc,
+    pub inner: RingArc,
+    pub track: Color,
+    pub foreground: Color,
+}
+
+impl<Message> canvas::Program<Message, cosmic::Theme, cosmic::Renderer> for ConcentricRings {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<cosmic::Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        // Same 24-unit grid as the single ring
+        let unit = bounds.width.min(bounds.height) / 24.0;
+        let center = frame.center();
+        let stroke_width = 2.5 * unit;
+
+        for (arc, radius) in [(self.outer, 10.5 * unit), (self.inner, 6.5 * unit)] {
+            frame.stroke(
+                &Path::circle(center, radius),
+                Stroke::default().with_color(self.track).with_width(stroke_width),
+            );
+            let progress = if arc.style == RingStyle::Locked {
+                1.0
+            } else {
+                (arc.percent / 100.0).clamp(0.0, 1.0)
+            };
+            stroke_progress(
+                &mut frame,
+                center,
+                radius,
+                stroke_width,
+                progress,
+                arc.color,
+                arc.style == RingStyle::Dashed,
+            );
+        }
+
+        if self.outer.style == RingStyle::Double || self.inner.style == RingStyle::Double {
+            frame.fill(&Path::circle(center, 2.5 * unit), self.foreground);
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Canvas program drawing a filled count badge scaled to its bounds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Badge {
+    pub label: String,
+    pub color: Color,
+    pub foreground: Color,
+}
+
+impl<Message> canvas::Program<Message, cosmic::Theme, cosmic::Renderer> for Badge {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<cosmic::Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let center = frame.center();
+        let radius = bounds.width.min(bounds.height) / 2.0;
+
+        frame.fill(&Path::circle(center, radius), self.color);
+        frame.fill_text(canvas::Text {
+            content: self.label.clone(),
+            position: center,
+            color: self.foreground,
+            size: Pixels(radius * 1.4),
+            align_x: cosmic::iced::widget::text::Alignment::Center,
+            align_y: alignment::Vertical::Center,
+            ..canvas::Text::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Canvas program drawing vertical tick marks across a horizontal bar, e.g. at alert thresholds
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarMarkers {
+    /// Positions along the bar (0-100) and their colors
+    pub marks: Vec<(f32, Color)>,
+}
+
+impl<Message> canvas::Program<Message, cosmic::Theme, cosmic::Renderer> for BarMarkers {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<cosmic::Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let width = 2.0;
+
+        for &(percent, color) in &self.marks {
+            // Keep the whole tick inside the bar at 0% and 100%
+            let x = (bounds.width * percent.clamp(0.0, 100.0) / 100.0).clamp(width / 2.0, bounds.width - width / 2.0);
+            let tick = Path::line(Point::new(x, 0.0), Point::new(x, bounds.height));
+            frame.stroke(&tick, Stroke::default().with_width(width).with_color(color));
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
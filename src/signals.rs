@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::{futures::SinkExt, Subscription};
+use cosmic::iced_futures::stream;
+use tokio::signal::unix::{signal, SignalKind};
+
+/// Requests sent to the applet with Unix signals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalRequest {
+    /// SIGUSR1: poll usage now
+    Refresh,
+    /// SIGUSR2: open or close the popup
+    TogglePopup,
+}
+
+/// Subscription that turns SIGUSR1 and SIGUSR2 into requests, e.g. from a Claude Code Stop hook running
+/// `pkill -USR1 -f cosmic-applet-claude`
+pub fn signal_subscription() -> Subscription<SignalRequest> {
+    Subscription::run_with_id(
+        "claude-signals",
+        stream::channel(4, |mut output| async move {
+            let (mut usr1, mut usr2) = match (signal(SignalKind::user_defined1()), signal(SignalKind::user_defined2())) {
+                (Ok(usr1), Ok(usr2)) => (usr1, usr2),
+                (Err(err), _) | (_, Err(err)) => {
+                    tracing::error!(?err, "Failed to install signal handlers");
+                    return;
+                }
+            };
+
+            loop {
+                let request = tokio::select! {
+                    Some(()) = usr1.recv() => SignalRequest::Refresh,
+                    Some(()) = usr2.recv() => SignalRequest::TogglePopup,
+                    else => return,
+                };
+                tracing::debug!("Received {:?} signal request", request);
+                let _ = output.send(request).await;
+            }
+        }),
+    )
+}
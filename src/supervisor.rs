@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::BTreeMap;
+
+/// Shortest wait before a stalled stream is restarted again
+const MIN_BACKOFF_MINUTES: i64 = 1;
+
+/// Longest wait between restarts of a stream that keeps stalling
+const MAX_BACKOFF_MINUTES: i64 = 30;
+
+/// Backend streams watched by the supervisor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StreamKind {
+    UsageApi,
+    OrgUsage,
+    Stats,
+    Transcripts,
+    Processes,
+    ServiceStatus,
+}
+
+impl StreamKind {
+    /// How long the stream may stay silent before it counts as stalled
+    fn stall_timeout(self, poll_interval_minutes: u32) -> Duration {
+        let interval = match self {
+            StreamKind::UsageApi | StreamKind::OrgUsage => Duration::minutes(poll_interval_minutes.max(1) as i64),
+            StreamKind::Stats => Duration::seconds(30),
+            StreamKind::Transcripts => Duration::seconds(60),
+            StreamKind::Processes => Duration::seconds(5),
+            StreamKind::ServiceStatus => Duration::minutes(5),
+        };
+        // Allow for slow requests and scans before declaring the stream dead
+        (interval * 3).max(Duration::minutes(2))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct StreamState {
+    /// When the stream last delivered an update
+    last_update: Option<DateTime<Utc>>,
+    /// When the stream was (re)started, the baseline until it delivers
+    started: Option<DateTime<Utc>>,
+    /// Part of the subscription id; bumping it restarts the stream
+    generation: u64,
+    /// Restarts since the last successful update, drives the backoff
+    restarts: u32,
+    stalled: bool,
+}
+
+/// Tracks when each backend stream last delivered and restarts the ones that went silent
+#[derive(Debug, Default)]
+pub struct Supervisor {
+    streams: BTreeMap<StreamKind, StreamState>,
+}
+
+impl Supervisor {
+    /// Note that a stream delivered an update
+    pub fn record(&mut self, kind: StreamKind) {
+        let state = self.streams.entry(kind).or_default();
+        state.last_update = Some(Utc::now());
+        state.restarts = 0;
+        state.stalled = false;
+    }
+
+    /// Restart generation to include in the stream's subscription id
+    pub fn generation(&self, kind: StreamKind) -> u64 {
+        self.streams.get(&kind).map_or(0, |state| state.generation)
+    }
+
+    pub fn last_update(&self, kind: StreamKind) -> Option<DateTime<Utc>> {
+        self.streams.get(&kind).and_then(|state| state.last_update)
+    }
+
+    pub fn is_stalled(&self, kind: StreamKind) -> bool {
+        self.streams.get(&kind).is_some_and(|state| state.stalled)
+    }
+
+    pub fn restarts(&self, kind: StreamKind) -> u32 {
+        self.streams.get(&kind).map_or(0, |state| state.restarts)
+    }
+
+    /// Check the active streams and restart any that stalled; returns the restarted streams
+    pub fn check(&mut self, active: &[StreamKind], poll_interval_minutes: u32) -> Vec<StreamKind> {
+        let now = Utc::now();
+        let mut restarted = Vec::new();
+
+        for &kind in active {
+            let state = self.streams.entry(kind).or_default();
+            // Streams that just became active start their clock now
+            let Some(baseline) = state.last_update.max(state.started) else {
+                state.started = Some(now);
+                continue;
+            };
+
+            if now - baseline < kind.stall_timeout(poll_interval_minutes) {
+                continue;
+            }
+            state.stalled = true;
+
+            // Back off exponentially between restarts of the same stream
+            let backoff = Duration::minutes((MIN_BACKOFF_MINUTES << state.restarts.min(5)).min(MAX_BACKOFF_MINUTES));
+            if state.started.is_some_and(|started| now - started < backoff) {
+                continue;
+            }
+
+            state.generation = state.generation.wrapping_add(1);
+            state.restarts = state.restarts.saturating_add(1);
+            state.started = Some(now);
+            restarted.push(kind);
+        }
+
+        restarted
+    }
+}